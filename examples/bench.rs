@@ -0,0 +1,131 @@
+//! A benchmark harness timing the construction and evaluation of the splines at several sizes,
+//! next to the work counted by the `profiling` feature: binary searches, segments evaluated and
+//! heap allocations.
+//!
+//! Each case runs a few warm-up iterations and reports the median time of the rest, so the
+//! numbers are comparable to those of another interpolator run through the same harness.
+//!
+//! Run with `cargo run --release --example bench --features profiling`.
+
+use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use spline_interpolation::profiling::{self, Counters, CountingAllocator};
+use std::alloc::System;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator<System> = CountingAllocator::new(System);
+
+const SIZES: [usize; 4] = [10, 100, 1_000, 10_000];
+const QUERIES: usize = 10_000;
+const WARM_UP: usize = 3;
+const ITERATIONS: usize = 15;
+
+/// Runs `f` repeatedly, returning the median time and the counters of one run.
+fn bench<R>(mut f: impl FnMut() -> R) -> (Duration, Counters) {
+    for _ in 0..WARM_UP {
+        black_box(f());
+    }
+    let mut times = Vec::with_capacity(ITERATIONS);
+    let mut counters = Counters::default();
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let (result, counted) = profiling::measure(&mut f);
+        times.push(start.elapsed());
+        black_box(result);
+        counters = counted;
+    }
+    times.sort();
+    (times[ITERATIONS / 2], counters)
+}
+
+fn report(case: &str, n: usize, per: usize, (time, counters): (Duration, Counters)) {
+    let per = per as f64;
+    println!(
+        "{case:<24} {n:>6} {:>12.1} {:>10.2} {:>10.2} {:>10.2} {:>12.1}",
+        time.as_nanos() as f64 / per,
+        counters.binary_searches as f64 / per,
+        counters.segments_evaluated as f64 / per,
+        counters.allocations as f64 / per,
+        counters.allocated_bytes as f64 / per,
+    );
+}
+
+fn main() {
+    println!(
+        "{:<24} {:>6} {:>12} {:>10} {:>10} {:>10} {:>12}",
+        "case", "n", "ns/op", "searches", "segments", "allocs", "bytes"
+    );
+    for n in SIZES {
+        // irregular knots, so the lookups search rather than take the uniform fast path
+        let points: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let x = i as f64 + 0.3 * (i as f64 * 0.7).sin();
+                (x, x.cos())
+            })
+            .collect();
+        let slopes: Vec<_> = points.iter().map(|&(x, y)| (x, y, -x.sin())).collect();
+        let last = points[n - 1].0;
+        let queries: Vec<f64> = (0..QUERIES)
+            .map(|k| last * k as f64 / (QUERIES - 1) as f64)
+            .collect();
+
+        report(
+            "natural cubic / build",
+            n,
+            1,
+            bench(|| NaturalCubicSpline::try_new(&points).unwrap()),
+        );
+        report(
+            "hermite / build",
+            n,
+            1,
+            bench(|| HermiteSpline::try_new(&slopes).unwrap()),
+        );
+        report(
+            "catmull-rom / build",
+            n,
+            1,
+            bench(|| CatmullRomSpline::try_new(&points).unwrap()),
+        );
+
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        let hermite = HermiteSpline::try_new(&slopes).unwrap();
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        report(
+            "natural cubic / value",
+            n,
+            QUERIES,
+            bench(|| {
+                queries
+                    .iter()
+                    .map(|&x| natural.try_value(x).unwrap())
+                    .sum::<f64>()
+            }),
+        );
+        report(
+            "hermite / value",
+            n,
+            QUERIES,
+            bench(|| {
+                queries
+                    .iter()
+                    .map(|&x| hermite.try_value(x).unwrap())
+                    .sum::<f64>()
+            }),
+        );
+        report(
+            "catmull-rom / value",
+            n,
+            QUERIES,
+            bench(|| {
+                queries
+                    .iter()
+                    .map(|&x| catmull_rom.try_value(x).unwrap())
+                    .sum::<f64>()
+            }),
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Building strongly-typed curve APIs on top of the generic splines.
+//!
+//! The splines in this crate are generic over a single scalar type `V: InterpolationValue`.
+//! This example shows two ways of keeping physical units in the type system:
+//!
+//! * `Quantity<U>` is a newtype over `f64` tagged with a unit marker `U`. Blanket impls over
+//!   every `U: Unit` provide the arithmetic traits required by `InterpolationValue`, so a
+//!   quantity can be fed to a spline directly (e.g. a temperature calibration curve).
+//! * `TypedCurve<X, Y>` maps one unit onto another (e.g. `Time -> Value`) by storing the raw
+//!   `f64` spline and converting at the boundary.
+//!
+//! Run with `cargo run --example typed_units`.
+
+use num_traits::{FromPrimitive, Num, One, Zero};
+use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use spline_interpolation::{InterpolationError, InterpolationValue};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+/// A marker type naming a physical unit.
+trait Unit: 'static + Copy + Debug + Send + Sync {
+    const SYMBOL: &'static str;
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Seconds;
+
+impl Unit for Seconds {
+    const SYMBOL: &'static str = "s";
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Kelvin;
+
+impl Unit for Kelvin {
+    const SYMBOL: &'static str = "K";
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Joules;
+
+impl Unit for Joules {
+    const SYMBOL: &'static str = "J";
+}
+
+/// An `f64` tagged with the unit `U`.
+struct Quantity<U: Unit>(f64, PhantomData<U>);
+
+impl<U: Unit> Quantity<U> {
+    const fn new(value: f64) -> Self {
+        Self(value, PhantomData)
+    }
+
+    const fn get(self) -> f64 {
+        self.0
+    }
+}
+
+type Time = Quantity<Seconds>;
+type Temperature = Quantity<Kelvin>;
+type Value = Quantity<Joules>;
+
+// The derives would put bounds on `U` itself, so the trivial traits are implemented by hand.
+impl<U: Unit> Clone for Quantity<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U: Unit> Copy for Quantity<U> {}
+
+impl<U: Unit> Debug for Quantity<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}", self.0, U::SYMBOL)
+    }
+}
+
+impl<U: Unit> Display for Quantity<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.0, U::SYMBOL)
+    }
+}
+
+impl<U: Unit> PartialEq for Quantity<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<U: Unit> PartialOrd for Quantity<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+macro_rules! impl_binary_ops {
+    ($($op:ident::$method:ident, $op_assign:ident::$method_assign:ident);* $(;)?) => {
+        $(
+            impl<U: Unit> $op for Quantity<U> {
+                type Output = Self;
+
+                fn $method(self, rhs: Self) -> Self {
+                    Self::new(self.0.$method(rhs.0))
+                }
+            }
+
+            impl<U: Unit> $op_assign for Quantity<U> {
+                fn $method_assign(&mut self, rhs: Self) {
+                    self.0.$method_assign(rhs.0);
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_ops! {
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+    Div::div, DivAssign::div_assign;
+}
+
+impl<U: Unit> Rem for Quantity<U> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        Self::new(self.0 % rhs.0)
+    }
+}
+
+impl<U: Unit> Neg for Quantity<U> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.0)
+    }
+}
+
+impl<U: Unit> Zero for Quantity<U> {
+    fn zero() -> Self {
+        Self::new(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<U: Unit> One for Quantity<U> {
+    fn one() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl<U: Unit> Num for Quantity<U> {
+    type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        f64::from_str_radix(str, radix).map(Self::new)
+    }
+}
+
+impl<U: Unit> FromPrimitive for Quantity<U> {
+    fn from_i64(n: i64) -> Option<Self> {
+        f64::from_i64(n).map(Self::new)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        f64::from_u64(n).map(Self::new)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        Some(Self::new(n))
+    }
+}
+
+impl<U: Unit> InterpolationValue for Quantity<U> {}
+
+/// A curve from `X` to `Y` built on an untyped natural cubic spline.
+struct TypedCurve<X: Unit, Y: Unit> {
+    spline: NaturalCubicSpline<f64>,
+    _units: PhantomData<(X, Y)>,
+}
+
+impl<X: Unit, Y: Unit> TypedCurve<X, Y> {
+    fn try_new(points: &[(Quantity<X>, Quantity<Y>)]) -> Result<Self, InterpolationError<f64>> {
+        let raw_points: Vec<_> = points.iter().map(|&(x, y)| (x.get(), y.get())).collect();
+        Ok(Self {
+            spline: NaturalCubicSpline::try_new(&raw_points)?,
+            _units: PhantomData,
+        })
+    }
+
+    fn try_value(self, x: Quantity<X>) -> Result<Quantity<Y>, InterpolationError<f64>> {
+        self.spline.try_value(x.get()).map(Quantity::new)
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // A sensor calibration curve: both axes are temperatures, so the spline works on the
+    // quantity type directly and the error carries the offending temperature.
+    let calibration = [
+        (Temperature::new(273.15), Temperature::new(273.0)),
+        (Temperature::new(293.15), Temperature::new(293.4)),
+        (Temperature::new(313.15), Temperature::new(313.9)),
+        (Temperature::new(333.15), Temperature::new(334.1)),
+    ];
+    let measured = Temperature::new(300.0);
+    let corrected = CatmullRomSpline::try_new(&calibration)?.try_value(measured)?;
+    println!("measured {measured} -> corrected {corrected}");
+
+    // An energy profile over time: the axes carry different units, so the typed curve converts
+    // at the boundary and the compiler rejects mixing them up.
+    let profile = [
+        (Time::new(0.0), Value::new(0.0)),
+        (Time::new(1.0), Value::new(4.5)),
+        (Time::new(2.0), Value::new(6.0)),
+        (Time::new(3.0), Value::new(6.5)),
+    ];
+    let t = Time::new(1.5);
+    let energy = TypedCurve::try_new(&profile)?.try_value(t)?;
+    println!("energy at {t} is {energy}");
+    Ok(())
+}
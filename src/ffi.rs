@@ -0,0 +1,214 @@
+//! A C interface to natural cubic splines over `f64`, for embedding in C and C++ code.
+//!
+//! A spline is built from two arrays with [`spline_natural_cubic_new`], evaluated with
+//! [`spline_value`] and [`spline_values`], and released with [`spline_free`]. Every fallible
+//! function returns a [`SplineStatus`], writing its result through an out pointer on success only.
+//!
+//! The crate builds as an `rlib`; link it from C as a `cdylib` or `staticlib`, e.g. with
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+
+// the only module allowed to use unsafe code, to read and write the pointers C passes in
+#![allow(unsafe_code)]
+
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::InterpolationError;
+use std::slice;
+
+/// The outcome of an FFI call, one code per [`InterpolationError`] variant a natural cubic spline
+/// can report.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplineStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// See [`InterpolationError::PointOrderError`].
+    PointOrder = 2,
+    /// See [`InterpolationError::DuplicatePointError`].
+    DuplicatePoint = 3,
+    /// See [`InterpolationError::NonFiniteError`].
+    NonFinitePoint = 4,
+    /// See [`InterpolationError::InsufficientPointsError`].
+    InsufficientPoints = 5,
+    /// See [`InterpolationError::OutOfLowerBound`].
+    OutOfLowerBound = 6,
+    /// See [`InterpolationError::OutOfUpperBound`].
+    OutOfUpperBound = 7,
+    /// See [`InterpolationError::NonFiniteInput`].
+    NonFiniteQuery = 8,
+    /// See [`InterpolationError::SolverError`].
+    Solver = 9,
+    /// Any other error.
+    Other = 255,
+}
+
+impl From<InterpolationError<f64>> for SplineStatus {
+    fn from(error: InterpolationError<f64>) -> Self {
+        match error {
+            InterpolationError::PointOrderError(_) => SplineStatus::PointOrder,
+            InterpolationError::DuplicatePointError(_) => SplineStatus::DuplicatePoint,
+            InterpolationError::NonFiniteError(_) => SplineStatus::NonFinitePoint,
+            InterpolationError::InsufficientPointsError(_) => SplineStatus::InsufficientPoints,
+            InterpolationError::OutOfLowerBound(_) => SplineStatus::OutOfLowerBound,
+            InterpolationError::OutOfUpperBound(_) => SplineStatus::OutOfUpperBound,
+            InterpolationError::NonFiniteInput(_) => SplineStatus::NonFiniteQuery,
+            InterpolationError::SolverError(_) => SplineStatus::Solver,
+            _ => SplineStatus::Other,
+        }
+    }
+}
+
+/// Builds the natural cubic spline through the `len` points `(xs[i], ys[i])` into `*out`.
+///
+/// # Safety
+///
+/// `xs` and `ys` must point to `len` readable values each and `out` to a writable pointer. The
+/// spline written to `*out` must be released with [`spline_free`] exactly once.
+#[no_mangle]
+pub unsafe extern "C" fn spline_natural_cubic_new(
+    xs: *const f64,
+    ys: *const f64,
+    len: usize,
+    out: *mut *mut NaturalCubicSpline<f64>,
+) -> SplineStatus {
+    if xs.is_null() || ys.is_null() || out.is_null() {
+        return SplineStatus::NullPointer;
+    }
+    let (xs, ys) = (
+        slice::from_raw_parts(xs, len),
+        slice::from_raw_parts(ys, len),
+    );
+    match NaturalCubicSpline::try_new_xy(xs, ys) {
+        Ok(spline) => {
+            *out = Box::into_raw(Box::new(spline));
+            SplineStatus::Ok
+        }
+        Err(error) => error.into(),
+    }
+}
+
+/// Evaluates `spline` at `x` into `*out`.
+///
+/// # Safety
+///
+/// `spline` must come from [`spline_natural_cubic_new`] and not be freed yet, and `out` must
+/// point to a writable value.
+#[no_mangle]
+pub unsafe extern "C" fn spline_value(
+    spline: *const NaturalCubicSpline<f64>,
+    x: f64,
+    out: *mut f64,
+) -> SplineStatus {
+    if spline.is_null() || out.is_null() {
+        return SplineStatus::NullPointer;
+    }
+    match (*spline).try_value(x) {
+        Ok(value) => {
+            *out = value;
+            SplineStatus::Ok
+        }
+        Err(error) => error.into(),
+    }
+}
+
+/// Evaluates `spline` at the `len` queries `xs[i]` into `out[i]`, stopping at the first error,
+/// with `out` then partially written.
+///
+/// # Safety
+///
+/// `spline` must come from [`spline_natural_cubic_new`] and not be freed yet, `xs` must point to
+/// `len` readable values and `out` to `len` writable ones.
+#[no_mangle]
+pub unsafe extern "C" fn spline_values(
+    spline: *const NaturalCubicSpline<f64>,
+    xs: *const f64,
+    len: usize,
+    out: *mut f64,
+) -> SplineStatus {
+    if spline.is_null() || xs.is_null() || out.is_null() {
+        return SplineStatus::NullPointer;
+    }
+    let (xs, out) = (
+        slice::from_raw_parts(xs, len),
+        slice::from_raw_parts_mut(out, len),
+    );
+    for (&x, value) in xs.iter().zip(out) {
+        match (*spline).try_value(x) {
+            Ok(v) => *value = v,
+            Err(error) => return error.into(),
+        }
+    }
+    SplineStatus::Ok
+}
+
+/// Releases a spline built by [`spline_natural_cubic_new`]; a null `spline` is ignored.
+///
+/// # Safety
+///
+/// `spline` must be null or come from [`spline_natural_cubic_new`] and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn spline_free(spline: *mut NaturalCubicSpline<f64>) {
+    if !spline.is_null() {
+        drop(Box::from_raw(spline));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ffi::{
+        spline_free, spline_natural_cubic_new, spline_value, spline_values, SplineStatus,
+    };
+    use std::ptr;
+
+    #[test]
+    fn test_round_trip() {
+        let (xs, ys) = ([0.0, 0.5, 1.0], [1.0, 0.5, 0.0]);
+        let mut spline = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                spline_natural_cubic_new(xs.as_ptr(), ys.as_ptr(), 3, &mut spline),
+                SplineStatus::Ok
+            );
+            let mut value = 0.0;
+            assert_eq!(spline_value(spline, 0.75, &mut value), SplineStatus::Ok);
+            assert_eq!(value, 0.25);
+            let mut values = [0.0; 3];
+            assert_eq!(
+                spline_values(spline, [0.0, 0.25, 1.0].as_ptr(), 3, values.as_mut_ptr()),
+                SplineStatus::Ok
+            );
+            assert_eq!(values, [1.0, 0.75, 0.0]);
+            assert_eq!(
+                spline_value(spline, 1.5, &mut value),
+                SplineStatus::OutOfUpperBound
+            );
+            assert_eq!(
+                spline_values(spline, [0.5, f64::NAN].as_ptr(), 2, values.as_mut_ptr()),
+                SplineStatus::NonFiniteQuery
+            );
+            spline_free(spline);
+            spline_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_construction_errors() {
+        let mut spline = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                spline_natural_cubic_new([1.0, 0.0].as_ptr(), [0.0, 0.0].as_ptr(), 2, &mut spline),
+                SplineStatus::PointOrder
+            );
+            assert_eq!(
+                spline_natural_cubic_new([0.0].as_ptr(), [0.0].as_ptr(), 1, &mut spline),
+                SplineStatus::InsufficientPoints
+            );
+            assert_eq!(
+                spline_natural_cubic_new(ptr::null(), [0.0].as_ptr(), 1, &mut spline),
+                SplineStatus::NullPointer
+            );
+        }
+        assert!(spline.is_null());
+    }
+}
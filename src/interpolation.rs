@@ -1,3 +1,649 @@
+pub(crate) mod arc_length;
+#[cfg(feature = "ndarray")]
+pub mod array;
+pub mod bezier;
+pub mod binary;
+pub mod builder;
+pub mod bundle;
 pub mod catmull_rom_spline;
+#[cfg(feature = "complex")]
+pub mod complex;
+pub mod composite;
+pub mod continuity;
+pub mod dedup;
+pub mod difference;
+pub mod error_bound;
+#[cfg(feature = "gp")]
+pub mod gp;
+pub mod grid_2d;
+pub mod grid_nd;
 pub mod hermite_spline;
+pub mod interpolator;
+#[cfg(feature = "io")]
+pub mod io;
 pub mod natural_cubic_spline;
+pub mod newton;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod parametric;
+pub mod partition;
+pub mod piecewise_polynomial;
+#[cfg(feature = "plotters")]
+pub mod plot;
+pub(crate) mod pretty;
+pub mod rbf;
+pub mod sample;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod shape;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod slopes;
+pub mod smoothing_spline;
+pub mod streaming;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "chrono")]
+pub mod time_axis;
+pub mod transformed;
+pub mod vector_field;
+
+use crate::profiling::record_binary_search;
+use crate::tolerance::Tolerance;
+use crate::{InterpolationError, InterpolationValue};
+
+/// Where a query falls relative to the knots of a spline.
+pub(crate) enum Location {
+    /// The query matches the knot with this index under the spline's tolerance.
+    Knot(usize),
+    /// The query lies strictly inside the segment starting at the knot with this index.
+    Segment(usize),
+}
+
+/// Locates `x` among the sorted knots of `points`, whose x-coordinates are read by `x_of`.
+///
+/// Returns `NonFiniteInput(x)` for a NaN or infinite query, which leaves the search total on the
+/// finite knots.
+pub(crate) fn locate<P, V: InterpolationValue>(
+    points: &[P],
+    x_of: impl Fn(&P) -> V,
+    x: V,
+    tolerance: &Tolerance<V>,
+) -> Result<Location, InterpolationError<V>> {
+    if !x.is_finite() {
+        return Err(InterpolationError::NonFiniteInput(x));
+    }
+    record_binary_search();
+    match points.binary_search_by(|point| {
+        x_of(point)
+            .partial_cmp(&x)
+            .expect("finite knots and queries are ordered")
+    }) {
+        Ok(pos) => Ok(Location::Knot(pos)),
+        Err(pos) => {
+            if pos > 0 && tolerance.eq(x_of(&points[pos - 1]), x) {
+                return Ok(Location::Knot(pos - 1));
+            }
+            if pos < points.len() && tolerance.eq(x_of(&points[pos]), x) {
+                return Ok(Location::Knot(pos));
+            }
+            if pos == 0 {
+                return Err(InterpolationError::OutOfLowerBound(x));
+            }
+            if pos == points.len() {
+                return Err(InterpolationError::OutOfUpperBound(x));
+            }
+            Ok(Location::Segment(pos - 1))
+        }
+    }
+}
+
+/// Yields the uniformly spaced knots `x0 + i dx`.
+pub(crate) fn uniform_knots<V: InterpolationValue>(x0: V, dx: V) -> impl Iterator<Item = V> {
+    (0..).map(move |i| x0 + V::from_count(i) * dx)
+}
+
+/// Locates `x` as [`locate`] does, but for knots built with the uniform `spacing` `(x0, dx)`
+/// starts from the segment `⌊(x - x0) / dx⌋` and only searches its neighbourhood, which absorbs the
+/// rounding of both the division and the knots.
+///
+/// Falls back to [`locate`] without a spacing, or if the guess misses.
+pub(crate) fn locate_uniform<P, V: InterpolationValue>(
+    points: &[P],
+    x_of: impl Fn(&P) -> V,
+    spacing: Option<(V, V)>,
+    x: V,
+    tolerance: &Tolerance<V>,
+) -> Result<Location, InterpolationError<V>> {
+    if let Some(guess) = spacing.and_then(|(x0, dx)| ((x - x0) / dx).floor_to_usize()) {
+        let last = points.len() - 1;
+        let guess = guess.min(last);
+        let (lo, hi) = (guess.saturating_sub(1), (guess + 2).min(last));
+        if x_of(&points[lo]) <= x && x <= x_of(&points[hi]) {
+            return Ok(match locate(&points[lo..=hi], &x_of, x, tolerance)? {
+                Location::Knot(i) => Location::Knot(lo + i),
+                Location::Segment(i) => Location::Segment(lo + i),
+            });
+        }
+    }
+    locate(points, x_of, x, tolerance)
+}
+
+/// Checks that every coordinate of the `points` is finite.
+///
+/// Returns `NonFiniteError(i)` for the first point with a NaN or infinite coordinate.
+pub(crate) fn check_finite<V: InterpolationValue, const N: usize>(
+    points: impl IntoIterator<Item = [V; N]>,
+) -> Result<(), InterpolationError<V>> {
+    match points
+        .into_iter()
+        .position(|point| !point.iter().all(|&v| v.is_finite()))
+    {
+        Some(i) => Err(InterpolationError::NonFiniteError(i)),
+        None => Ok(()),
+    }
+}
+
+/// Checks that the knots `xs` are finite and strictly ascending.
+///
+/// Returns `NonFiniteError(i)`, `DuplicatePointError(x)` or `PointOrderError(i)` for the first
+/// offending knot.
+pub(crate) fn check_knots<V: InterpolationValue>(
+    xs: impl IntoIterator<Item = V>,
+) -> Result<(), InterpolationError<V>> {
+    let mut previous = None;
+    for (i, x) in xs.into_iter().enumerate() {
+        if !x.is_finite() {
+            return Err(InterpolationError::NonFiniteError(i));
+        }
+        check_append(previous, i, x)?;
+        previous = Some(x);
+    }
+    Ok(())
+}
+
+/// Checks that a point at `x` can be appended as the `index`-th point after the x-coordinate
+/// `last`.
+///
+/// Returns `DuplicatePointError(x)` if `x` equals `last` and `PointOrderError(index)` if it is
+/// smaller.
+pub(crate) fn check_append<V: InterpolationValue>(
+    last: Option<V>,
+    index: usize,
+    x: V,
+) -> Result<(), InterpolationError<V>> {
+    match last {
+        Some(last) if x == last => Err(InterpolationError::DuplicatePointError(x)),
+        Some(last) if x < last => Err(InterpolationError::PointOrderError(index)),
+        _ => Ok(()),
+    }
+}
+
+/// Finds where the point `[x, ...]` would be inserted into the sorted `knots`.
+///
+/// Returns `NonFiniteError(n)` if a coordinate of the point, which would be the `n`-th, is NaN or
+/// infinite, and `DuplicatePointError(x)` if a knot at `x` already exists.
+pub(crate) fn insertion_index<V: InterpolationValue, const N: usize>(
+    knots: &[V],
+    point: [V; N],
+) -> Result<usize, InterpolationError<V>> {
+    if !point.iter().all(|&v| v.is_finite()) {
+        return Err(InterpolationError::NonFiniteError(knots.len()));
+    }
+    let x = point[0];
+    record_binary_search();
+    let i = knots.partition_point(|&knot| knot < x);
+    if knots.get(i) == Some(&x) {
+        return Err(InterpolationError::DuplicatePointError(x));
+    }
+    Ok(i)
+}
+
+/// Checks that removing the `index`-th of `len` knots leaves at least `min_len` of them.
+///
+/// # Panics
+///
+/// Panics if `index >= len`.
+pub(crate) fn check_removal<V: InterpolationValue>(
+    len: usize,
+    index: usize,
+    min_len: usize,
+) -> Result<(), InterpolationError<V>> {
+    assert!(index < len, "removal index {index} should be < len {len}");
+    if len - 1 < min_len {
+        return Err(InterpolationError::InsufficientPointsError(len - 1));
+    }
+    Ok(())
+}
+
+/// Finds the segment of the sorted `knots` containing `x`.
+///
+/// A knot belongs to the segment on its right except for the last one, which closes the last
+/// segment. Returns `NonFiniteInput(x)` for a NaN or infinite query.
+pub(crate) fn segment_index<V: InterpolationValue>(
+    knots: &[V],
+    x: V,
+) -> Result<usize, InterpolationError<V>> {
+    if !x.is_finite() {
+        return Err(InterpolationError::NonFiniteInput(x));
+    }
+    let last = knots.len() - 1;
+    if x < knots[0] {
+        return Err(InterpolationError::OutOfLowerBound(x));
+    }
+    if x > knots[last] {
+        return Err(InterpolationError::OutOfUpperBound(x));
+    }
+    Ok(segment_index_unchecked(knots, x))
+}
+
+/// Finds the segment of the sorted `knots` containing `x` as [`segment_index`] does, without
+/// checking `x` outside debug builds, where the end segments take the queries beyond them.
+pub(crate) fn segment_index_unchecked<V: InterpolationValue>(knots: &[V], x: V) -> usize {
+    let last = knots.len() - 1;
+    debug_assert!(
+        knots[0] <= x && x <= knots[last],
+        "query {x:?} should lie within the knots"
+    );
+    record_binary_search();
+    knots.partition_point(|&knot| knot <= x).clamp(1, last) - 1
+}
+
+/// Finds the knot of the sorted `knots` nearest to `x`, the lower one of two equally near.
+///
+/// Returns `NonFiniteInput(x)` for a NaN or infinite query.
+pub(crate) fn nearest_knot<V: InterpolationValue>(
+    knots: &[V],
+    x: V,
+) -> Result<usize, InterpolationError<V>> {
+    if !x.is_finite() {
+        return Err(InterpolationError::NonFiniteInput(x));
+    }
+    record_binary_search();
+    let i = knots.partition_point(|&knot| knot < x);
+    Ok(if i == 0 {
+        0
+    } else if i == knots.len() || x - knots[i - 1] <= knots[i] - x {
+        i - 1
+    } else {
+        i
+    })
+}
+
+/// Returns the cubic Hermite basis functions at `t`: the weights of the values at both ends and
+/// the weights of the (normalized) slopes at both ends.
+pub(crate) fn hermite_basis<V: InterpolationValue>(t: V) -> ([V; 2], [V; 2]) {
+    let two = V::from_integer(2);
+    let three = V::from_integer(3);
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (
+        [two * t3 - three * t2 + V::one(), three * t2 - two * t3],
+        [t3 - two * t2 + t, t3 - t2],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::builder::{Extrapolation, SplineBuilder};
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::interpolator::Interpolator;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::partition::{DensityPartition, RegionMethod};
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::interpolation::slopes::SlopeMethod;
+    use crate::interpolation::streaming::{
+        PointBuilder, StreamingCatmullRomSpline, StreamingHermiteSpline,
+    };
+    use crate::interpolation::uniform_knots;
+    use crate::tolerance::Tolerance;
+    use crate::InterpolationError;
+
+    const XS: [f64; 8] = [0.0, 0.4, 1.0, 1.3, 2.1, 2.5, 2.6, 3.0];
+
+    /// Returns `p(x)` and `p'(x)` for a fixed polynomial `p` of the given degree.
+    fn polynomial(degree: usize, x: f64) -> (f64, f64) {
+        let coefficients = [0.5, -1.0, 0.75, 0.25, -0.125];
+        let value = (0..=degree)
+            .map(|k| coefficients[k] * x.powi(k as i32))
+            .sum();
+        let slope = (1..=degree)
+            .map(|k| coefficients[k] * k as f64 * x.powi(k as i32 - 1))
+            .sum();
+        (value, slope)
+    }
+
+    type Build = dyn Fn(&[(f64, f64, f64)]) -> (usize, Box<dyn Fn(f64) -> f64>);
+
+    /// Checks that an interpolant reproduces polynomials up to its reported degree exactly,
+    /// and no further, on `[0, 2.6]`.
+    fn assert_degree_exactness(build: &Build) {
+        let max_error = |degree| {
+            let data: Vec<_> = XS
+                .iter()
+                .map(|&x| {
+                    let (y, dydx) = polynomial(degree, x);
+                    (x, y, dydx)
+                })
+                .collect();
+            let (exactness, interpolant) = build(&data);
+            let error = (0..=52)
+                .map(|i| i as f64 * 0.05)
+                .map(|x| (interpolant(x) - polynomial(degree, x).0).abs())
+                .fold(0.0, f64::max);
+            (exactness, error)
+        };
+        let (exactness, _) = max_error(0);
+        for degree in 0..=exactness {
+            assert!(max_error(degree).1 < 1e-12);
+        }
+        assert!(max_error(exactness + 1).1 > 1e-6);
+    }
+
+    fn xy(data: &[(f64, f64, f64)]) -> Vec<(f64, f64)> {
+        data.iter().map(|&(x, y, _)| (x, y)).collect()
+    }
+
+    #[test]
+    fn test_degree_exactness() {
+        assert_degree_exactness(&|data| {
+            let spline = HermiteSpline::try_new(data).unwrap();
+            (
+                spline.degree_exactness(),
+                Box::new(move |x| spline.try_value(x).unwrap()),
+            )
+        });
+        assert_degree_exactness(&|data| {
+            let spline = NaturalCubicSpline::try_new(&xy(data)).unwrap();
+            (
+                spline.degree_exactness(),
+                Box::new(move |x| spline.try_value(x).unwrap()),
+            )
+        });
+        assert_degree_exactness(&|data| {
+            let spline = CatmullRomSpline::try_new(&xy(data)).unwrap();
+            (
+                spline.degree_exactness(),
+                Box::new(move |x| spline.try_value(x).unwrap()),
+            )
+        });
+        for method in [
+            SlopeMethod::FiniteDifference,
+            SlopeMethod::CatmullRom,
+            SlopeMethod::Akima,
+            SlopeMethod::Pchip,
+        ] {
+            assert_degree_exactness(&move |data| {
+                let mut spline = StreamingHermiteSpline::new(method);
+                for &(x, y, _) in data {
+                    spline.push(x, y).unwrap();
+                }
+                (
+                    spline.degree_exactness(),
+                    Box::new(move |x| spline.try_value(x).unwrap()),
+                )
+            });
+        }
+        assert_degree_exactness(&|data| {
+            let mut spline = StreamingCatmullRomSpline::new();
+            for &(x, y, _) in data {
+                spline.push(x, y).unwrap();
+            }
+            (
+                spline.degree_exactness(),
+                Box::new(move |x| spline.try_value(x).unwrap()),
+            )
+        });
+        for method in [
+            RegionMethod::NaturalCubic,
+            RegionMethod::CatmullRom,
+            RegionMethod::Hermite(SlopeMethod::Akima),
+        ] {
+            assert_degree_exactness(&move |data| {
+                let mut builder = PointBuilder::new();
+                for &(x, y, _) in data {
+                    builder.push(x, y).unwrap();
+                }
+                let options = DensityPartition {
+                    sparse: method,
+                    ..DensityPartition::default()
+                };
+                let spline = builder.build_partitioned(&options).unwrap();
+                (
+                    spline.degree_exactness(),
+                    Box::new(move |x| spline.try_value(x).unwrap()),
+                )
+            });
+        }
+    }
+
+    #[test]
+    fn test_uniform_lookup() {
+        // 0.1 is inexact, so the guessed segment is sometimes off by one
+        let (x0, dx) = (-1.0, 0.1);
+        let ys: Vec<f64> = (0..41).map(|i| (i as f64 * 0.37).sin()).collect();
+        let dydxs: Vec<f64> = (0..41).map(|i| (i as f64 * 0.37).cos()).collect();
+        let xs: Vec<f64> = uniform_knots(x0, dx).take(41).collect();
+        let natural = NaturalCubicSpline::try_new_uniform(x0, dx, &ys).unwrap();
+        let expected_natural = NaturalCubicSpline::try_new_xy(&xs, &ys).unwrap();
+        let catmull_rom = CatmullRomSpline::try_new_uniform(x0, dx, &ys)
+            .unwrap()
+            .with_tolerance(Tolerance::Absolute(1e-9));
+        let expected_catmull_rom = CatmullRomSpline::try_new_xy(&xs, &ys)
+            .unwrap()
+            .with_tolerance(Tolerance::Absolute(1e-9));
+        let hermite = HermiteSpline::try_new_uniform(x0, dx, &ys, &dydxs).unwrap();
+        let expected_hermite = HermiteSpline::try_new_xy(&xs, &ys, &dydxs).unwrap();
+        let queries = xs
+            .iter()
+            .copied()
+            .chain((0..=4000).map(|i| x0 + i as f64 * 0.001))
+            .chain([xs[40] + 1e-10]);
+        for x in queries {
+            assert_eq!(
+                natural.try_value(x).ok(),
+                expected_natural.try_value(x).ok()
+            );
+            assert_eq!(
+                catmull_rom.try_value(x).ok(),
+                expected_catmull_rom.try_value(x).ok()
+            );
+            assert_eq!(
+                hermite.try_value(x).ok(),
+                expected_hermite.try_value(x).ok()
+            );
+        }
+        assert!(matches!(
+            natural.try_value(-1.5),
+            Err(InterpolationError::OutOfLowerBound(_))
+        ));
+        assert!(matches!(
+            NaturalCubicSpline::try_new_uniform(0.0, 0.0, &ys),
+            Err(InterpolationError::DuplicatePointError(_))
+        ));
+        assert!(matches!(
+            HermiteSpline::try_new_uniform(0.0, 1.0, &ys, &dydxs[1..]),
+            Err(InterpolationError::LengthMismatchError(41, 40))
+        ));
+        let mut natural = natural;
+        natural.insert_point(10.0, 0.0).unwrap();
+        assert_eq!(natural.try_value(10.0).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_segment_queries() {
+        let data: Vec<(f64, f64, f64)> = XS.iter().map(|&x| (x, x.sin(), x.cos())).collect();
+        let hermite = HermiteSpline::try_new(&data).unwrap();
+        let catmull_rom = CatmullRomSpline::try_new(&xy(&data)).unwrap();
+        for (x, segment, nearest) in [
+            (0.0, 0, 0),
+            (0.2, 0, 0),
+            (0.7, 1, 1),
+            (0.71, 1, 2),
+            (2.6, 6, 6),
+            (3.0, 6, 7),
+        ] {
+            assert_eq!(hermite.segment_index(x).unwrap(), segment);
+            assert_eq!(catmull_rom.segment_index(x).unwrap(), segment);
+            assert_eq!(hermite.nearest_knot(x).unwrap(), nearest);
+            let (start, end) = catmull_rom.segment_bounds(segment);
+            assert!(start <= x && x <= end);
+        }
+        assert_eq!(hermite.nearest_knot(9.0).unwrap(), 7);
+        assert!(matches!(
+            hermite.segment_index(3.5),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+        assert!(matches!(
+            catmull_rom.nearest_knot(f64::NAN),
+            Err(InterpolationError::NonFiniteInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_value_gradients() {
+        let ys: Vec<f64> = XS.iter().map(|x| x.sin()).collect();
+        let dydxs: Vec<f64> = XS.iter().map(|x| x.cos()).collect();
+        type Evaluate<'a> = &'a dyn Fn(&[f64], f64) -> (f64, Vec<f64>);
+        let evaluates: [Evaluate; 3] = [
+            &|ys, x| {
+                let spline = HermiteSpline::try_new_xy(&XS, ys, &dydxs).unwrap();
+                (
+                    spline.try_value(x).unwrap(),
+                    spline.value_gradient(x).unwrap(),
+                )
+            },
+            &|ys, x| {
+                let spline = NaturalCubicSpline::try_new_xy(&XS, ys).unwrap();
+                (
+                    spline.try_value(x).unwrap(),
+                    spline.value_gradient(x).unwrap(),
+                )
+            },
+            &|ys, x| {
+                let spline = CatmullRomSpline::try_new_xy(&XS, ys).unwrap();
+                (
+                    spline.try_value(x).unwrap(),
+                    spline.value_gradient(x).unwrap(),
+                )
+            },
+        ];
+        for evaluate in evaluates {
+            for x in [0.0, 0.1, 0.7, 1.3, 1.9, 2.55, 2.9, 3.0] {
+                let (value, gradient) = evaluate(&ys, x);
+                // the value is linear in the y-coordinates, so bumping one moves it by its weight
+                for (i, &weight) in gradient.iter().enumerate() {
+                    let mut bumped = ys.clone();
+                    bumped[i] += 1.0;
+                    let delta = evaluate(&bumped, x).0 - value;
+                    assert!((delta - weight).abs() < 1e-12, "{delta} vs {weight} at {x}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unchecked_values() {
+        let data: Vec<(f64, f64, f64)> = XS.iter().map(|&x| (x, x.sin(), x.cos())).collect();
+        let hermite = HermiteSpline::try_new(&data).unwrap();
+        let natural = NaturalCubicSpline::try_new(&xy(&data)).unwrap();
+        let catmull_rom = CatmullRomSpline::try_new(&xy(&data)).unwrap();
+        let polynomial = PiecewisePolynomial::from(&natural);
+        for i in 0..=300 {
+            let x = i as f64 * 0.01;
+            let pairs = [
+                (hermite.value_unchecked(x), hermite.try_value(x).unwrap()),
+                (natural.value_unchecked(x), natural.try_value(x).unwrap()),
+                (
+                    catmull_rom.value_unchecked(x),
+                    catmull_rom.try_value(x).unwrap(),
+                ),
+                (
+                    polynomial.value_unchecked(x),
+                    polynomial.try_value(x).unwrap(),
+                ),
+            ];
+            for (unchecked, checked) in pairs {
+                assert!(
+                    (unchecked - checked).abs() < 1e-14,
+                    "{unchecked} vs {checked} at {x}"
+                );
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "should lie within the knots")]
+    fn test_unchecked_value_out_of_bounds() {
+        let natural = NaturalCubicSpline::try_new(&[(0.0, 1.0), (1.0, 2.0)]).unwrap();
+        natural.value_unchecked(1.5);
+    }
+
+    #[test]
+    fn test_non_finite_queries() {
+        let points: Vec<(f64, f64, f64)> = XS.iter().map(|&x| (x, x.sin(), x.cos())).collect();
+        let curves: Vec<Box<dyn Interpolator<f64>>> = vec![
+            Box::new(HermiteSpline::try_new(&points).unwrap()),
+            Box::new(NaturalCubicSpline::try_new(&xy(&points)).unwrap()),
+            Box::new(CatmullRomSpline::try_new_uniform(0.0, 0.5, &XS).unwrap()),
+            Box::new(PiecewisePolynomial::from(
+                &NaturalCubicSpline::try_new(&xy(&points)).unwrap(),
+            )),
+            SplineBuilder::new(&xy(&points))
+                .extrapolation(Extrapolation::Constant)
+                .build()
+                .unwrap(),
+        ];
+        for curve in &curves {
+            for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+                assert!(
+                    matches!(
+                        curve.try_value(x),
+                        Err(InterpolationError::NonFiniteInput(_))
+                    ),
+                    "{:?} at {x}",
+                    curve.method()
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_exactness() {
+        use rust_decimal::Decimal;
+        let dec = |s| Decimal::from_str_exact(s).unwrap();
+
+        // y = 1.5x - 2 on knots whose widths divide exactly
+        let points: Vec<(Decimal, Decimal)> =
+            [dec("0"), dec("0.5"), dec("1"), dec("1.5"), dec("2")]
+                .into_iter()
+                .map(|x| (x, dec("1.5") * x - dec("2")))
+                .collect();
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        let hermite =
+            HermiteSpline::try_new_with_slope_method(&points, SlopeMethod::FiniteDifference)
+                .unwrap();
+        for x in [dec("0.25"), dec("0.8"), dec("1.7"), dec("2")] {
+            let y = dec("1.5") * x - dec("2");
+            assert_eq!(natural.try_value(x).unwrap(), y);
+            assert_eq!(catmull_rom.try_value(x).unwrap(), y);
+            assert_eq!(hermite.try_value(x).unwrap(), y);
+        }
+        // a knot returns its stored value even where the fit does not reproduce it exactly
+        let points = [
+            (dec("0"), dec("0.1")),
+            (dec("0.3"), dec("0.7")),
+            (dec("1"), dec("0.2")),
+        ];
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        for (x, y) in points {
+            assert_eq!(natural.try_value(x).unwrap(), y);
+            assert_eq!(catmull_rom.try_value(x).unwrap(), y);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Arc length of piecewise curves from their speed on each segment, and its inversion.
+
+use crate::math::{abs, epsilon};
+use crate::TranscendentalValue;
+
+/// The largest number of times a segment is halved to integrate its speed accurately.
+const MAX_DEPTH: usize = 10;
+
+/// The largest number of safeguarded Newton steps locating one arc length.
+const MAX_ITERATIONS: usize = 64;
+
+/// The cumulative arc length of a curve, where `speed(i, t)` is the norm of the derivative at the
+/// parameter `t` of the segment `i`.
+///
+/// The segments are split adaptively into panels on which the 5-point Gauss-Legendre rule
+/// converges, e.g. around the sharp bend of the graph of a steep spline at a maximum.
+pub(crate) struct ArcLength<V: TranscendentalValue, F: Fn(usize, V) -> V> {
+    // the panels `(segment, start, end)` in order, and the length up to the end of each
+    panels: Vec<(usize, V, V)>,
+    cumulative: Vec<V>,
+    speed: F,
+    // the nodes on [-1, 1] and weights of the Gauss-Legendre rule
+    rule: [(V, V); 5],
+}
+
+impl<V: TranscendentalValue, F: Fn(usize, V) -> V> ArcLength<V, F> {
+    /// Integrates `speed` over every segment between `breakpoints`.
+    pub(crate) fn new(breakpoints: &[V], speed: F) -> Self {
+        let int = V::from_integer;
+        let (outer, inner) = (int(2) * (int(10) / int(7)).sqrt(), int(13) * int(70).sqrt());
+        let node = |shift: V| (int(5) + shift).sqrt() / int(3);
+        let rule = [
+            (-node(outer), (int(322) - inner) / int(900)),
+            (-node(-outer), (int(322) + inner) / int(900)),
+            (V::zero(), int(128) / int(225)),
+            (node(-outer), (int(322) + inner) / int(900)),
+            (node(outer), (int(322) - inner) / int(900)),
+        ];
+        let mut arc_length = Self {
+            panels: Vec::new(),
+            cumulative: Vec::new(),
+            speed,
+            rule,
+        };
+        let tolerance = epsilon::<V>().sqrt();
+        let mut panels = Vec::new();
+        for (i, segment) in breakpoints.windows(2).enumerate() {
+            let whole = arc_length.gauss_legendre(i, segment[0], segment[1]);
+            arc_length.refine(
+                (i, segment[0], segment[1]),
+                whole,
+                tolerance,
+                0,
+                &mut panels,
+            );
+        }
+        let mut total = V::zero();
+        for (panel, length) in panels {
+            total += length;
+            arc_length.panels.push(panel);
+            arc_length.cumulative.push(total);
+        }
+        arc_length
+    }
+
+    /// Returns the length of the whole curve.
+    pub(crate) fn total(&self) -> V {
+        self.cumulative[self.cumulative.len() - 1]
+    }
+
+    /// Returns the parameters of `n` points equally spaced by arc length, the ends included, at
+    /// least 2.
+    pub(crate) fn evenly_spaced_parameters(&self, n: usize) -> Vec<V> {
+        let last = n.max(2) - 1;
+        let step = self.total() / V::from_count(last);
+        (0..=last)
+            .map(|k| match k {
+                0 => self.panels[0].1,
+                k if k == last => self.panels[self.panels.len() - 1].2,
+                k => self.parameter_at(step * V::from_count(k)),
+            })
+            .collect()
+    }
+
+    /// Returns the parameter at which the curve has the length `s`, by Newton's method on the
+    /// panel containing it, falling back to bisection whenever a step leaves the bracket.
+    fn parameter_at(&self, s: V) -> V {
+        let p = self
+            .cumulative
+            .partition_point(|&length| length < s)
+            .min(self.panels.len() - 1);
+        let (i, start, end) = self.panels[p];
+        let before = if p == 0 {
+            V::zero()
+        } else {
+            self.cumulative[p - 1]
+        };
+        let (remaining, panel_length) = (s - before, self.cumulative[p] - before);
+        if panel_length == V::zero() {
+            return start;
+        }
+        let (mut low, mut high) = (start, end);
+        let mut t = start + (end - start) * remaining / panel_length;
+        for _ in 0..MAX_ITERATIONS {
+            let excess = self.gauss_legendre(i, start, t) - remaining;
+            if excess > V::zero() {
+                high = t;
+            } else {
+                low = t;
+            }
+            let speed = (self.speed)(i, t);
+            let newton = if speed > V::zero() {
+                t - excess / speed
+            } else {
+                low
+            };
+            let next = if low < newton && newton < high {
+                newton
+            } else {
+                (low + high) / V::from_integer(2)
+            };
+            if next == t {
+                break;
+            }
+            t = next;
+        }
+        t
+    }
+
+    /// Halves `panel`, whose length is estimated as `whole`, until the halves agree with it within
+    /// the relative `tolerance`, and appends the panels with their lengths to `panels`.
+    fn refine(
+        &self,
+        panel: (usize, V, V),
+        whole: V,
+        tolerance: V,
+        depth: usize,
+        panels: &mut Vec<((usize, V, V), V)>,
+    ) {
+        let (i, start, end) = panel;
+        let middle = (start + end) / V::from_integer(2);
+        let (left, right) = (
+            self.gauss_legendre(i, start, middle),
+            self.gauss_legendre(i, middle, end),
+        );
+        if depth == MAX_DEPTH || abs(left + right - whole) <= tolerance * abs(left + right) {
+            panels.push(((i, start, middle), left));
+            panels.push(((i, middle, end), right));
+        } else {
+            self.refine((i, start, middle), left, tolerance, depth + 1, panels);
+            self.refine((i, middle, end), right, tolerance, depth + 1, panels);
+        }
+    }
+
+    /// Integrates the speed of the segment `i` from `a` to `b` with the Gauss-Legendre rule.
+    fn gauss_legendre(&self, i: usize, a: V, b: V) -> V {
+        let half_width = (b - a) / V::from_integer(2);
+        let center = a + half_width;
+        self.rule.iter().fold(V::zero(), |acc, &(node, weight)| {
+            acc + weight * (self.speed)(i, center + half_width * node)
+        }) * half_width
+    }
+}
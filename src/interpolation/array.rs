@@ -0,0 +1,140 @@
+//! Constructors from [`ndarray`] views, for zero-copy interop with ndarray-backed data.
+//!
+//! Batch evaluation into an array is provided by
+//! [`Interpolator::try_values_into`](crate::interpolation::interpolator::Interpolator::try_values_into).
+
+use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+use crate::interpolation::hermite_spline::HermiteSpline;
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::{InterpolationError, InterpolationValue};
+use ndarray::ArrayView1;
+
+/// Checks that `column` has the length of `xs`.
+fn check_column<V: InterpolationValue>(
+    xs: &ArrayView1<V>,
+    column: &ArrayView1<V>,
+) -> Result<(), InterpolationError<V>> {
+    if column.len() != xs.len() {
+        return Err(InterpolationError::LengthMismatchError(
+            xs.len(),
+            column.len(),
+        ));
+    }
+    Ok(())
+}
+
+impl<V: InterpolationValue> NaturalCubicSpline<V> {
+    /// Constructs a new `NaturalCubicSpline` from array views of the x- and y-coordinates, which
+    /// need not be contiguous.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new_xy`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use spline_interpolation::interpolation::interpolator::Interpolator;
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let xs = array![0.0, 0.5, 1.0];
+    /// let ys = array![1.0, 0.5, 0.0];
+    /// let spline = NaturalCubicSpline::try_from_arrays(xs.view(), ys.view()).unwrap();
+    /// let mut out = ndarray::Array1::zeros(2);
+    /// spline
+    ///     .try_values_into(array![0.25, 0.75].view(), out.view_mut())
+    ///     .unwrap();
+    /// assert_eq!(out, array![0.75, 0.25]);
+    /// ```
+    pub fn try_from_arrays(
+        xs: ArrayView1<V>,
+        ys: ArrayView1<V>,
+    ) -> Result<Self, InterpolationError<V>> {
+        check_column(&xs, &ys)?;
+        Self::try_from_iter(xs.iter().copied().zip(ys.iter().copied()))
+    }
+}
+
+impl<V: InterpolationValue> CatmullRomSpline<V> {
+    /// Constructs a new `CatmullRomSpline` from array views of the x- and y-coordinates, which
+    /// need not be contiguous.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new_xy`].
+    pub fn try_from_arrays(
+        xs: ArrayView1<V>,
+        ys: ArrayView1<V>,
+    ) -> Result<Self, InterpolationError<V>> {
+        check_column(&xs, &ys)?;
+        Self::try_from_iter(xs.iter().copied().zip(ys.iter().copied()))
+    }
+}
+
+impl<V: InterpolationValue> HermiteSpline<V> {
+    /// Constructs a new `HermiteSpline` from array views of the x-coordinates, values and slopes,
+    /// which need not be contiguous.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new_xy`].
+    pub fn try_from_arrays(
+        xs: ArrayView1<V>,
+        ys: ArrayView1<V>,
+        dydxs: ArrayView1<V>,
+    ) -> Result<Self, InterpolationError<V>> {
+        check_column(&xs, &ys)?;
+        check_column(&xs, &dydxs)?;
+        Self::try_from_iter(
+            xs.iter()
+                .zip(&ys)
+                .zip(&dydxs)
+                .map(|((&x, &y), &dydx)| (x, y, dydx)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::interpolator::Interpolator;
+    use crate::InterpolationError;
+    use ndarray::{array, Array1, Axis, Slice};
+
+    #[test]
+    fn test_strided_views() {
+        // every other column of a wider table
+        let table = array![
+            [0.0, 9.0, 0.5, 9.0, 1.0, 9.0],
+            [1.0, 9.0, 0.5, 9.0, 0.0, 9.0]
+        ];
+        let every_other = Slice::new(0, None, 2);
+        let spline = CatmullRomSpline::try_from_arrays(
+            table.row(0).slice_axis(Axis(0), every_other),
+            table.row(1).slice_axis(Axis(0), every_other),
+        )
+        .unwrap();
+        let xs = Array1::linspace(0.0, 1.0, 5);
+        let mut out = Array1::zeros(10);
+        spline
+            .try_values_into(xs.view(), out.slice_axis_mut(Axis(0), every_other))
+            .unwrap();
+        for (&x, &value) in xs.iter().zip(out.slice_axis(Axis(0), every_other)) {
+            assert_eq!(value, spline.try_value(x).unwrap());
+        }
+        assert!(matches!(
+            spline.try_values_into(xs.view(), out.view_mut()),
+            Err(InterpolationError::LengthMismatchError(5, 10))
+        ));
+        assert!(matches!(
+            HermiteSpline::try_from_arrays(
+                xs.view(),
+                xs.view(),
+                xs.slice_axis(Axis(0), Slice::from(1..))
+            ),
+            Err(InterpolationError::LengthMismatchError(5, 4))
+        ));
+    }
+}
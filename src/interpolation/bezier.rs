@@ -0,0 +1,94 @@
+//! Cubic Bézier curves equivalent to the segments of the cubic splines, as taken by vector
+//! renderers such as SVG, Canvas or skia.
+
+use crate::InterpolationValue;
+
+/// A cubic Bézier curve in the `(x, y)` plane, as returned for each segment by the
+/// `to_bezier_segments` methods of the cubic splines.
+///
+/// The x-coordinates of the control points split the segment into thirds, so the curve is the
+/// graph of the spline segment exactly, with the parameter running linearly in `x`.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+///
+/// let spline = HermiteSpline::try_new(&[(0.0, 0.0, 3.0), (3.0, 6.0, 0.0)]).unwrap();
+/// let segments = spline.to_bezier_segments();
+/// assert_eq!(
+///     segments[0].control_points,
+///     [(0.0, 0.0), (1.0, 3.0), (2.0, 6.0), (3.0, 6.0)]
+/// );
+/// assert_eq!(segments[0].point(0.5), (1.5, spline.try_value(1.5).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier<V: InterpolationValue> {
+    /// The start point, the two inner control points and the end point.
+    pub control_points: [(V, V); 4],
+}
+
+impl<V: InterpolationValue> CubicBezier<V> {
+    /// Builds the curve from `start` to `end` with the given slopes, whose inner control points
+    /// lie a third of the segment width along the tangents.
+    pub(crate) fn from_hermite(start: (V, V), start_slope: V, end: (V, V), end_slope: V) -> Self {
+        let third = (end.0 - start.0) / V::from_integer(3);
+        Self {
+            control_points: [
+                start,
+                (start.0 + third, start.1 + third * start_slope),
+                (end.0 - third, end.1 - third * end_slope),
+                end,
+            ],
+        }
+    }
+
+    /// Returns the point of the curve at the parameter `t` in `[0, 1]`, by de Casteljau's
+    /// algorithm.
+    pub fn point(&self, t: V) -> (V, V) {
+        let lerp = |a: (V, V), b: (V, V)| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+        let [p0, p1, p2, p3] = self.control_points;
+        let (q0, q1, q2) = (lerp(p0, p1), lerp(p1, p2), lerp(p2, p3));
+        let (r0, r1) = (lerp(q0, q1), lerp(q1, q2));
+        lerp(r0, r1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::bezier::CubicBezier;
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+
+    /// Checks that the curves join up and trace the spline.
+    fn assert_traces(segments: &[CubicBezier<f64>], spline: impl Fn(f64) -> f64) {
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].control_points[3], pair[1].control_points[0]);
+        }
+        for segment in segments {
+            for t in [0.0, 0.2, 0.5, 0.9, 1.0] {
+                let (x, y) = segment.point(t);
+                assert!((y - spline(x)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_segments_trace_splines() {
+        let points = [(0.0, 1.0), (0.7, 2.0), (1.5, -1.0), (2.0, 0.5), (3.2, 0.0)];
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        let segments = natural.to_bezier_segments();
+        assert_eq!(segments.len(), 4);
+        assert_traces(&segments, |x| natural.try_value(x).unwrap());
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        assert_traces(&catmull_rom.to_bezier_segments(), |x| {
+            catmull_rom.try_value(x).unwrap()
+        });
+        let hermite_points: Vec<_> = points.iter().map(|&(x, y)| (x, y, x - y)).collect();
+        let hermite = HermiteSpline::try_new(&hermite_points).unwrap();
+        assert_traces(&hermite.to_bezier_segments(), |x| {
+            hermite.try_value(x).unwrap()
+        });
+    }
+}
@@ -0,0 +1,332 @@
+//! A compact, versioned binary format for the coefficients of piecewise polynomials, independent
+//! of serde, for persisting many curves and reading them back from memory-mapped files.
+//!
+//! # Format, version 1
+//!
+//! All integers and scalars are little-endian.
+//!
+//! | offset | size | content |
+//! |---|---|---|
+//! | 0 | 4 | the magic bytes `SPPP` |
+//! | 4 | 2 | the format version, `1` |
+//! | 6 | 1 | the width of a scalar in bytes: `4` for `f32`, `8` for `f64` |
+//! | 7 | 1 | reserved, `0` |
+//! | 8 | 4 | the order `k + 1` of the polynomials, as `u32` |
+//! | 12 | 4 | the number of segments `n`, as `u32` |
+//! | 16 | `(n + 1) w` | the breakpoints |
+//! | 16 + `(n + 1) w` | `n (k + 1) w` | the coefficient rows, highest power first |
+//!
+//! The scalars start 16 bytes in, so a record at an 8-byte aligned offset keeps them aligned, and
+//! each record is [`PiecewisePolynomial::encoded_len`] bytes long, so records can be concatenated
+//! and read back in turn with [`PiecewisePolynomial::from_bytes_prefix`].
+//! The metadata is not encoded; the serde form of `PiecewisePolynomial` keeps it.
+//!
+//! # Example
+//!
+//! ```
+//! use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+//! use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+//!
+//! let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)]).unwrap();
+//! let bytes = PiecewisePolynomial::from(&spline).to_bytes();
+//! assert_eq!(&bytes[..4], b"SPPP");
+//! let restored = PiecewisePolynomial::<f64>::from_bytes(&bytes).unwrap();
+//! assert_eq!(restored.try_value(0.75).unwrap(), 0.25);
+//! ```
+
+use crate::interpolation::check_knots;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::{InterpolationError, InterpolationValue};
+use thiserror::Error;
+
+/// The magic bytes opening every record.
+pub const MAGIC: [u8; 4] = *b"SPPP";
+
+/// The format version written by [`PiecewisePolynomial::to_bytes`].
+pub const VERSION: u16 = 1;
+
+const HEADER_LEN: usize = 16;
+
+/// The errors of decoding a record.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFormatError {
+    /// The record does not start with [`MAGIC`].
+    #[error("not a piecewise polynomial record")]
+    MagicError,
+    /// The record has a format version this crate cannot read.
+    #[error("unsupported format version {0}")]
+    VersionError(u16),
+    /// The scalars of the record are `found` bytes wide instead of `expected`.
+    #[error("expected scalars of {expected} bytes but got {found}")]
+    ScalarWidthError {
+        /// The width of the requested scalar type.
+        expected: u8,
+        /// The width stored in the record.
+        found: u8,
+    },
+    /// The record is `actual` bytes long instead of the `expected` its header implies.
+    #[error("expected a record of {0} bytes but got {1}")]
+    RecordLengthError(usize, usize),
+    /// The header declares a zero order or no segments.
+    #[error("the record declares an empty shape")]
+    ShapeError,
+}
+
+/// A scalar with a fixed little-endian encoding.
+pub trait BinaryScalar: InterpolationValue {
+    /// The width of the encoding in bytes.
+    const WIDTH: u8;
+
+    /// Appends the little-endian bytes of `self` to `out`.
+    fn write_le(self, out: &mut Vec<u8>);
+
+    /// Reads a value from the first [`Self::WIDTH`] bytes of `bytes`.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_binary_scalar {
+    ($($float:ty),*) => {
+        $(
+            impl BinaryScalar for $float {
+                const WIDTH: u8 = std::mem::size_of::<$float>() as u8;
+
+                fn write_le(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    const WIDTH: usize = std::mem::size_of::<$float>();
+                    let mut buffer = [0; WIDTH];
+                    buffer.copy_from_slice(&bytes[..WIDTH]);
+                    <$float>::from_le_bytes(buffer)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_scalar!(f32, f64);
+
+impl<V: BinaryScalar> PiecewisePolynomial<V> {
+    /// Returns the length in bytes of the encoding of `self`.
+    pub fn encoded_len(&self) -> usize {
+        let (n, order) = (self.segment_count(), self.degree() + 1);
+        HEADER_LEN + (n + 1 + n * order) * usize::from(V::WIDTH)
+    }
+
+    /// Encodes the breakpoints and coefficients in the documented
+    /// [binary format](crate::interpolation::binary).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the order or the number of segments exceeds `u32::MAX`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (n, order) = (self.segment_count(), self.degree() + 1);
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&[V::WIDTH, 0]);
+        for count in [order, n] {
+            let count = u32::try_from(count).expect("the shape should fit in a u32");
+            bytes.extend_from_slice(&count.to_le_bytes());
+        }
+        for &x in self.breakpoints() {
+            x.write_le(&mut bytes);
+        }
+        for i in 0..n {
+            for &c in self.coefficients(i) {
+                c.write_le(&mut bytes);
+            }
+        }
+        bytes
+    }
+
+    /// Decodes a piecewise polynomial written by [`Self::to_bytes`], with empty metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FormatError` for a record that is malformed, of another version or scalar width,
+    /// or not exactly [`Self::encoded_len`] bytes long, and the errors of [`Self::try_new`] for
+    /// breakpoints that are not finite and strictly ascending.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InterpolationError<V>> {
+        let (polynomial, rest) = Self::from_bytes_prefix(bytes)?;
+        if !rest.is_empty() {
+            return Err(BinaryFormatError::RecordLengthError(
+                bytes.len() - rest.len(),
+                bytes.len(),
+            )
+            .into());
+        }
+        Ok(polynomial)
+    }
+
+    /// Decodes the record at the start of `bytes`, with empty metadata, and returns it with the
+    /// bytes following it, e.g. to read concatenated records one after another.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::from_bytes`], except that `bytes` may extend past the record.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    ///
+    /// let line = PiecewisePolynomial::try_new(vec![0.0, 1.0], vec![vec![2.0, 1.0]]).unwrap();
+    /// let step = PiecewisePolynomial::try_new(vec![0.0, 1.0, 2.0], vec![vec![0.0], vec![1.0]])
+    ///     .unwrap();
+    /// let mut bytes = line.to_bytes();
+    /// bytes.extend(step.to_bytes());
+    /// let (first, rest) = PiecewisePolynomial::<f64>::from_bytes_prefix(&bytes).unwrap();
+    /// let (second, rest) = PiecewisePolynomial::<f64>::from_bytes_prefix(rest).unwrap();
+    /// assert_eq!((first, second), (line, step));
+    /// assert!(rest.is_empty());
+    /// ```
+    pub fn from_bytes_prefix(bytes: &[u8]) -> Result<(Self, &[u8]), InterpolationError<V>> {
+        let header = bytes
+            .get(..HEADER_LEN)
+            .ok_or(BinaryFormatError::RecordLengthError(
+                HEADER_LEN,
+                bytes.len(),
+            ))?;
+        if header[..4] != MAGIC {
+            return Err(BinaryFormatError::MagicError.into());
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(BinaryFormatError::VersionError(version).into());
+        }
+        if header[6] != V::WIDTH {
+            return Err(BinaryFormatError::ScalarWidthError {
+                expected: V::WIDTH,
+                found: header[6],
+            }
+            .into());
+        }
+        let read_count = |offset: usize| {
+            let count = u32::from_le_bytes([
+                header[offset],
+                header[offset + 1],
+                header[offset + 2],
+                header[offset + 3],
+            ]);
+            usize::try_from(count).map_err(|_| BinaryFormatError::ShapeError)
+        };
+        let (order, n) = (read_count(8)?, read_count(12)?);
+        if order == 0 || n == 0 {
+            return Err(BinaryFormatError::ShapeError.into());
+        }
+        let width = usize::from(V::WIDTH);
+        let expected = (n + 1)
+            .checked_add(n.checked_mul(order).ok_or(BinaryFormatError::ShapeError)?)
+            .and_then(|count| count.checked_mul(width))
+            .and_then(|len| len.checked_add(HEADER_LEN))
+            .ok_or(BinaryFormatError::ShapeError)?;
+        let (record, rest) = match bytes.split_at_checked(expected) {
+            Some(split) => split,
+            None => {
+                return Err(BinaryFormatError::RecordLengthError(expected, bytes.len()).into());
+            }
+        };
+        let mut scalars = record[HEADER_LEN..].chunks_exact(width).map(V::read_le);
+        let breakpoints: Vec<V> = scalars.by_ref().take(n + 1).collect();
+        check_knots(breakpoints.iter().copied())?;
+        Ok((
+            Self::new_unchecked(breakpoints, scalars.collect(), order),
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::binary::BinaryFormatError;
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_round_trip() {
+        let points: Vec<(f32, f32)> = (0..10).map(|i| (i as f32 * 0.3, (i * i) as f32)).collect();
+        let polynomial = PiecewisePolynomial::from(&CatmullRomSpline::try_new(&points).unwrap());
+        let bytes = polynomial.to_bytes();
+        assert_eq!(bytes.len(), polynomial.encoded_len());
+        assert_eq!(bytes.len(), 16 + (10 + 9 * 4) * 4);
+        assert_eq!(PiecewisePolynomial::from_bytes(&bytes).unwrap(), polynomial);
+        // a stable layout: the header of a single linear segment of f64
+        let line = PiecewisePolynomial::try_new(vec![0.0, 1.0], vec![vec![2.0, 1.0]]).unwrap();
+        let bytes = line.to_bytes();
+        assert_eq!(
+            bytes[..16],
+            *b"SPPP\x01\x00\x08\x00\x02\x00\x00\x00\x01\x00\x00\x00"
+        );
+        assert_eq!(bytes[24..32], 1.0_f64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_concatenated_records() {
+        let polynomials: Vec<PiecewisePolynomial<f64>> = (2..5)
+            .map(|n| {
+                let points: Vec<_> = (0..n).map(|i| (i as f64, (i * i) as f64)).collect();
+                PiecewisePolynomial::from(&CatmullRomSpline::try_new(&points).unwrap())
+            })
+            .collect();
+        let bytes: Vec<u8> = polynomials.iter().flat_map(|p| p.to_bytes()).collect();
+        let mut rest = &bytes[..];
+        for polynomial in &polynomials {
+            let (decoded, tail) = PiecewisePolynomial::from_bytes_prefix(rest).unwrap();
+            assert_eq!(&decoded, polynomial);
+            assert_eq!(rest.len() - tail.len(), polynomial.encoded_len());
+            rest = tail;
+        }
+        assert!(rest.is_empty());
+        // a truncated last record
+        let truncated = &bytes[polynomials[0].encoded_len()..bytes.len() - 1];
+        let (_, tail) = PiecewisePolynomial::<f64>::from_bytes_prefix(truncated).unwrap();
+        assert!(matches!(
+            PiecewisePolynomial::<f64>::from_bytes_prefix(tail),
+            Err(InterpolationError::FormatError(
+                BinaryFormatError::RecordLengthError(expected, actual)
+            )) if expected == polynomials[2].encoded_len() && actual == expected - 1
+        ));
+    }
+
+    #[test]
+    fn test_malformed_records() {
+        let line = PiecewisePolynomial::try_new(vec![0.0, 1.0], vec![vec![2.0, 1.0]]).unwrap();
+        let bytes = line.to_bytes();
+        let decode = |bytes: &[u8]| match PiecewisePolynomial::<f64>::from_bytes(bytes) {
+            Err(InterpolationError::FormatError(error)) => error,
+            other => panic!("expected a format error, got {other:?}"),
+        };
+        assert_eq!(
+            decode(&bytes[..40]),
+            BinaryFormatError::RecordLengthError(48, 40)
+        );
+        assert_eq!(
+            decode(&bytes[..8]),
+            BinaryFormatError::RecordLengthError(16, 8)
+        );
+        let mut corrupt = bytes.clone();
+        corrupt[0] = b'X';
+        assert_eq!(decode(&corrupt), BinaryFormatError::MagicError);
+        let mut corrupt = bytes.clone();
+        corrupt[4] = 2;
+        assert_eq!(decode(&corrupt), BinaryFormatError::VersionError(2));
+        assert_eq!(
+            PiecewisePolynomial::<f32>::from_bytes(&bytes)
+                .unwrap_err()
+                .to_string(),
+            "invalid binary record: expected scalars of 4 bytes but got 8"
+        );
+        let mut long = bytes.clone();
+        long.push(0);
+        assert_eq!(decode(&long), BinaryFormatError::RecordLengthError(48, 49));
+        let mut unsorted = bytes;
+        unsorted[24..32].copy_from_slice(&(-1.0_f64).to_le_bytes());
+        assert!(matches!(
+            PiecewisePolynomial::<f64>::from_bytes(&unsorted),
+            Err(InterpolationError::PointOrderError(1))
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+//! A single entry point choosing the interpolation method and its options.
+
+use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+use crate::interpolation::dedup::DedupPolicy;
+use crate::interpolation::hermite_spline::HermiteSpline;
+use crate::interpolation::interpolator::{impl_interpolator, Interpolator};
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::slopes::SlopeMethod;
+use crate::metadata::Metadata;
+use crate::tolerance::Tolerance;
+use crate::{InterpolationError, InterpolationValue};
+
+/// The interpolation method built by a [`SplineBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Method {
+    /// A [`NaturalCubicSpline`].
+    #[default]
+    NaturalCubic,
+    /// A [`CatmullRomSpline`].
+    CatmullRom,
+    /// A [`HermiteSpline`] with slopes estimated by the given rule.
+    Hermite(SlopeMethod),
+}
+
+/// How an interpolant built by a [`SplineBuilder`] answers queries outside its knots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extrapolation {
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)`, as the splines themselves do.
+    #[default]
+    Error,
+    /// Continues with the value at the nearest end.
+    Constant,
+    /// Continues along the tangent at the nearest end.
+    Linear,
+}
+
+/// Builds an interpolant from points `(x, y)`, selecting the method and its options in one place
+/// instead of through the constructors of each spline.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::builder::{Extrapolation, Method, SplineBuilder};
+/// use spline_interpolation::interpolation::dedup::DedupPolicy;
+/// use spline_interpolation::interpolation::slopes::SlopeMethod;
+///
+/// let points = [(1.0, 1.0), (0.0, 0.0), (2.0, 2.0), (1.0, 3.0)];
+/// let curve = SplineBuilder::new(&points)
+///     .method(Method::Hermite(SlopeMethod::Pchip))
+///     .dedup(DedupPolicy::KeepFirst)
+///     .extrapolation(Extrapolation::Linear)
+///     .build()
+///     .unwrap();
+/// assert_eq!(curve.try_value(0.5).unwrap(), 0.5);
+/// assert_eq!(curve.try_value(3.0).unwrap(), 3.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplineBuilder<V: InterpolationValue> {
+    points: Vec<(V, V)>,
+    method: Method,
+    dedup: Option<DedupPolicy>,
+    extrapolation: Extrapolation,
+    tolerance: Tolerance<V>,
+    metadata: Metadata,
+}
+
+impl<V: InterpolationValue> SplineBuilder<V> {
+    /// Starts building an interpolant through `points`, by default a natural cubic spline that
+    /// requires sorted points and does not extrapolate.
+    pub fn new(points: &[(V, V)]) -> Self {
+        Self {
+            points: points.to_vec(),
+            method: Method::default(),
+            dedup: None,
+            extrapolation: Extrapolation::default(),
+            tolerance: Tolerance::Exact,
+            metadata: Metadata::default(),
+        }
+    }
+
+    /// Sets the interpolation method.
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Accepts the points in any order, sorting them by x-coordinate and merging the points
+    /// sharing one with `policy`.
+    pub fn dedup(mut self, policy: DedupPolicy) -> Self {
+        self.dedup = Some(policy);
+        self
+    }
+
+    /// Sets how queries outside the knots are answered.
+    pub fn extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    /// Sets the tolerance within which a query is treated as hitting a knot exactly.
+    pub fn tolerance(mut self, tolerance: Tolerance<V>) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Attaches provenance metadata to the interpolant.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Builds the interpolant.
+    ///
+    /// With [`Extrapolation::Error`] the box holds the spline of the chosen [`Method`] itself,
+    /// which [`downcast_ref`](crate::interpolation::interpolator::Interpolator#method.downcast_ref)
+    /// recovers; otherwise it holds an [`Extrapolated`] wrapping it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(i)` if the `i`-th point has a NaN or infinite
+    /// coordinate, and otherwise the errors of the constructor of the chosen method, e.g.
+    /// `PointOrderError(i)` for unsorted points without [`Self::dedup`].
+    pub fn build(self) -> Result<Box<dyn Interpolator<V>>, InterpolationError<V>> {
+        let points = match self.dedup {
+            Some(policy) => policy.sort_and_apply(&self.points)?,
+            None => self.points,
+        };
+        let (tolerance, metadata) = (self.tolerance, self.metadata);
+        let (interpolator, polynomial): (Box<dyn Interpolator<V>>, PiecewisePolynomial<V>) =
+            match self.method {
+                Method::NaturalCubic => {
+                    let spline = NaturalCubicSpline::try_new(&points)?
+                        .with_tolerance(tolerance)
+                        .with_metadata(metadata);
+                    let polynomial = (&spline).into();
+                    (Box::new(spline), polynomial)
+                }
+                Method::CatmullRom => {
+                    let spline = CatmullRomSpline::try_new(&points)?
+                        .with_tolerance(tolerance)
+                        .with_metadata(metadata);
+                    let polynomial = (&spline).into();
+                    (Box::new(spline), polynomial)
+                }
+                Method::Hermite(slope_method) => {
+                    let spline = HermiteSpline::try_new_with_slope_method(&points, slope_method)?
+                        .with_tolerance(tolerance)
+                        .with_metadata(metadata);
+                    let polynomial = (&spline).into();
+                    (Box::new(spline), polynomial)
+                }
+            };
+        if self.extrapolation == Extrapolation::Error {
+            return Ok(interpolator);
+        }
+        let derivative = polynomial.derivative();
+        let end = |x: V| -> Result<_, InterpolationError<V>> {
+            let slope = match self.extrapolation {
+                Extrapolation::Linear => derivative.try_value(x)?,
+                _ => V::zero(),
+            };
+            Ok((x, polynomial.try_value(x)?, slope))
+        };
+        let breakpoints = polynomial.breakpoints();
+        Ok(Box::new(Extrapolated {
+            lower: end(breakpoints[0])?,
+            upper: end(breakpoints[breakpoints.len() - 1])?,
+            inner: interpolator,
+        }))
+    }
+}
+
+/// An interpolant continued past its knots by a line, as built by [`SplineBuilder`] with
+/// [`Extrapolation::Constant`] or [`Extrapolation::Linear`].
+pub struct Extrapolated<V: InterpolationValue> {
+    inner: Box<dyn Interpolator<V>>,
+    // (x, y, dydx) at the first and last knot
+    lower: (V, V, V),
+    upper: (V, V, V),
+}
+
+impl<V: InterpolationValue> Extrapolated<V> {
+    /// Returns the interpolant between the knots.
+    pub fn inner(&self) -> &dyn Interpolator<V> {
+        self.inner.as_ref()
+    }
+
+    /// Returns the provenance metadata of the inner interpolant.
+    pub fn metadata(&self) -> &Metadata {
+        self.inner
+            .metadata()
+            .expect("the splines built by SplineBuilder carry metadata")
+    }
+
+    /// Evaluates the interpolant at `x`, extrapolating outside the knots.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the inner interpolant other than `OutOfLowerBound` and
+    /// `OutOfUpperBound`.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        let (x0, y0, dydx) = match self.inner.try_value(x) {
+            Err(InterpolationError::OutOfLowerBound(_)) => self.lower,
+            Err(InterpolationError::OutOfUpperBound(_)) => self.upper,
+            result => return result,
+        };
+        Ok(y0 + dydx * (x - x0))
+    }
+}
+
+impl_interpolator!(Extrapolated, Extrapolated, metadata);
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::builder::{Extrapolated, Extrapolation, Method, SplineBuilder};
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::dedup::DedupPolicy;
+    use crate::interpolation::interpolator::MethodTag;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::slopes::SlopeMethod;
+    use crate::metadata::Metadata;
+    use crate::InterpolationError;
+
+    const POINTS: [(f64, f64); 4] = [(0.0, 1.0), (1.0, 2.0), (2.0, 0.0), (3.0, 1.0)];
+
+    #[test]
+    fn test_methods_match_constructors() {
+        let natural = SplineBuilder::new(&POINTS).build().unwrap();
+        assert_eq!(natural.method(), MethodTag::NaturalCubic);
+        let catmull_rom = SplineBuilder::new(&POINTS)
+            .method(Method::CatmullRom)
+            .build()
+            .unwrap();
+        let expected = CatmullRomSpline::try_new(&POINTS).unwrap();
+        for x in [0.0, 0.5, 1.7, 3.0] {
+            assert_eq!(
+                catmull_rom.try_value(x).unwrap(),
+                expected.try_value(x).unwrap()
+            );
+        }
+        assert!(natural.downcast_ref::<NaturalCubicSpline<f64>>().is_some());
+        let hermite = SplineBuilder::new(&POINTS)
+            .method(Method::Hermite(SlopeMethod::Akima))
+            .metadata(Metadata::new().with_name("hermite"))
+            .build()
+            .unwrap();
+        assert_eq!(hermite.method(), MethodTag::Hermite);
+        assert_eq!(hermite.metadata().unwrap().name(), Some("hermite"));
+        assert!(matches!(
+            SplineBuilder::new(&[(1.0, 0.0), (0.0, 1.0), (2.0, 0.0)]).build(),
+            Err(InterpolationError::PointOrderError(1))
+        ));
+        let sorted = SplineBuilder::new(&[(1.0, 0.0), (0.0, 1.0), (2.0, 0.0)])
+            .dedup(DedupPolicy::Average)
+            .build()
+            .unwrap();
+        assert_eq!(sorted.try_value(0.0).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_extrapolation() {
+        let curve = SplineBuilder::new(&POINTS)
+            .extrapolation(Extrapolation::Constant)
+            .metadata(Metadata::new().with_name("constant"))
+            .build()
+            .unwrap();
+        assert_eq!(curve.method(), MethodTag::Extrapolated);
+        assert_eq!(curve.try_value(-1.0).unwrap(), 1.0);
+        assert_eq!(curve.try_value(10.0).unwrap(), 1.0);
+        assert_eq!(curve.metadata().unwrap().name(), Some("constant"));
+        let extrapolated = curve.downcast_ref::<Extrapolated<f64>>().unwrap();
+        assert_eq!(extrapolated.inner().method(), MethodTag::NaturalCubic);
+
+        // Catmull-Rom end slopes are the end secants
+        let curve = SplineBuilder::new(&POINTS)
+            .method(Method::CatmullRom)
+            .extrapolation(Extrapolation::Linear)
+            .build()
+            .unwrap();
+        assert!((curve.try_value(-1.0).unwrap()).abs() < 1e-12);
+        assert!((curve.try_value(4.0).unwrap() - 2.0).abs() < 1e-12);
+        assert_eq!(curve.try_value(1.0).unwrap(), 2.0);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::interpolation::natural_cubic_spline::{second_derivative_rhs, second_derivative_system};
+use crate::interpolation::{check_knots, segment_index};
+use crate::{InterpolationError, InterpolationValue};
+
+/// Many natural cubic splines sharing one knot grid.
+///
+/// The spline system is factorized once for all curves, and the values and second derivatives are
+/// stored knot by knot, so evaluating every curve at `x` reads one contiguous block per knot.
+pub struct SplineBundle<V: InterpolationValue> {
+    xs: Vec<V>,
+    curve_count: usize,
+    // ys[i * curve_count + c] is the value of curve c at xs[i]
+    ys: Vec<V>,
+    second_derivatives: Vec<V>,
+}
+
+impl<V: InterpolationValue> SplineBundle<V> {
+    /// Constructs a new `SplineBundle` with one natural cubic spline per curve.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The knots shared by every curve, in ascending order.
+    /// * `curves` - The values of each curve at the knots.
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(n)` - If there are fewer than 3 knots.
+    /// * `InterpolationError::PointOrderError(i)` - If the `i`-th knot is smaller than its
+    ///   predecessor.
+    /// * `InterpolationError::DuplicatePointError(x)` - If two knots equal `x`.
+    /// * `InterpolationError::NonFiniteError(i)` - If the `i`-th knot is NaN or infinite.
+    /// * `InterpolationError::LengthMismatchError(expected, actual)` - If a curve does not have one
+    ///   value per knot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::bundle::SplineBundle;
+    ///
+    /// let xs = [0.0, 0.5, 1.0];
+    /// let bundle = SplineBundle::try_new(&xs, &[[1.0, 0.5, 0.0], [0.0, 1.0, 2.0]]).unwrap();
+    /// assert_eq!(bundle.try_values(0.75).unwrap(), [0.25, 1.5]);
+    /// ```
+    pub fn try_new(xs: &[V], curves: &[impl AsRef<[V]>]) -> Result<Self, InterpolationError<V>> {
+        let n = xs.len();
+        if n < 3 {
+            return Err(InterpolationError::InsufficientPointsError(n));
+        }
+        check_knots(xs.iter().copied())?;
+        let curve_count = curves.len();
+        let system = second_derivative_system(xs)?;
+        let mut ys = vec![V::zero(); n * curve_count];
+        let mut second_derivatives = vec![V::zero(); n * curve_count];
+        for (c, curve) in curves.iter().enumerate() {
+            let curve = curve.as_ref();
+            if curve.len() != n {
+                return Err(InterpolationError::LengthMismatchError(n, curve.len()));
+            }
+            let derivatives = system.solve_unchecked(&second_derivative_rhs(xs, curve));
+            for (i, (&y, m)) in curve.iter().zip(derivatives).enumerate() {
+                ys[i * curve_count + c] = y;
+                second_derivatives[i * curve_count + c] = m;
+            }
+        }
+        Ok(Self {
+            xs: xs.to_vec(),
+            curve_count,
+            ys,
+            second_derivatives,
+        })
+    }
+
+    /// Returns the knots shared by every curve.
+    pub fn xs(&self) -> &[V] {
+        &self.xs
+    }
+
+    /// Returns the number of curves.
+    pub fn curve_count(&self) -> usize {
+        self.curve_count
+    }
+
+    /// Evaluates every curve at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` if `x` lies outside the knots.
+    pub fn try_values(&self, x: V) -> Result<Vec<V>, InterpolationError<V>> {
+        let mut values = vec![V::zero(); self.curve_count];
+        self.try_values_into(x, &mut values)?;
+        Ok(values)
+    }
+
+    /// Evaluates every curve at `x` into `values`, without allocating.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatchError(expected, actual)` if `values` does not have one entry per
+    /// curve, and `OutOfLowerBound(x)` or `OutOfUpperBound(x)` if `x` lies outside the knots.
+    pub fn try_values_into(&self, x: V, values: &mut [V]) -> Result<(), InterpolationError<V>> {
+        let k = self.curve_count;
+        if values.len() != k {
+            return Err(InterpolationError::LengthMismatchError(k, values.len()));
+        }
+        let i = segment_index(&self.xs, x)?;
+        let h = self.xs[i + 1] - self.xs[i];
+        let a = (self.xs[i + 1] - x) / h;
+        let b = (x - self.xs[i]) / h;
+        let six = V::from_integer(6);
+        let ca = (a * a * a - a) * h * h / six;
+        let cb = (b * b * b - b) * h * h / six;
+        let (ys, next_ys) = self.ys[i * k..(i + 2) * k].split_at(k);
+        let (ms, next_ms) = self.second_derivatives[i * k..(i + 2) * k].split_at(k);
+        for (c, value) in values.iter_mut().enumerate() {
+            *value = a * ys[c] + b * next_ys[c] + ca * ms[c] + cb * next_ms[c];
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::bundle::SplineBundle;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_matches_individual_splines() {
+        let xs = [0.0, 0.25, 1.0, 2.0, 5.0, 10.0];
+        let curves: Vec<Vec<f64>> = (0..4)
+            .map(|c| {
+                xs.iter()
+                    .map(|&x| (x * (c + 1) as f64 * 0.3).sin())
+                    .collect()
+            })
+            .collect();
+        let bundle = SplineBundle::try_new(&xs, &curves).unwrap();
+        let splines: Vec<_> = curves
+            .iter()
+            .map(|ys| {
+                let points: Vec<_> = xs.iter().copied().zip(ys.iter().copied()).collect();
+                NaturalCubicSpline::try_new(&points).unwrap()
+            })
+            .collect();
+        for i in 0..=100 {
+            let x = i as f64 * 0.1;
+            for (value, spline) in bundle.try_values(x).unwrap().into_iter().zip(&splines) {
+                assert!((value - spline.try_value(x).unwrap()).abs() < 1e-12);
+            }
+        }
+        assert!(matches!(
+            bundle.try_values(10.5),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+        assert!(matches!(
+            bundle.try_values_into(1.0, &mut [0.0; 3]),
+            Err(InterpolationError::LengthMismatchError(4, 3))
+        ));
+        assert!(matches!(
+            SplineBundle::try_new(&xs, &[vec![0.0; 5]]),
+            Err(InterpolationError::LengthMismatchError(6, 5))
+        ));
+    }
+}
@@ -1,16 +1,38 @@
-use crate::HermiteSplineError;
+use crate::interpolation::bezier::CubicBezier;
+use crate::interpolation::continuity::ContinuityReport;
+use crate::interpolation::dedup::DedupPolicy;
+use crate::interpolation::difference::MaxDifference;
+use crate::interpolation::error_bound::derivative_estimates;
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::sample::SplineSample;
+#[cfg(feature = "serde")]
+use crate::interpolation::serialization::SplineDocument;
+#[cfg(feature = "svg")]
+use crate::interpolation::svg::{self, PathSampling};
+use crate::interpolation::{
+    check_finite, check_knots, check_removal, insertion_index, locate_uniform, nearest_knot,
+    segment_index, segment_index_unchecked, uniform_knots, Location,
+};
+use crate::math::abs;
+use crate::metadata::Metadata;
+use crate::profiling::record_segment_evaluation;
+use crate::tolerance::Tolerance;
+use crate::InterpolationError;
 use crate::InterpolationValue;
+use crate::TranscendentalValue;
 use nalgebra::{Matrix4, Vector4};
-use num_traits::Zero;
-use std::ops::Mul;
-
-struct Point2<V> {
-    pub x: V,
-    pub y: V,
-}
+use std::fmt::{self, Debug, Display};
+use std::ops::{Mul, RangeInclusive};
 
 pub struct CatmullRomSpline<V: InterpolationValue> {
-    points: Vec<Point2<V>>,
+    // the knots and values are stored apart, so the search reads xs only
+    xs: Vec<V>,
+    ys: Vec<V>,
+    tolerance: Tolerance<V>,
+    metadata: Metadata,
+    // (x0, dx) while the knots are x0 + i dx, for the constant-time lookup
+    spacing: Option<(V, V)>,
 }
 
 impl<V: InterpolationValue> CatmullRomSpline<V> {
@@ -22,12 +44,16 @@ impl<V: InterpolationValue> CatmullRomSpline<V> {
     ///
     /// # Returns
     ///
-    /// * `Result<Self, HermiteSplineError<V>>` - A `Result` that either contains the constructed `HermiteSpline` or an `HermiteSplineError`.
+    /// * `Result<Self, InterpolationError<V>>` - A `Result` that either contains the constructed `HermiteSpline` or an `InterpolationError`.
     ///
     /// # Errors
     ///
-    /// * `HermiteSplineError::InsufficientPointsError(n)` - If the number of `raw_points` is less than 3, where `n` is the number of raw_points.
-    /// * `HermiteSplineError::PointOrderError` - If the x-coordinates of the `raw_points` are not in ascending order.
+    /// * `InterpolationError::InsufficientPointsError(n)` - If the number of `raw_points` is less than 2, where `n` is the number of raw_points.
+    /// * `InterpolationError::NonFiniteError(i)` - If the `i`-th raw point has a NaN or infinite coordinate.
+    /// * `InterpolationError::DuplicatePointError(x)` - If two raw points share the x-coordinate `x`.
+    /// * `InterpolationError::PointOrderError(i)` - If the x-coordinate of the `i`-th raw point is smaller than its predecessor.
+    ///
+    /// Two points make the line through them: the end slopes are secants, which coincide.
     ///
     /// # Example
     ///
@@ -38,24 +64,202 @@ impl<V: InterpolationValue> CatmullRomSpline<V> {
     /// let spline = CatmullRomSpline::try_new(&raw_points);
     /// assert!(spline.is_ok());
     /// ```
-    pub fn try_new(raw_points: &[(V, V)]) -> Result<Self, HermiteSplineError<V>> {
-        if raw_points.len() < 3 {
-            return Err(HermiteSplineError::InsufficientPointsError(
-                raw_points.len(),
-            ));
+    pub fn try_new(raw_points: &[(V, V)]) -> Result<Self, InterpolationError<V>> {
+        Self::try_from_iter(raw_points.iter().copied())
+    }
+
+    /// Constructs a new `CatmullRomSpline` from points `(x, y)` yielded by an iterator, without
+    /// collecting them into a slice first.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new`].
+    pub fn try_from_iter(
+        raw_points: impl IntoIterator<Item = (V, V)>,
+    ) -> Result<Self, InterpolationError<V>> {
+        let (xs, ys) = raw_points.into_iter().unzip();
+        Self::try_from_columns(xs, ys)
+    }
+
+    fn try_from_columns(xs: Vec<V>, ys: Vec<V>) -> Result<Self, InterpolationError<V>> {
+        if xs.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(xs.len()));
         }
-        let mut temp = raw_points[0].0;
-        let mut points = Vec::new();
-        for &(x, y) in raw_points {
-            let point = Point2 { x, y };
-            if point.x < temp {
-                return Err(HermiteSplineError::PointOrderError);
-            }
-            temp = point.x;
-            points.push(point);
+        check_finite(xs.iter().zip(&ys).map(|(&x, &y)| [x, y]))?;
+        check_knots(xs.iter().copied())?;
+        Ok(Self {
+            xs,
+            ys,
+            tolerance: Tolerance::Exact,
+            spacing: None,
+            metadata: Metadata::default(),
+        })
+    }
+
+    /// Constructs a new `CatmullRomSpline` from parallel columns of x- and y-coordinates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::LengthMismatchError(xs.len(), ys.len())` if the columns differ
+    /// in length, and otherwise the errors of [`Self::try_new`].
+    pub fn try_new_xy(xs: &[V], ys: &[V]) -> Result<Self, InterpolationError<V>> {
+        if xs.len() != ys.len() {
+            return Err(InterpolationError::LengthMismatchError(xs.len(), ys.len()));
         }
-        Ok(Self { points })
+        Self::try_from_columns(xs.to_vec(), ys.to_vec())
+    }
+
+    /// Constructs a new `CatmullRomSpline` through `(x0 + i dx, ys[i])`, whose evaluation finds
+    /// the segment of a query in constant time as [`NaturalCubicSpline::try_new_uniform`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new`] for the generated points.
+    ///
+    /// [`NaturalCubicSpline::try_new_uniform`]: crate::interpolation::natural_cubic_spline::NaturalCubicSpline::try_new_uniform
+    pub fn try_new_uniform(x0: V, dx: V, ys: &[V]) -> Result<Self, InterpolationError<V>> {
+        let mut spline = Self::try_from_iter(uniform_knots(x0, dx).zip(ys.iter().copied()))?;
+        spline.spacing = Some((x0, dx));
+        Ok(spline)
+    }
+
+    /// Constructs a new `CatmullRomSpline` from points in any order, sorting them by x-coordinate and
+    /// merging the points sharing one with `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(i)` if the `i`-th point has a NaN or infinite
+    /// coordinate, and the errors of [`Self::try_new`] for the merged points.
+    pub fn try_new_unsorted(
+        raw_points: &[(V, V)],
+        policy: DedupPolicy,
+    ) -> Result<Self, InterpolationError<V>> {
+        Self::try_new(&policy.sort_and_apply(raw_points)?)
+    }
+
+    /// Sets the tolerance within which a query is treated as hitting a knot exactly,
+    /// returning the stored value instead of evaluating a segment.
+    ///
+    /// Queries within the tolerance of the first or last knot are accepted even if they lie
+    /// slightly outside the domain. The default is [`Tolerance::Exact`].
+    pub fn with_tolerance(mut self, tolerance: Tolerance<V>) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Attaches provenance metadata to the spline.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the provenance metadata of the spline.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the provenance metadata of the spline for editing.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Returns the index of the segment containing `x`; see
+    /// [`PiecewisePolynomial::segment_index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` outside the knots, and
+    /// `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn segment_index(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        segment_index(&self.xs, x)
+    }
+
+    /// Returns the knots `(start, end)` of the `i`-th segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than the number of segments, one less than the number of knots.
+    pub fn segment_bounds(&self, i: usize) -> (V, V) {
+        (self.xs[i], self.xs[i + 1])
+    }
+
+    /// Returns the index of the knot nearest to `x`; see [`PiecewisePolynomial::nearest_knot`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn nearest_knot(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        nearest_knot(&self.xs, x)
+    }
+
+    /// Returns the highest degree of polynomials this spline reproduces exactly, which is 1:
+    /// the chord and secant slopes are exact for linear data only.
+    pub fn degree_exactness(&self) -> usize {
+        1
+    }
+
+    /// Inserts the point `(x, y)` at its place in the knot order.
+    ///
+    /// Only the segments within two knots of the new point change; the spline is not refitted.
+    ///
+    /// # Returns
+    ///
+    /// The index of the new knot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::DuplicatePointError(x)` if there already is a knot at `x`, and
+    /// `InterpolationError::NonFiniteError(n)` if a coordinate of the point, which would be the
+    /// `n`-th, is NaN or infinite. The spline is then unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+    ///
+    /// let mut spline = CatmullRomSpline::try_new(&[(0.0, 0.0), (1.0, 1.0), (3.0, 3.0)]).unwrap();
+    /// assert_eq!(spline.insert_point(2.0, 0.0).unwrap(), 2);
+    /// assert_eq!(spline.try_value(2.0).unwrap(), 0.0);
+    /// assert_eq!(spline.remove_point(2).unwrap(), (2.0, 0.0));
+    /// ```
+    pub fn insert_point(&mut self, x: V, y: V) -> Result<usize, InterpolationError<V>> {
+        let i = insertion_index(&self.xs, [x, y])?;
+        self.xs.insert(i, x);
+        self.ys.insert(i, y);
+        self.spacing = None;
+        Ok(i)
     }
+
+    /// Removes the `index`-th knot and returns it as `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if fewer than 2 points would remain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_point(&mut self, index: usize) -> Result<(V, V), InterpolationError<V>> {
+        check_removal(self.xs.len(), index, 2)?;
+        let (x, y) = (self.xs.remove(index), self.ys.remove(index));
+        self.spacing = None;
+        Ok((x, y))
+    }
+
+    /// Appends a point after the last one; the caller guarantees the ordering.
+    ///
+    /// Only the trailing segments change: every segment but the last two keeps its coefficients.
+    pub(crate) fn push_unchecked(&mut self, x: V, y: V) {
+        self.xs.push(x);
+        self.ys.push(y);
+        self.spacing = None;
+    }
+
+    /// Returns the x-coordinate of the `i`-th knot.
+    pub(crate) fn knot(&self, i: usize) -> V {
+        self.xs[i]
+    }
+
     /// Tries to find the value `x` in the Hermite spline.
     ///
     /// # Arguments
@@ -65,121 +269,422 @@ impl<V: InterpolationValue> CatmullRomSpline<V> {
     /// # Returns
     ///
     /// * `Ok(V)`: If the value `x` is found in the Hermite spline, returns the corresponding value `V`.
-    /// * `Err(HermiteSplineError<V>)`: If the value `x` is not found, returns an error indicating whether `x` is out of the lower or upper bound of the spline.
-    pub fn try_value(self, x: V) -> Result<V, HermiteSplineError<V>> {
-        match self
-            .points
-            .binary_search_by(|point| point.x.partial_cmp(&x).unwrap())
-        {
-            Ok(pos) => Ok(self.points[pos].y),
-            Err(pos) => {
-                if pos.is_zero() {
-                    return Err(HermiteSplineError::OutOfLowerBound(x));
-                }
-                if pos > self.points.len() {
-                    return Err(HermiteSplineError::OutOfUpperBound(x));
+    /// * `Err(InterpolationError<V>)`: If the value `x` is not found, returns an error indicating whether `x` is out of the lower or upper bound of the spline.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => Ok(self.ys[pos]),
+            Location::Segment(pos) => Ok(self.value_in_segment(pos, x)),
+        }
+    }
+
+    /// Evaluates the spline at `x` without checking it, for hot loops over queries already known
+    /// to lie within the knots.
+    ///
+    /// Unlike [`Self::try_value`], a query at a knot is evaluated with the polynomial of its
+    /// segment, which may round differently from the stored value.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `x` is NaN or outside the knots. Release builds extrapolate the
+    /// end segments instead, and return NaN for a NaN query.
+    pub fn value_unchecked(&self, x: V) -> V {
+        self.value_in_segment(segment_index_unchecked(&self.xs, x), x)
+    }
+
+    /// Evaluates the polynomial of the `pos`-th segment at `x`.
+    fn value_in_segment(&self, pos: usize, x: V) -> V {
+        record_segment_evaluation();
+        let h = self.xs[pos + 1] - self.xs[pos];
+        let delta = (x - self.xs[pos]) / h;
+        let delta2 = delta * delta;
+        let delta3 = delta2 * delta;
+        let d = Vector4::new(delta3, delta2, delta, V::one());
+        (d.transpose() * self.segment_coefficients(pos)).x
+    }
+
+    /// Returns the derivative of the value at `x` with respect to each of the y-coordinates, e.g.
+    /// for analytic bucketed deltas.
+    ///
+    /// The value is linear in the y-coordinates with the knots fixed, so these are also the weights
+    /// of the y-coordinates in the value. The slopes are local, so at most the four values around
+    /// the segment containing `x` have a nonzero weight.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn value_gradient(&self, x: V) -> Result<Vec<V>, InterpolationError<V>> {
+        let mut gradient = vec![V::zero(); self.xs.len()];
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => gradient[pos] = V::one(),
+            Location::Segment(pos) => {
+                let t = (x - self.xs[pos]) / (self.xs[pos + 1] - self.xs[pos]);
+                let d = Vector4::new(t * t * t, t * t, t, V::one());
+                let weights = d.transpose() * self.segment_matrix(pos);
+                for (k, &weight) in weights.iter().enumerate() {
+                    if let Some(g) = (pos + k).checked_sub(1).and_then(|i| gradient.get_mut(i)) {
+                        *g += weight;
+                    }
                 }
-                let pos = pos - 1;
-                let point = &self.points[pos];
-                let next_point = &self.points[pos + 1];
-                let h = next_point.x - point.x;
-                let delta = (x - point.x) / h;
-                let delta2 = delta * delta;
-                let delta3 = delta2 * delta;
-                let d = Vector4::new(delta3, delta2, delta, V::one());
-                Ok((d.transpose()
-                    * if pos == 0 {
-                        let next_next_point = &self.points[pos + 2];
-                        let next_h = next_next_point.x - next_point.x;
-                        let beta = h / (h + next_h);
-                        Matrix4::new(
-                            V::zero(),
-                            V::one() - beta,
-                            -V::one(),
-                            beta,
-                            V::zero(),
-                            -V::one() + beta,
-                            V::one(),
-                            -beta,
-                            V::zero(),
-                            -V::one(),
-                            V::one(),
-                            V::zero(),
-                            V::zero(),
-                            V::one(),
-                            V::zero(),
-                            V::zero(),
-                        )
-                        .mul(Vector4::new(
-                            V::zero(),
-                            point.y,
-                            next_point.y,
-                            next_next_point.y,
-                        ))
-                    } else if pos + 2 == self.points.len() {
-                        let prev_point = &self.points[pos - 1];
-                        let prev_h = next_point.x - prev_point.x;
-                        let alpha = h / (h + prev_h);
-                        Matrix4::new(
-                            -alpha,
-                            V::one(),
-                            -V::one() * alpha,
-                            V::zero(),
-                            V::from_i8(2).unwrap() * alpha,
-                            V::from_i8(-2).unwrap(),
-                            V::from_i8(2).unwrap() - V::from_i8(2).unwrap() * alpha,
-                            V::zero(),
-                            -alpha,
-                            V::zero(),
-                            alpha,
-                            V::zero(),
-                            V::zero(),
-                            V::one(),
-                            V::zero(),
-                            V::zero(),
-                        )
-                        .mul(Vector4::new(
-                            prev_point.y,
-                            point.y,
-                            next_point.y,
-                            V::zero(),
-                        ))
-                    } else {
-                        let prev_point = &self.points[pos - 1];
-                        let prev_h = next_point.x - prev_point.x;
-                        let alpha = h / (h + prev_h);
-                        let next_next_point = &self.points[pos + 2];
-                        let next_h = next_next_point.x - next_point.x;
-                        let beta = h / (h + next_h);
-                        Matrix4::new(
-                            -alpha,
-                            V::from_i8(2).unwrap() - beta,
-                            V::from_i8(-2).unwrap() + alpha,
-                            beta,
-                            V::from_i8(2).unwrap() * alpha,
-                            beta - V::from_i8(3).unwrap(),
-                            V::from_i8(3).unwrap() - V::from_i8(2).unwrap() * alpha,
-                            -beta,
-                            -alpha,
-                            V::zero(),
-                            alpha,
-                            V::zero(),
-                            V::zero(),
-                            V::one(),
-                            V::zero(),
-                            V::zero(),
-                        )
-                        .mul(Vector4::new(
-                            prev_point.y,
-                            point.y,
-                            next_point.y,
-                            next_next_point.y,
-                        ))
-                    })
-                .x)
+            }
+        }
+        Ok(gradient)
+    }
+
+    /// Evaluates the spline and its first and second derivatives at `x` with a single segment
+    /// lookup.
+    ///
+    /// At a knot the derivatives are those of the segment starting there, or ending there for the
+    /// last knot.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_eval_all(&self, x: V) -> Result<SplineSample<V>, InterpolationError<V>> {
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => {
+                let segment = pos.min(self.xs.len() - 2);
+                let t = if segment == pos { V::zero() } else { V::one() };
+                Ok(SplineSample {
+                    value: self.ys[pos],
+                    ..self.sample_in_segment(segment, t)
+                })
+            }
+            Location::Segment(pos) => {
+                let t = (x - self.xs[pos]) / (self.xs[pos + 1] - self.xs[pos]);
+                Ok(self.sample_in_segment(pos, t))
             }
         }
     }
+
+    /// Returns the cubic Bézier curve of each segment, e.g. to draw the spline with a vector
+    /// renderer.
+    pub fn to_bezier_segments(&self) -> Vec<CubicBezier<V>> {
+        (0..self.xs.len() - 1)
+            .map(|pos| {
+                CubicBezier::from_hermite(
+                    (self.xs[pos], self.ys[pos]),
+                    self.sample_in_segment(pos, V::zero()).dydx,
+                    (self.xs[pos + 1], self.ys[pos + 1]),
+                    self.sample_in_segment(pos, V::one()).dydx,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the jumps of the spline and its first two derivatives at the interior knots, where
+    /// the spline is C1 and only the curvature jumps.
+    pub fn continuity_report(&self) -> ContinuityReport<V> {
+        ContinuityReport::from_segment_ends(
+            &self.xs,
+            (0..self.xs.len() - 1).map(|pos| {
+                (
+                    self.sample_in_segment(pos, V::zero()),
+                    self.sample_in_segment(pos, V::one()),
+                )
+            }),
+        )
+    }
+
+    /// Returns `n` points `(x, y)` of the graph of the spline equally spaced by arc length, the
+    /// first and last knots included, at least 2; see [`PiecewisePolynomial::sample_evenly`].
+    pub fn sample_evenly(&self, n: usize) -> Vec<(V, V)>
+    where
+        V: TranscendentalValue,
+    {
+        PiecewisePolynomial::from(self)
+            .evenly_spaced_xs(n)
+            .into_iter()
+            .map(|x| {
+                (
+                    x,
+                    self.try_value(x).expect("the samples lie inside the knots"),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns every `x` at which the spline crosses the level `y`, in increasing order; see
+    /// [`PiecewisePolynomial::intersect_level`].
+    pub fn intersect_level(&self, y: V) -> Vec<V> {
+        PiecewisePolynomial::from(self).intersect_level(y)
+    }
+
+    /// Returns every `x` at which the spline crosses the line `a + b x`, in increasing order; see
+    /// [`PiecewisePolynomial::intersect_line`].
+    pub fn intersect_line(&self, a: V, b: V) -> Vec<V> {
+        PiecewisePolynomial::from(self).intersect_line(a, b)
+    }
+
+    /// Returns the lowest point `(x, y)` of the spline over `range`; see
+    /// [`PiecewisePolynomial::argmin`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the knots.
+    pub fn argmin(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        let (x, _) = PiecewisePolynomial::from(self).argmin(range)?;
+        Ok((x, self.try_value(x)?))
+    }
+
+    /// Returns the highest point `(x, y)` of the spline over `range`; see
+    /// [`PiecewisePolynomial::argmax`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the knots.
+    pub fn argmax(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        let (x, _) = PiecewisePolynomial::from(self).argmax(range)?;
+        Ok((x, self.try_value(x)?))
+    }
+
+    /// Returns the largest absolute and relative differences to the reference `other`, e.g. a
+    /// spline of another method through the same points, over the common domain of both; see
+    /// [`PiecewisePolynomial::max_difference`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`PiecewisePolynomial::max_difference`].
+    pub fn max_difference(
+        &self,
+        other: impl Into<PiecewisePolynomial<V>>,
+    ) -> Result<MaxDifference<V>, InterpolationError<V>> {
+        PiecewisePolynomial::from(self).max_difference(&other.into())
+    }
+
+    /// Returns the spline as a LaTeX `cases` environment of its polynomial on each segment, see
+    /// [`PiecewisePolynomial::to_latex`].
+    pub fn to_latex(&self) -> String
+    where
+        V: Display,
+    {
+        PiecewisePolynomial::from(self).to_latex()
+    }
+
+    /// Returns the SVG path data, the `d` attribute, of the graph of the spline.
+    #[cfg(feature = "svg")]
+    pub fn to_svg_path_data(&self, sampling: PathSampling) -> String
+    where
+        V: Display,
+    {
+        match sampling {
+            PathSampling::Exact => svg::bezier_path_data(&self.to_bezier_segments()),
+            PathSampling::Polyline(points) => {
+                svg::polyline_path_data((self.xs[0], self.xs[self.xs.len() - 1]), points, |x| {
+                    self.try_value(x).expect("the samples lie inside the knots")
+                })
+            }
+        }
+    }
+
+    /// Evaluates the `pos`-th segment at the normalized position `t`.
+    fn sample_in_segment(&self, pos: usize, t: V) -> SplineSample<V> {
+        record_segment_evaluation();
+        let h = self.xs[pos + 1] - self.xs[pos];
+        SplineSample::from_normalized_cubic(&self.segment_coefficients(pos), t, h)
+    }
+
+    /// Estimates the interpolation error on each segment from the error of the knot slopes.
+    ///
+    /// The slopes are chords through the neighbouring points (secants at the ends), whose error is
+    /// about `|f''| |h₁ - h₀| / 2 + |f'''| (h₀² - h₀h₁ + h₁²) / 6` for neighbouring widths `h₀`, `h₁`,
+    /// or `|f''| h / 2` for a secant. A slope error `e` at either end of a segment of width `h`
+    /// changes the interpolant by at most `h e / 4`. The derivatives are estimated from divided
+    /// differences of the data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if there are fewer than 4 points.
+    pub fn error_bound_estimate(&self) -> Result<Vec<V>, InterpolationError<V>> {
+        let (xs, ys) = (&self.xs, &self.ys);
+        let d3 = derivative_estimates(xs, ys, 3)?;
+        let d2 = derivative_estimates(xs, ys, 2)?;
+        let two = V::from_integer(2);
+        let six = V::from_integer(6);
+        let last = xs.len() - 1;
+        let slope_error = |k: usize, i: usize| {
+            if k == 0 {
+                d2[i] * (xs[1] - xs[0]) / two
+            } else if k == last {
+                d2[i] * (xs[last] - xs[last - 1]) / two
+            } else {
+                let (h0, h1) = (xs[k] - xs[k - 1], xs[k + 1] - xs[k]);
+                d2[i] * abs(h1 - h0) / two + d3[i] * (h0 * h0 - h0 * h1 + h1 * h1) / six
+            }
+        };
+        Ok((0..last)
+            .map(|i| {
+                let (e0, e1) = (slope_error(i, i), slope_error(i + 1, i));
+                let e = if e0 > e1 { e0 } else { e1 };
+                (xs[i + 1] - xs[i]) * e / V::from_integer(4)
+            })
+            .collect())
+    }
+
+    /// Returns the coefficients of `(t^3, t^2, t, 1)` on the `pos`-th segment,
+    /// where `t` is the position normalized to `[0, 1]`.
+    fn segment_coefficients(&self, pos: usize) -> Vector4<V> {
+        let window = Vector4::new(
+            pos.checked_sub(1).map_or(V::zero(), |i| self.ys[i]),
+            self.ys[pos],
+            self.ys[pos + 1],
+            self.ys.get(pos + 2).copied().unwrap_or(V::zero()),
+        );
+        self.segment_matrix(pos).mul(window)
+    }
+
+    /// Returns the matrix taking the values at the knots `pos - 1` to `pos + 2` to the
+    /// coefficients of the `pos`-th segment, with zero columns for knots beyond the ends.
+    fn segment_matrix(&self, pos: usize) -> Matrix4<V> {
+        let xs = &self.xs;
+        let h = xs[pos + 1] - xs[pos];
+        if xs.len() == 2 {
+            // both end slopes are the secant
+            Matrix4::new(
+                V::zero(),
+                V::zero(),
+                V::zero(),
+                V::zero(),
+                V::zero(),
+                V::zero(),
+                V::zero(),
+                V::zero(),
+                V::zero(),
+                -V::one(),
+                V::one(),
+                V::zero(),
+                V::zero(),
+                V::one(),
+                V::zero(),
+                V::zero(),
+            )
+        } else if pos == 0 {
+            let next_h = xs[pos + 2] - xs[pos + 1];
+            let beta = h / (h + next_h);
+            Matrix4::new(
+                V::zero(),
+                V::one() - beta,
+                -V::one(),
+                beta,
+                V::zero(),
+                -V::one() + beta,
+                V::one(),
+                -beta,
+                V::zero(),
+                -V::one(),
+                V::one(),
+                V::zero(),
+                V::zero(),
+                V::one(),
+                V::zero(),
+                V::zero(),
+            )
+        } else if pos + 2 == xs.len() {
+            let prev_h = xs[pos] - xs[pos - 1];
+            let alpha = h / (h + prev_h);
+            Matrix4::new(
+                -alpha,
+                V::one(),
+                alpha - V::one(),
+                V::zero(),
+                V::from_integer(2) * alpha,
+                V::from_integer(-2),
+                V::from_integer(2) - V::from_integer(2) * alpha,
+                V::zero(),
+                -alpha,
+                V::zero(),
+                alpha,
+                V::zero(),
+                V::zero(),
+                V::one(),
+                V::zero(),
+                V::zero(),
+            )
+        } else {
+            let prev_h = xs[pos] - xs[pos - 1];
+            let alpha = h / (h + prev_h);
+            let next_h = xs[pos + 2] - xs[pos + 1];
+            let beta = h / (h + next_h);
+            Matrix4::new(
+                -alpha,
+                V::from_integer(2) - beta,
+                V::from_integer(-2) + alpha,
+                beta,
+                V::from_integer(2) * alpha,
+                beta - V::from_integer(3),
+                V::from_integer(3) - V::from_integer(2) * alpha,
+                -beta,
+                -alpha,
+                V::zero(),
+                alpha,
+                V::zero(),
+                V::zero(),
+                V::one(),
+                V::zero(),
+                V::zero(),
+            )
+        }
+    }
+}
+
+impl<V: InterpolationValue> From<&CatmullRomSpline<V>> for PiecewisePolynomial<V> {
+    fn from(spline: &CatmullRomSpline<V>) -> Self {
+        PiecewisePolynomial::from_normalized_cubics(
+            spline.xs.clone(),
+            (0..spline.xs.len() - 1).map(|pos| spline.segment_coefficients(pos)),
+        )
+        .with_metadata(spline.metadata.clone())
+    }
+}
+
+impl_interpolator!(CatmullRomSpline, CatmullRom, metadata);
+
+impl<V: InterpolationValue> Debug for CatmullRomSpline<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatmullRomSpline")
+            .field("xs", &self.xs)
+            .field("ys", &self.ys)
+            .field("tolerance", &self.tolerance)
+            .field("metadata", &self.metadata)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Writes the polynomial on each segment, see the [`Display`] form of [`PiecewisePolynomial`].
+impl<V: InterpolationValue + Display> Display for CatmullRomSpline<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&PiecewisePolynomial::from(self), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V: InterpolationValue + serde::Serialize> serde::Serialize for CatmullRomSpline<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SplineDocument {
+            points: self
+                .xs
+                .iter()
+                .copied()
+                .zip(self.ys.iter().copied())
+                .collect(),
+            tolerance: self.tolerance,
+            metadata: self.metadata.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Fits the spline again through the deserialized points, failing as [`CatmullRomSpline::try_new`].
+#[cfg(feature = "serde")]
+impl<'de, V: InterpolationValue + Display + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for CatmullRomSpline<V>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = SplineDocument::<(V, V), V>::deserialize(deserializer)?;
+        Ok(Self::try_new(&document.points)
+            .map_err(serde::de::Error::custom)?
+            .with_tolerance(document.tolerance)
+            .with_metadata(document.metadata))
+    }
 }
 
 #[cfg(test)]
@@ -188,13 +693,90 @@ mod tests {
     use rust_decimal::Decimal;
 
     use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::InterpolationError;
 
     #[test]
     fn test_f64() {
         let points = [(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)];
         let interpolator = CatmullRomSpline::try_new(&points).unwrap();
         let val = interpolator.try_value(0.75).unwrap();
-        assert!((val - 0.27083333333333337_f64).abs() < f64::EPSILON);
+        assert!((val - 0.25_f64).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_two_points() {
+        let mut spline = CatmullRomSpline::try_new(&[(1.0, 2.0), (3.0, 1.0)]).unwrap();
+        assert_eq!(spline.try_value(2.0).unwrap(), 1.5);
+        let sample = spline.try_eval_all(3.0).unwrap();
+        assert_eq!((sample.value, sample.dydx, sample.d2ydx2), (1.0, -0.5, 0.0));
+        assert_eq!(spline.insert_point(2.0, 0.0).unwrap(), 1);
+        spline.remove_point(1).unwrap();
+        assert!(matches!(
+            spline.remove_point(0),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+        assert!(matches!(
+            CatmullRomSpline::try_new(&[(0.0, 1.0)]),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+    }
+
+    #[test]
+    fn test_insert_non_finite_point() {
+        let mut spline = CatmullRomSpline::try_new(&[(0.0, 0.0), (1.0, 1.0), (3.0, 3.0)]).unwrap();
+        for (x, y) in [
+            (f64::NAN, 1.0),
+            (f64::INFINITY, 1.0),
+            (f64::NEG_INFINITY, 1.0),
+            (2.0, f64::NAN),
+            (2.0, f64::NEG_INFINITY),
+        ] {
+            assert!(matches!(
+                spline.insert_point(x, y),
+                Err(InterpolationError::NonFiniteError(3))
+            ));
+        }
+        assert!(matches!(
+            spline.try_value(-0.01),
+            Err(InterpolationError::OutOfLowerBound(_))
+        ));
+        assert_eq!(spline.try_value(2.0).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn test_column_and_iterator_constructors() {
+        let xs = [0.0, 0.5, 1.0, 2.0];
+        let ys = [1.0, 0.5, 0.0, 1.0];
+        let from_columns = CatmullRomSpline::try_new_xy(&xs, &ys).unwrap();
+        let from_iter = CatmullRomSpline::try_from_iter(xs.into_iter().zip(ys)).unwrap();
+        for x in [0.0, 0.3, 0.75, 1.6] {
+            assert_eq!(
+                from_columns.try_value(x).unwrap(),
+                from_iter.try_value(x).unwrap()
+            );
+        }
+        assert!(matches!(
+            CatmullRomSpline::try_new_xy(&xs, &ys[..3]),
+            Err(InterpolationError::LengthMismatchError(4, 3))
+        ));
+    }
+
+    #[test]
+    fn test_error_bound_estimate() {
+        let points: Vec<(f64, f64)> = [0.0, 0.4, 0.7, 1.2, 1.5, 2.1]
+            .iter()
+            .map(|&x: &f64| (x, x.exp()))
+            .collect();
+        let interpolator = CatmullRomSpline::try_new(&points).unwrap();
+        let bounds = interpolator.error_bound_estimate().unwrap();
+        assert_eq!(bounds.len(), 5);
+        for (w, bound) in points.windows(2).zip(bounds) {
+            let error = (1..100)
+                .map(|k| w[0].0 + (w[1].0 - w[0].0) * k as f64 / 100.0)
+                .map(|x| (interpolator.try_value(x).unwrap() - x.exp()).abs())
+                .fold(0.0, f64::max);
+            assert!(error <= bound, "error {error} above the bound {bound}");
+        }
     }
 
     #[cfg(feature = "decimal")]
@@ -207,9 +789,6 @@ mod tests {
         ];
         let interpolator = CatmullRomSpline::try_new(&points).unwrap();
         let val = interpolator.try_value(Decimal::new(75, 2)).unwrap();
-        assert_eq!(
-            val,
-            Decimal::from_str_exact("0.2708333333333333333333333333").unwrap()
-        );
+        assert_eq!(val, Decimal::new(25, 2));
     }
 }
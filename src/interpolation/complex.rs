@@ -0,0 +1,167 @@
+//! Interpolation of complex ordinates over real abscissae, e.g. frequency responses.
+
+use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+use crate::interpolation::hermite_spline::HermiteSpline;
+use crate::interpolation::interpolator::Interpolator;
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::{InterpolationError, InterpolationValue};
+use num_complex::Complex;
+
+/// A spline through complex values at real knots, kept as one real spline of type `S` per part.
+///
+/// The splines of this crate are linear in their values and slopes, so interpolating the real and
+/// imaginary parts apart is the same as interpolating the complex values as a whole.
+///
+/// # Example
+///
+/// ```
+/// use num_complex::Complex;
+/// use spline_interpolation::interpolation::complex::ComplexSpline;
+///
+/// // the response 1 / (1 + i f) of a first-order low-pass filter
+/// let response: Vec<_> = (0..=20)
+///     .map(|k| {
+///         let f = k as f64 * 0.25;
+///         (f, Complex::new(1.0, 0.0) / Complex::new(1.0, f))
+///     })
+///     .collect();
+/// let spline = ComplexSpline::try_natural_cubic(&response).unwrap();
+/// let expected = Complex::new(1.0, 0.0) / Complex::new(1.0, 1.1);
+/// assert!((spline.try_value(1.1).unwrap() - expected).norm() < 1e-3);
+/// ```
+pub struct ComplexSpline<S> {
+    re: S,
+    im: S,
+}
+
+impl<S> ComplexSpline<S> {
+    /// Combines the splines of the real and imaginary parts, which should share their knots.
+    pub fn from_parts(re: S, im: S) -> Self {
+        Self { re, im }
+    }
+
+    /// Returns the spline of the real part.
+    pub fn re(&self) -> &S {
+        &self.re
+    }
+
+    /// Returns the spline of the imaginary part.
+    pub fn im(&self) -> &S {
+        &self.im
+    }
+
+    /// Splits the spline into the splines of the real and imaginary parts.
+    pub fn into_parts(self) -> (S, S) {
+        (self.re, self.im)
+    }
+
+    /// Evaluates the spline at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Interpolator::try_value`] for the spline of either part.
+    pub fn try_value<V: InterpolationValue>(
+        &self,
+        x: V,
+    ) -> Result<Complex<V>, InterpolationError<V>>
+    where
+        S: Interpolator<V>,
+    {
+        Ok(Complex::new(self.re.try_value(x)?, self.im.try_value(x)?))
+    }
+}
+
+/// Splits complex points into the real points of either part.
+fn split<V: InterpolationValue>(points: &[(V, Complex<V>)]) -> [Vec<(V, V)>; 2] {
+    [
+        points.iter().map(|(x, y)| (*x, y.re)).collect(),
+        points.iter().map(|(x, y)| (*x, y.im)).collect(),
+    ]
+}
+
+impl<V: InterpolationValue> ComplexSpline<NaturalCubicSpline<V>> {
+    /// Constructs natural cubic splines through the points `(x, y)` with complex `y`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`NaturalCubicSpline::try_new`].
+    pub fn try_natural_cubic(points: &[(V, Complex<V>)]) -> Result<Self, InterpolationError<V>> {
+        let [re, im] = split(points);
+        Ok(Self::from_parts(
+            NaturalCubicSpline::try_new(&re)?,
+            NaturalCubicSpline::try_new(&im)?,
+        ))
+    }
+}
+
+impl<V: InterpolationValue> ComplexSpline<CatmullRomSpline<V>> {
+    /// Constructs Catmull-Rom splines through the points `(x, y)` with complex `y`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`CatmullRomSpline::try_new`].
+    pub fn try_catmull_rom(points: &[(V, Complex<V>)]) -> Result<Self, InterpolationError<V>> {
+        let [re, im] = split(points);
+        Ok(Self::from_parts(
+            CatmullRomSpline::try_new(&re)?,
+            CatmullRomSpline::try_new(&im)?,
+        ))
+    }
+}
+
+impl<V: InterpolationValue> ComplexSpline<HermiteSpline<V>> {
+    /// Constructs Hermite splines through the points `(x, y, dy/dx)` with complex `y` and `dy/dx`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`HermiteSpline::try_new`].
+    pub fn try_hermite(
+        points: &[(V, Complex<V>, Complex<V>)],
+    ) -> Result<Self, InterpolationError<V>> {
+        let re: Vec<_> = points.iter().map(|(x, y, d)| (*x, y.re, d.re)).collect();
+        let im: Vec<_> = points.iter().map(|(x, y, d)| (*x, y.im, d.im)).collect();
+        Ok(Self::from_parts(
+            HermiteSpline::try_new(&re)?,
+            HermiteSpline::try_new(&im)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::complex::ComplexSpline;
+    use crate::InterpolationError;
+    use num_complex::Complex;
+
+    #[test]
+    fn test_complex_exponential() {
+        let xs: Vec<f64> = (0..=40).map(|k| k as f64 * 0.1).collect();
+        let e = |x: f64| Complex::new(0.0, x).exp();
+        let points: Vec<_> = xs.iter().map(|&x| (x, e(x))).collect();
+        let hermite_points: Vec<_> = xs
+            .iter()
+            .map(|&x| (x, e(x), Complex::new(0.0, 1.0) * e(x)))
+            .collect();
+        let natural = ComplexSpline::try_natural_cubic(&points).unwrap();
+        let catmull_rom = ComplexSpline::try_catmull_rom(&points).unwrap();
+        let hermite = ComplexSpline::try_hermite(&hermite_points).unwrap();
+        for k in 0..=80 {
+            let x = k as f64 * 0.05;
+            // the natural end conditions miss the curvature of the ends by O(h²)
+            assert!((natural.try_value(x).unwrap() - e(x)).norm() < 2e-3);
+            assert!((catmull_rom.try_value(x).unwrap() - e(x)).norm() < 1e-2);
+            assert!((hermite.try_value(x).unwrap() - e(x)).norm() < 1e-6);
+        }
+        assert_eq!(natural.try_value(xs[3]).unwrap(), e(xs[3]));
+        assert!(matches!(
+            natural.try_value(4.5),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+        let mut invalid = points.clone();
+        invalid[3].1.im = f64::NAN;
+        assert!(matches!(
+            ComplexSpline::try_natural_cubic(&invalid),
+            Err(InterpolationError::NonFiniteError(3))
+        ));
+    }
+}
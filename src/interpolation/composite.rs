@@ -0,0 +1,386 @@
+//! Interpolants stitched together from different methods over consecutive ranges of the knots,
+//! as curve construction specifications are often written.
+
+use crate::interpolation::builder::{Extrapolation, Method};
+use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+use crate::interpolation::hermite_spline::HermiteSpline;
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::shape::Shape;
+use crate::interpolation::{check_finite, check_knots, segment_index};
+use crate::{InterpolationError, InterpolationValue, TranscendentalValue};
+
+/// The method interpolating one piece of a [`CompositeInterpolator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceMethod {
+    /// Straight lines between the points.
+    Linear,
+    /// Straight lines between the logarithms of the values, e.g. discount factors with piecewise
+    /// constant forward rates. The values must be positive.
+    LogLinear,
+    /// A spline of the given method through the points of the piece.
+    Spline(Method),
+    /// A Hermite spline with the given shape through the points of the piece, see
+    /// [`HermiteSpline::try_new_with_shape`].
+    Shaped(Shape),
+}
+
+/// Builds a [`CompositeInterpolator`] from points `(x, y)`, one method for each range of knots
+/// between consecutive seams.
+///
+/// Every piece is fitted to its own points only, the seams included, so the interpolant is
+/// continuous at the seams while its slope may jump there.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::builder::{Extrapolation, Method};
+/// use spline_interpolation::interpolation::composite::{CompositeBuilder, PieceMethod};
+/// use spline_interpolation::interpolation::slopes::SlopeMethod;
+///
+/// // discount factors: log-linear up to 1y, a monotone cubic up to 30y and flat beyond
+/// let pillars = [
+///     (0.0_f64, 1.0),
+///     (0.25, 0.995),
+///     (1.0, 0.98),
+///     (5.0, 0.88),
+///     (10.0, 0.75),
+///     (30.0, 0.4),
+/// ];
+/// let curve = CompositeBuilder::new(&pillars, PieceMethod::LogLinear)
+///     .switch_at(1.0, PieceMethod::Spline(Method::Hermite(SlopeMethod::Pchip)))
+///     .extrapolation(Extrapolation::Constant)
+///     .build()
+///     .unwrap();
+/// // the forward rate is constant between the pillars at 0.25 and 1
+/// let forward = |a: f64, b: f64| {
+///     (curve.try_value(a).unwrap() / curve.try_value(b).unwrap()).ln() / (b - a)
+/// };
+/// assert!((forward(0.25, 0.5) - forward(0.5, 1.0)).abs() < 1e-12);
+/// assert_eq!(curve.try_value(1.0).unwrap(), 0.98);
+/// assert_eq!(curve.try_value(40.0).unwrap(), 0.4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositeBuilder<V: InterpolationValue> {
+    points: Vec<(V, V)>,
+    first: PieceMethod,
+    // the seams and the methods starting at them
+    switches: Vec<(V, PieceMethod)>,
+    extrapolation: Extrapolation,
+}
+
+impl<V: InterpolationValue> CompositeBuilder<V> {
+    /// Starts building an interpolant through the sorted `points`, interpolated with `method`
+    /// from the first point on and not extrapolated.
+    pub fn new(points: &[(V, V)], method: PieceMethod) -> Self {
+        Self {
+            points: points.to_vec(),
+            first: method,
+            switches: Vec::new(),
+            extrapolation: Extrapolation::default(),
+        }
+    }
+
+    /// Switches to `method` from the knot at `seam` on.
+    pub fn switch_at(mut self, seam: V, method: PieceMethod) -> Self {
+        self.switches.push((seam, method));
+        self
+    }
+
+    /// Sets how queries outside the points are answered, from the piece at that end.
+    pub fn extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+}
+
+impl<V: TranscendentalValue> CompositeBuilder<V> {
+    /// Builds the interpolant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::SeamError(x)` if the seam `x` is not an interior point after
+    /// the previous seam, `NonPositiveValueError(i)` if a value of a log-linear piece is not positive,
+    /// and otherwise the errors of validating the points and of fitting each piece, e.g.
+    /// `InsufficientPointsError(n)` for a natural cubic piece of 2 points.
+    pub fn build(self) -> Result<CompositeInterpolator<V>, InterpolationError<V>> {
+        let points = self.points;
+        if points.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(points.len()));
+        }
+        check_finite(points.iter().map(|&(x, y)| [x, y]))?;
+        check_knots(points.iter().map(|&(x, _)| x))?;
+        let mut starts = vec![0];
+        for &(seam, _) in &self.switches {
+            let previous = starts[starts.len() - 1];
+            match points[previous + 1..points.len() - 1]
+                .iter()
+                .position(|&(x, _)| x == seam)
+            {
+                Some(offset) => starts.push(previous + 1 + offset),
+                None => return Err(InterpolationError::SeamError(seam)),
+            }
+        }
+        starts.push(points.len() - 1);
+        let methods = [self.first]
+            .into_iter()
+            .chain(self.switches.iter().map(|&(_, method)| method));
+        let pieces = starts
+            .windows(2)
+            .zip(methods)
+            .map(|(range, method)| Piece::fit(&points[range[0]..=range[1]], range[0], method))
+            .collect::<Result<Vec<_>, _>>()?;
+        // the ends continue from the points themselves, which the pieces only reproduce up to
+        // rounding
+        let end = |piece: &Piece<V>, (x, y): (V, V)| -> Result<_, InterpolationError<V>> {
+            let slope = match self.extrapolation {
+                Extrapolation::Linear => piece.slope(x)?,
+                _ => V::zero(),
+            };
+            Ok((x, y, slope))
+        };
+        Ok(CompositeInterpolator {
+            lower: end(&pieces[0], points[0])?,
+            upper: end(&pieces[pieces.len() - 1], points[points.len() - 1])?,
+            seams: self.switches.iter().map(|&(seam, _)| seam).collect(),
+            pieces,
+            extrapolation: self.extrapolation,
+        })
+    }
+}
+
+/// An interpolant stitched together from different methods over consecutive ranges of knots, as
+/// built by [`CompositeBuilder`].
+#[derive(Debug, Clone)]
+pub struct CompositeInterpolator<V: InterpolationValue> {
+    pieces: Vec<Piece<V>>,
+    seams: Vec<V>,
+    extrapolation: Extrapolation,
+    // (x, y, dydx) continuing past the first and last point
+    lower: (V, V, V),
+    upper: (V, V, V),
+}
+
+impl<V: InterpolationValue> CompositeInterpolator<V> {
+    /// Returns the seams where the method changes, in ascending order.
+    pub fn seams(&self) -> &[V] {
+        &self.seams
+    }
+
+    /// Returns the method of the piece containing `x`, or `None` outside the points.
+    pub fn method_at(&self, x: V) -> Option<PieceMethod> {
+        let piece = &self.pieces[self.seams.partition_point(|&seam| seam <= x)];
+        piece.value(x).ok().map(|_| piece.method)
+    }
+
+    /// Evaluates the interpolant at `x`; a query at a seam is answered by the piece starting there.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` outside the points with
+    /// [`Extrapolation::Error`].
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        let piece = &self.pieces[self.seams.partition_point(|&seam| seam <= x)];
+        let (x0, y0, dydx) = match piece.value(x) {
+            Err(InterpolationError::OutOfLowerBound(_))
+                if self.extrapolation != Extrapolation::Error =>
+            {
+                self.lower
+            }
+            Err(InterpolationError::OutOfUpperBound(_))
+                if self.extrapolation != Extrapolation::Error =>
+            {
+                self.upper
+            }
+            result => return result,
+        };
+        Ok(y0 + dydx * (x - x0))
+    }
+}
+
+impl_interpolator!(CompositeInterpolator, Composite);
+
+/// One piece of a [`CompositeInterpolator`] with the method fitting it.
+#[derive(Debug, Clone)]
+struct Piece<V: InterpolationValue> {
+    method: PieceMethod,
+    curve: Curve<V>,
+}
+
+#[derive(Debug, Clone)]
+enum Curve<V: InterpolationValue> {
+    Polynomial(PiecewisePolynomial<V>),
+    // the knots, the logarithms of the values and the exponential undoing them
+    LogLinear {
+        xs: Vec<V>,
+        logs: Vec<V>,
+        exp: fn(V) -> V,
+    },
+}
+
+impl<V: InterpolationValue> Piece<V> {
+    fn value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        match &self.curve {
+            Curve::Polynomial(polynomial) => polynomial.try_value(x),
+            Curve::LogLinear { xs, logs, exp } => {
+                let i = segment_index(xs, x)?;
+                let t = (x - xs[i]) / (xs[i + 1] - xs[i]);
+                Ok(exp(logs[i] + t * (logs[i + 1] - logs[i])))
+            }
+        }
+    }
+
+    fn slope(&self, x: V) -> Result<V, InterpolationError<V>> {
+        match &self.curve {
+            Curve::Polynomial(polynomial) => polynomial.derivative().try_value(x),
+            Curve::LogLinear { xs, logs, .. } => {
+                let i = segment_index(xs, x)?;
+                Ok(self.value(x)? * (logs[i + 1] - logs[i]) / (xs[i + 1] - xs[i]))
+            }
+        }
+    }
+}
+
+impl<V: TranscendentalValue> Piece<V> {
+    /// Fits the points of a piece, the `offset`-th of all points and those following it.
+    fn fit(
+        points: &[(V, V)],
+        offset: usize,
+        method: PieceMethod,
+    ) -> Result<Self, InterpolationError<V>> {
+        let polynomial = |polynomial| Curve::Polynomial(polynomial);
+        let curve = match method {
+            PieceMethod::Linear => polynomial(PiecewisePolynomial::new_unchecked(
+                points.iter().map(|&(x, _)| x).collect(),
+                points
+                    .windows(2)
+                    .flat_map(|w| [(w[1].1 - w[0].1) / (w[1].0 - w[0].0), w[0].1])
+                    .collect(),
+                2,
+            )),
+            PieceMethod::LogLinear => {
+                if let Some(i) = points.iter().position(|&(_, y)| y <= V::zero()) {
+                    return Err(InterpolationError::NonPositiveValueError(offset + i));
+                }
+                Curve::LogLinear {
+                    xs: points.iter().map(|&(x, _)| x).collect(),
+                    logs: points.iter().map(|&(_, y)| y.ln()).collect(),
+                    exp: V::exp,
+                }
+            }
+            PieceMethod::Spline(Method::NaturalCubic) => {
+                polynomial((&NaturalCubicSpline::try_new(points)?).into())
+            }
+            PieceMethod::Spline(Method::CatmullRom) => {
+                polynomial((&CatmullRomSpline::try_new(points)?).into())
+            }
+            PieceMethod::Spline(Method::Hermite(slope_method)) => polynomial(
+                (&HermiteSpline::try_new_with_slope_method(points, slope_method)?).into(),
+            ),
+            PieceMethod::Shaped(shape) => {
+                polynomial((&HermiteSpline::try_new_with_shape(points, shape)?).into())
+            }
+        };
+        Ok(Self { method, curve })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::builder::{Extrapolation, Method};
+    use crate::interpolation::composite::{CompositeBuilder, PieceMethod};
+    use crate::interpolation::interpolator::{Interpolator, MethodTag};
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::shape::Shape;
+    use crate::InterpolationError;
+
+    const POINTS: [(f64, f64); 7] = [
+        (0.0, 1.0),
+        (1.0, 2.0),
+        (2.0, 4.0),
+        (3.0, 3.0),
+        (4.0, 5.0),
+        (5.0, 6.0),
+        (6.0, 6.5),
+    ];
+
+    #[test]
+    fn test_pieces() {
+        let curve = CompositeBuilder::new(&POINTS, PieceMethod::Linear)
+            .switch_at(2.0, PieceMethod::Spline(Method::NaturalCubic))
+            .switch_at(5.0, PieceMethod::Shaped(Shape::Increasing))
+            .extrapolation(Extrapolation::Linear)
+            .build()
+            .unwrap();
+        assert_eq!(curve.seams(), [2.0, 5.0]);
+        assert_eq!(curve.try_value(0.5).unwrap(), 1.5);
+        // the middle piece is the natural spline of its own points
+        let natural = NaturalCubicSpline::try_new(&POINTS[2..=5]).unwrap();
+        for x in [2.0, 2.5, 3.7, 4.9] {
+            assert!((curve.try_value(x).unwrap() - natural.try_value(x).unwrap()).abs() < 1e-12);
+        }
+        // continuous at the seams
+        for seam in [2.0, 5.0] {
+            let (left, right) = (
+                curve.try_value(seam - 1e-9).unwrap(),
+                curve.try_value(seam).unwrap(),
+            );
+            assert!((left - right).abs() < 1e-6);
+        }
+        assert_eq!(curve.method_at(1.0), Some(PieceMethod::Linear));
+        assert_eq!(
+            curve.method_at(5.0),
+            Some(PieceMethod::Shaped(Shape::Increasing))
+        );
+        assert_eq!(curve.method_at(7.0), None);
+        // extrapolated along the end slopes
+        assert_eq!(curve.try_value(-1.0).unwrap(), 0.0);
+        let dyn_curve: &dyn Interpolator<f64> = &curve;
+        assert_eq!(dyn_curve.method(), MethodTag::Composite);
+    }
+
+    #[test]
+    fn test_log_linear() {
+        let curve = CompositeBuilder::new(&POINTS[..3], PieceMethod::LogLinear)
+            .build()
+            .unwrap();
+        // geometric between the points
+        assert!((curve.try_value(0.5).unwrap() - 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!((curve.try_value(1.5).unwrap() - 8.0_f64.sqrt()).abs() < 1e-12);
+        assert!(matches!(
+            curve.try_value(2.5),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+        assert!(matches!(
+            CompositeBuilder::new(&[(0.0, 1.0), (1.0, 0.0)], PieceMethod::LogLinear).build(),
+            Err(InterpolationError::NonPositiveValueError(1))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_seams() {
+        for seams in [
+            vec![2.5],
+            vec![0.0],
+            vec![6.0],
+            vec![3.0, 2.0],
+            vec![3.0, 3.0],
+        ] {
+            let mut builder = CompositeBuilder::new(&POINTS, PieceMethod::Linear);
+            for &seam in &seams {
+                builder = builder.switch_at(seam, PieceMethod::Linear);
+            }
+            assert!(matches!(
+                builder.build(),
+                Err(InterpolationError::SeamError(_))
+            ));
+        }
+        // a natural cubic spline through 2 points is their line
+        let composite = CompositeBuilder::new(&POINTS, PieceMethod::Spline(Method::NaturalCubic))
+            .switch_at(1.0, PieceMethod::Linear)
+            .build()
+            .unwrap();
+        assert_eq!(composite.try_value(0.5).unwrap(), 1.5);
+    }
+}
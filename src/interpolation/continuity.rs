@@ -0,0 +1,164 @@
+//! Diagnostics of the smoothness of piecewise polynomials at their interior breakpoints.
+
+use crate::interpolation::sample::SplineSample;
+use crate::math::abs;
+use crate::tolerance::Tolerance;
+use crate::InterpolationValue;
+
+/// The jumps of a piecewise polynomial and its first two derivatives at one interior breakpoint,
+/// each the limit from the right minus the limit from the left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Junction<V: InterpolationValue> {
+    /// The breakpoint.
+    pub x: V,
+    /// The jump in value.
+    pub value_jump: V,
+    /// The jump in the first derivative.
+    pub slope_jump: V,
+    /// The jump in the second derivative.
+    pub curvature_jump: V,
+}
+
+/// The jumps at every interior breakpoint, as returned by the `continuity_report` methods, computed
+/// analytically from the coefficients of the segments meeting there.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+/// use spline_interpolation::tolerance::Tolerance;
+///
+/// let points = [(0.0_f64, 1.0), (0.4, 2.0), (1.0, 0.0), (2.5, 1.0), (3.0, 3.0)];
+/// let report = CatmullRomSpline::try_new(&points).unwrap().continuity_report();
+/// assert!(report.is_c1(Tolerance::Absolute(1e-12)));
+/// assert!(!report.is_c2(Tolerance::Absolute(1e-12)));
+/// let worst = report.worst_slope_jump().unwrap();
+/// assert!(worst.slope_jump.abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuityReport<V: InterpolationValue> {
+    junctions: Vec<Junction<V>>,
+    /// The limits from the left and from the right at each junction.
+    limits: Vec<(SplineSample<V>, SplineSample<V>)>,
+}
+
+impl<V: InterpolationValue> ContinuityReport<V> {
+    /// Builds the report from the samples of each segment at its start and at its end.
+    pub(crate) fn from_segment_ends(
+        breakpoints: &[V],
+        ends: impl IntoIterator<Item = (SplineSample<V>, SplineSample<V>)>,
+    ) -> Self {
+        let ends: Vec<_> = ends.into_iter().collect();
+        let limits: Vec<_> = ends.windows(2).map(|pair| (pair[0].1, pair[1].0)).collect();
+        let junctions = limits
+            .iter()
+            .zip(&breakpoints[1..])
+            .map(|(&(left, right), &x)| Junction {
+                x,
+                value_jump: right.value - left.value,
+                slope_jump: right.dydx - left.dydx,
+                curvature_jump: right.d2ydx2 - left.d2ydx2,
+            })
+            .collect();
+        Self { junctions, limits }
+    }
+
+    /// Returns the junctions in the order of the breakpoints.
+    pub fn junctions(&self) -> &[Junction<V>] {
+        &self.junctions
+    }
+
+    /// Returns the junction with the largest jump in the first derivative, or `None` without
+    /// interior breakpoints.
+    pub fn worst_slope_jump(&self) -> Option<&Junction<V>> {
+        self.worst_by(|junction| junction.slope_jump)
+    }
+
+    /// Returns the junction with the largest jump in the second derivative, or `None` without
+    /// interior breakpoints.
+    pub fn worst_curvature_jump(&self) -> Option<&Junction<V>> {
+        self.worst_by(|junction| junction.curvature_jump)
+    }
+
+    /// Returns `true` if the limits of the values and of the first derivatives from both sides
+    /// are equal under `tol` everywhere.
+    pub fn is_c1(&self, tol: Tolerance<V>) -> bool {
+        self.limits
+            .iter()
+            .all(|(left, right)| tol.eq(left.value, right.value) && tol.eq(left.dydx, right.dydx))
+    }
+
+    /// Returns `true` if moreover the limits of the second derivatives are equal under `tol`
+    /// everywhere.
+    pub fn is_c2(&self, tol: Tolerance<V>) -> bool {
+        self.is_c1(tol)
+            && self
+                .limits
+                .iter()
+                .all(|(left, right)| tol.eq(left.d2ydx2, right.d2ydx2))
+    }
+
+    fn worst_by(&self, jump: impl Fn(&Junction<V>) -> V) -> Option<&Junction<V>> {
+        self.junctions.iter().reduce(|worst, junction| {
+            if abs(jump(junction)) > abs(jump(worst)) {
+                junction
+            } else {
+                worst
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::tolerance::Tolerance;
+
+    const POINTS: [(f64, f64); 5] = [(0.0, 1.0), (0.3, -1.0), (1.0, 0.5), (1.2, 2.0), (3.0, 0.0)];
+
+    #[test]
+    fn test_spline_smoothness() {
+        let natural = NaturalCubicSpline::try_new(&POINTS)
+            .unwrap()
+            .continuity_report();
+        assert_eq!(natural.junctions().len(), 3);
+        assert!(natural.is_c2(Tolerance::Absolute(1e-9)));
+        // including the special-cased first and last segments
+        let catmull_rom = CatmullRomSpline::try_new(&POINTS)
+            .unwrap()
+            .continuity_report();
+        assert!(catmull_rom.is_c1(Tolerance::Absolute(1e-12)));
+        let hermite_points: Vec<_> = POINTS.iter().map(|&(x, y)| (x, y, y - x)).collect();
+        let hermite = HermiteSpline::try_new(&hermite_points)
+            .unwrap()
+            .continuity_report();
+        assert!(hermite.is_c1(Tolerance::Absolute(1e-12)));
+        assert!(!hermite.is_c2(Tolerance::Absolute(1e-12)));
+    }
+
+    #[test]
+    fn test_kink() {
+        // |x - 1| on [0, 2] and a step of 0.5 at 3
+        let polynomial = PiecewisePolynomial::try_new(
+            vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            vec![
+                vec![-1.0, 1.0],
+                vec![1.0, 0.0],
+                vec![0.0, 1.0],
+                vec![0.0, 1.5],
+            ],
+        )
+        .unwrap();
+        let report = polynomial.continuity_report();
+        assert!(!report.is_c1(Tolerance::Absolute(0.1)));
+        assert!(!report.is_c1(Tolerance::Relative(0.5)));
+        assert!(report.is_c1(Tolerance::Absolute(2.0)));
+        let worst = report.worst_slope_jump().unwrap();
+        assert_eq!((worst.x, worst.slope_jump), (1.0, 2.0));
+        assert_eq!(report.junctions()[2].value_jump, 0.5);
+        assert_eq!(report.worst_curvature_jump().unwrap().curvature_jump, 0.0);
+    }
+}
@@ -0,0 +1,164 @@
+//! Merging points that share an x-coordinate, as repeated timestamps of real data feeds.
+
+use crate::{InterpolationError, InterpolationValue};
+
+/// How to merge consecutive points sharing an x-coordinate into one.
+///
+/// The interpolants reject duplicate x-coordinates; merging them is opt-in, either up front with
+/// [`DedupPolicy::apply`] or while collecting points with
+/// [`PointBuilder::with_dedup_policy`](crate::interpolation::streaming::PointBuilder::with_dedup_policy).
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::dedup::DedupPolicy;
+///
+/// let points = [(0.0, 1.0), (1.0, 2.0), (1.0, 4.0), (2.0, 0.0)];
+/// assert_eq!(DedupPolicy::KeepFirst.apply(&points), [(0.0, 1.0), (1.0, 2.0), (2.0, 0.0)]);
+/// assert_eq!(DedupPolicy::KeepLast.apply(&points), [(0.0, 1.0), (1.0, 4.0), (2.0, 0.0)]);
+/// assert_eq!(DedupPolicy::Average.apply(&points), [(0.0, 1.0), (1.0, 3.0), (2.0, 0.0)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupPolicy {
+    /// Keeps the y-coordinate of the first point.
+    KeepFirst,
+    /// Keeps the y-coordinate of the last point.
+    KeepLast,
+    /// Takes the mean of the y-coordinates.
+    Average,
+}
+
+impl DedupPolicy {
+    /// Merges every run of consecutive points sharing an x-coordinate into one point.
+    ///
+    /// Points with equal x-coordinates that are not adjacent are kept, so that unsorted input is
+    /// still rejected by the constructors.
+    pub fn apply<V: InterpolationValue>(self, points: &[(V, V)]) -> Vec<(V, V)> {
+        self.merge_runs(points.iter().map(|&(x, y)| (x, [y])))
+            .into_iter()
+            .map(|(x, [y])| (x, y))
+            .collect()
+    }
+
+    /// Sorts the points by x-coordinate and merges the points sharing one, in their input order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(i)` if the `i`-th point has a NaN or infinite
+    /// coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::dedup::DedupPolicy;
+    ///
+    /// let points = [(1.0, 2.0), (0.0, 1.0), (1.0, 4.0)];
+    /// let sorted = DedupPolicy::KeepFirst.sort_and_apply(&points).unwrap();
+    /// assert_eq!(sorted, [(0.0, 1.0), (1.0, 2.0)]);
+    /// ```
+    pub fn sort_and_apply<V: InterpolationValue>(
+        self,
+        points: &[(V, V)],
+    ) -> Result<Vec<(V, V)>, InterpolationError<V>> {
+        Ok(self
+            .sort_and_merge_runs(points.iter().map(|&(x, y)| (x, [y])))?
+            .into_iter()
+            .map(|(x, [y])| (x, y))
+            .collect())
+    }
+
+    /// Sorts the points `(x, values)` by `x` and merges the values of the points sharing one.
+    pub(crate) fn sort_and_merge_runs<V: InterpolationValue, const N: usize>(
+        self,
+        points: impl IntoIterator<Item = (V, [V; N])>,
+    ) -> Result<Vec<(V, [V; N])>, InterpolationError<V>> {
+        let mut points: Vec<_> = points.into_iter().collect();
+        if let Some(i) = points
+            .iter()
+            .position(|(x, values)| !(x.is_finite() && values.iter().all(|v| v.is_finite())))
+        {
+            return Err(InterpolationError::NonFiniteError(i));
+        }
+        // stable, so that the points sharing an x-coordinate keep their input order
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Ok(self.merge_runs(points))
+    }
+
+    fn merge_runs<V: InterpolationValue, const N: usize>(
+        self,
+        points: impl IntoIterator<Item = (V, [V; N])>,
+    ) -> Vec<(V, [V; N])> {
+        let mut merged: Vec<(V, [V; N])> = Vec::new();
+        let mut run_len = 0;
+        for (x, values) in points {
+            match merged.last_mut() {
+                Some(last) if last.0 == x => {
+                    for (merged, value) in last.1.iter_mut().zip(values) {
+                        *merged = self.merge(*merged, run_len, value);
+                    }
+                    run_len += 1;
+                }
+                _ => {
+                    merged.push((x, values));
+                    run_len = 1;
+                }
+            }
+        }
+        merged
+    }
+
+    /// Merges `y` into `merged`, the y-coordinate of `run_len` points merged so far.
+    pub(crate) fn merge<V: InterpolationValue>(self, merged: V, run_len: usize, y: V) -> V {
+        match self {
+            DedupPolicy::KeepFirst => merged,
+            DedupPolicy::KeepLast => y,
+            DedupPolicy::Average => merged + (y - merged) / V::from_count(run_len + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::dedup::DedupPolicy;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_runs() {
+        let points = [(0.0, 1.0), (0.0, 2.0), (0.0, 6.0), (1.0, 0.0), (2.0, 1.0)];
+        assert!(matches!(
+            NaturalCubicSpline::try_new(&points),
+            Err(InterpolationError::DuplicatePointError(0.0))
+        ));
+        let merged = DedupPolicy::Average.apply(&points);
+        assert_eq!(merged, [(0.0, 3.0), (1.0, 0.0), (2.0, 1.0)]);
+        assert!(NaturalCubicSpline::try_new(&merged).is_ok());
+        let unsorted = [(0.0, 1.0), (1.0, 0.0), (0.0, 2.0)];
+        assert_eq!(DedupPolicy::KeepLast.apply(&unsorted), unsorted);
+    }
+
+    #[test]
+    fn test_unsorted_constructors() {
+        let points = [(2.0, 1.0), (0.0, 1.0), (1.0, 0.0), (0.0, 3.0)];
+        let spline = NaturalCubicSpline::try_new_unsorted(&points, DedupPolicy::KeepLast).unwrap();
+        let sorted = NaturalCubicSpline::try_new(&[(0.0, 3.0), (1.0, 0.0), (2.0, 1.0)]).unwrap();
+        for x in [0.0, 0.3, 1.0, 1.7] {
+            assert_eq!(spline.try_value(x).unwrap(), sorted.try_value(x).unwrap());
+        }
+        let spline = HermiteSpline::try_new_unsorted(
+            &[(1.0, 1.0, 0.0), (0.0, 0.0, 1.0), (1.0, 3.0, 2.0)],
+            DedupPolicy::Average,
+        )
+        .unwrap();
+        assert_eq!(spline.try_value(1.0).unwrap(), 2.0);
+        assert!(matches!(
+            CatmullRomSpline::try_new_unsorted(
+                &[(1.0, 0.0), (f64::NAN, 0.0), (0.0, 1.0)],
+                DedupPolicy::KeepFirst
+            ),
+            Err(InterpolationError::NonFiniteError(1))
+        ));
+    }
+}
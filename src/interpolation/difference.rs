@@ -0,0 +1,277 @@
+//! The largest difference between two piecewise polynomials, e.g. to quantify the impact of
+//! switching interpolation methods on a curve.
+
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::segment_index;
+use crate::math::abs;
+use crate::math::roots::{horner, roots_in};
+use crate::{InterpolationError, InterpolationValue};
+
+/// The largest differences between an interpolant `f` and a reference `g` over their common
+/// domain, as returned by the `max_difference` methods.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+///
+/// let points = [(0.0_f64, 1.0), (1.0, 1.5), (2.0, 1.8), (4.0, 2.1), (7.0, 2.3)];
+/// let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+/// let natural = NaturalCubicSpline::try_new(&points).unwrap();
+/// let difference = catmull_rom.max_difference(&natural).unwrap();
+/// let (x, worst) = difference.absolute;
+/// assert!(worst > 0.0 && worst < 0.1);
+/// let at_x = catmull_rom.try_value(x).unwrap() - natural.try_value(x).unwrap();
+/// assert!((worst - at_x.abs()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxDifference<V: InterpolationValue> {
+    /// The largest absolute difference `|f(x) - g(x)|`, as `(x, difference)`.
+    pub absolute: (V, V),
+    /// The largest relative difference `|f(x) - g(x)| / |g(x)|`, as `(x, difference)`, or `None`
+    /// if `g` vanishes where `f` does not, which leaves it unbounded.
+    pub relative: Option<(V, V)>,
+}
+
+impl<V: InterpolationValue> PiecewisePolynomial<V> {
+    /// Returns the largest absolute and relative differences to the reference `other` over the
+    /// common domain of both.
+    ///
+    /// The maxima are exact up to rounding: the merged breakpoints split the domain into pieces on
+    /// which the difference `d = f - g` is a polynomial, and the candidates are the ends of the
+    /// pieces, the roots of `d'` and the roots of `d' g - d g'`, where `|d / g|` is stationary.
+    /// The first of equally large candidates wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfUpperBound(x)` if the domains do not overlap, with `x` the later of their
+    /// starts.
+    pub fn max_difference(&self, other: &Self) -> Result<MaxDifference<V>, InterpolationError<V>> {
+        let (ours, theirs) = (self.breakpoints(), other.breakpoints());
+        let low = max(ours[0], theirs[0]);
+        let high = min(ours[ours.len() - 1], theirs[theirs.len() - 1]);
+        if high < low {
+            return Err(InterpolationError::OutOfUpperBound(low));
+        }
+        let mut breakpoints: Vec<V> = ours
+            .iter()
+            .chain(theirs)
+            .copied()
+            .filter(|&x| low <= x && x <= high)
+            .collect();
+        breakpoints.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        breakpoints.dedup();
+        let (mut absolute, mut relative) = ((low, V::zero()), Some((low, V::zero())));
+        let mut unbounded = false;
+        for piece in breakpoints.windows(2) {
+            let (start, end) = (piece[0], piece[1]);
+            let middle = (start + end) / V::from_integer(2);
+            let f = self.piece_at(middle, start)?;
+            let g = other.piece_at(middle, start)?;
+            let d = subtract(&f, &g);
+            let width = end - start;
+            let candidates = |polynomial: &[V]| roots_in(polynomial, V::zero(), width);
+            let stationary = candidates(&subtract(
+                &multiply(&derivative(&d), &g),
+                &multiply(&d, &derivative(&g)),
+            ));
+            for t in candidates(&derivative(&d))
+                .into_iter()
+                .chain(stationary)
+                .chain([V::zero(), width])
+            {
+                let x = if t == width { end } else { start + t };
+                let (difference, reference) = (abs(horner(&d, t)), abs(horner(&g, t)));
+                if difference > absolute.1 {
+                    absolute = (x, difference);
+                }
+                if reference != V::zero() {
+                    let ratio = difference / reference;
+                    if relative.is_some_and(|(_, worst)| ratio > worst) {
+                        relative = Some((x, ratio));
+                    }
+                }
+            }
+            unbounded |= candidates(&g)
+                .into_iter()
+                .any(|t| horner(&d, t) != V::zero());
+        }
+        if breakpoints.len() == 1 {
+            // the domains touch at a single point
+            let (difference, reference) = (
+                abs(self.try_value(low)? - other.try_value(low)?),
+                abs(other.try_value(low)?),
+            );
+            absolute = (low, difference);
+            unbounded = reference == V::zero() && difference != V::zero();
+            if !unbounded && reference != V::zero() {
+                relative = Some((low, difference / reference));
+            }
+        }
+        Ok(MaxDifference {
+            absolute,
+            relative: if unbounded { None } else { relative },
+        })
+    }
+
+    /// Returns the polynomial of the segment containing `x` in the variable `s = x - origin`,
+    /// highest power first.
+    fn piece_at(&self, x: V, origin: V) -> Result<Vec<V>, InterpolationError<V>> {
+        let i = segment_index(self.breakpoints(), x)?;
+        Ok(shifted(
+            self.coefficients(i),
+            origin - self.breakpoints()[i],
+        ))
+    }
+}
+
+/// Returns the coefficients of `p(t + shift)` for the polynomial `p`, highest power first, by
+/// repeated synthetic division.
+fn shifted<V: InterpolationValue>(coefficients: &[V], shift: V) -> Vec<V> {
+    let mut shifted = coefficients.to_vec();
+    let n = shifted.len();
+    for k in 0..n.saturating_sub(1) {
+        for j in 1..n - k {
+            let carried = shifted[j - 1] * shift;
+            shifted[j] += carried;
+        }
+    }
+    shifted
+}
+
+/// Returns `p - q`, highest power first.
+fn subtract<V: InterpolationValue>(p: &[V], q: &[V]) -> Vec<V> {
+    let n = p.len().max(q.len());
+    let at = |r: &[V], k: usize| {
+        if k + r.len() >= n {
+            r[k + r.len() - n]
+        } else {
+            V::zero()
+        }
+    };
+    (0..n).map(|k| at(p, k) - at(q, k)).collect()
+}
+
+/// Returns `p q`, highest power first.
+fn multiply<V: InterpolationValue>(p: &[V], q: &[V]) -> Vec<V> {
+    if p.is_empty() || q.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![V::zero(); p.len() + q.len() - 1];
+    for (i, &a) in p.iter().enumerate() {
+        for (j, &b) in q.iter().enumerate() {
+            product[i + j] += a * b;
+        }
+    }
+    product
+}
+
+/// Returns `p'`, highest power first, which is empty for a constant.
+fn derivative<V: InterpolationValue>(p: &[V]) -> Vec<V> {
+    let degree = p.len().saturating_sub(1);
+    p[..degree]
+        .iter()
+        .enumerate()
+        .map(|(k, &c)| c * V::from_count(degree - k))
+        .collect()
+}
+
+fn max<V: InterpolationValue>(a: V, b: V) -> V {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+fn min<V: InterpolationValue>(a: V, b: V) -> V {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_exact_maxima() {
+        // x^2 on [0, 2] against the line 1 + x / 2 on [-1, 3]: over the common domain [0, 2], the
+        // difference x^2 - x / 2 - 1 is -1 at 0, -17 / 16 at its minimum 1 / 4 and 2 at 2
+        let square = PiecewisePolynomial::try_new(
+            vec![0.0, 1.0, 2.0],
+            vec![vec![1.0, 0.0, 0.0], vec![1.0, 2.0, 1.0]],
+        )
+        .unwrap();
+        let line = PiecewisePolynomial::try_new(vec![-1.0, 3.0], vec![vec![0.5, 0.5]]).unwrap();
+        let difference = square.max_difference(&line).unwrap();
+        assert_eq!(difference.absolute, (2.0, 2.0));
+        // |x^2 - x / 2 - 1| / (1 + x / 2) is largest at the ends, 1 at 0 and 1 at 2
+        let (x, relative) = difference.relative.unwrap();
+        assert_eq!((x, relative), (0.0, 1.0));
+        // the relative difference to a reference crossing zero is unbounded
+        let crossing = PiecewisePolynomial::try_new(vec![0.0, 2.0], vec![vec![1.0, -1.0]]).unwrap();
+        assert!(square.max_difference(&crossing).unwrap().relative.is_none());
+        assert!(crossing
+            .max_difference(&crossing)
+            .unwrap()
+            .relative
+            .is_some());
+        let apart = PiecewisePolynomial::try_new(vec![5.0, 6.0], vec![vec![1.0]]).unwrap();
+        assert!(matches!(
+            square.max_difference(&apart),
+            Err(InterpolationError::OutOfUpperBound(5.0))
+        ));
+    }
+
+    #[test]
+    fn test_against_dense_probing() {
+        let points = [(0.0, 1.0), (0.3, -1.0), (1.0, 0.5), (1.2, 2.0), (3.0, 0.5)];
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        let natural = NaturalCubicSpline::try_new(&points[1..]).unwrap();
+        let difference = catmull_rom.max_difference(&natural).unwrap();
+        let probe = |x: f64| {
+            let (f, g) = (
+                catmull_rom.try_value(x).unwrap(),
+                natural.try_value(x).unwrap(),
+            );
+            ((f - g).abs(), (f - g).abs() / g.abs())
+        };
+        let (mut absolute, mut relative) = (0.0_f64, 0.0_f64);
+        for i in 0..=27_000 {
+            let (a, r) = probe(0.3 + i as f64 * 1e-4);
+            (absolute, relative) = (absolute.max(a), relative.max(r));
+        }
+        assert!(difference.absolute.1 >= absolute);
+        assert!(difference.absolute.1 - absolute < 1e-8);
+        assert!((probe(difference.absolute.0).0 - difference.absolute.1).abs() < 1e-12);
+        // the natural spline crosses zero between 0.3 and 1.0
+        assert!(difference.relative.is_none());
+        let shifted: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x, y + 3.0)).collect();
+        let (catmull_rom, natural) = (
+            CatmullRomSpline::try_new(&shifted).unwrap(),
+            NaturalCubicSpline::try_new(&shifted).unwrap(),
+        );
+        let (x, relative) = catmull_rom
+            .max_difference(&natural)
+            .unwrap()
+            .relative
+            .unwrap();
+        let dense = (0..=30_000)
+            .map(|i| {
+                let x = i as f64 * 1e-4;
+                let g = natural.try_value(x).unwrap();
+                (catmull_rom.try_value(x).unwrap() - g).abs() / g
+            })
+            .fold(0.0, f64::max);
+        assert!(relative >= dense && relative - dense < 1e-8);
+        let g = natural.try_value(x).unwrap();
+        assert!(((catmull_rom.try_value(x).unwrap() - g).abs() / g - relative).abs() < 1e-12);
+    }
+}
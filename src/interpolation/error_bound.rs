@@ -0,0 +1,50 @@
+//! Interpolation error estimates from divided differences of the data.
+
+use crate::math::{abs, divided_differences};
+use crate::{InterpolationError, InterpolationValue};
+
+/// Estimates `|f^(k)|` on every interval of the sorted points `(xs, ys)` from the `k`-th divided
+/// difference over the `k + 1` knots centred on the interval, using `f[x_j, ..., x_{j+k}] ≈ f^(k) / k!`.
+///
+/// # Errors
+///
+/// Returns `InsufficientPointsError(n)` if there are not `k + 1` points.
+pub(crate) fn derivative_estimates<V: InterpolationValue>(
+    xs: &[V],
+    ys: &[V],
+    k: usize,
+) -> Result<Vec<V>, InterpolationError<V>> {
+    let n = xs.len();
+    if n < k + 1 {
+        return Err(InterpolationError::InsufficientPointsError(n));
+    }
+    let factorial = (1..=k).fold(V::one(), |acc, i| acc * V::from_count(i));
+    Ok((0..n - 1)
+        .map(|i| {
+            let start = i.saturating_sub(k.saturating_sub(1) / 2).min(n - 1 - k);
+            let window = start..start + k + 1;
+            let table = divided_differences(&xs[window.clone()], &ys[window]);
+            abs(table[k]) * factorial
+        })
+        .collect())
+}
+
+/// Raises `v` to the non-negative integer power `k`.
+pub(crate) fn powi<V: InterpolationValue>(v: V, k: usize) -> V {
+    (0..k).fold(V::one(), |acc, _| acc * v)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::error_bound::derivative_estimates;
+
+    #[test]
+    fn test_polynomial_derivatives() {
+        let xs = [0.0, 0.3, 0.5, 1.1, 1.4, 2.0];
+        let ys: Vec<f64> = xs.iter().map(|x| x * x * x * x - x).collect();
+        for estimate in derivative_estimates(&xs, &ys, 4).unwrap() {
+            assert!((estimate - 24.0).abs() < 1e-9);
+        }
+        assert!(derivative_estimates(&xs[..4], &ys[..4], 4).is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! One-dimensional Gaussian-process regression (kriging), an interpolant with uncertainty bands
+//! for sparse and possibly noisy observations.
+
+use crate::interpolation::check_finite;
+use crate::interpolation::interpolator::{sealed, Interpolator, MethodTag};
+use crate::math::abs;
+use crate::math::dense_matrix::{cholesky, solve_lower, solve_lower_transposed};
+use crate::metadata::Metadata;
+use crate::{InterpolationError, TranscendentalValue};
+use std::any::Any;
+
+/// The correlation `ρ(r)` of two values at the distance `r`, with the length scale `ℓ`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GpKernel<V: TranscendentalValue> {
+    /// `ρ(r) = exp(-r² / (2ℓ²))`, the squared exponential, giving infinitely smooth means.
+    Rbf(V),
+    /// `ρ(r) = exp(-r / ℓ)`, the Matérn kernel with `ν = 1/2`, giving continuous means with kinks
+    /// at the observations.
+    Matern12(V),
+    /// `ρ(r) = (1 + √3 r / ℓ) exp(-√3 r / ℓ)`, the Matérn kernel with `ν = 3/2`, giving once
+    /// differentiable means.
+    Matern32(V),
+    /// `ρ(r) = (1 + √5 r / ℓ + 5r² / (3ℓ²)) exp(-√5 r / ℓ)`, the Matérn kernel with `ν = 5/2`,
+    /// giving twice differentiable means.
+    Matern52(V),
+}
+
+impl<V: TranscendentalValue> GpKernel<V> {
+    /// Returns the length scale `ℓ`.
+    pub fn length_scale(&self) -> V {
+        match *self {
+            GpKernel::Rbf(l)
+            | GpKernel::Matern12(l)
+            | GpKernel::Matern32(l)
+            | GpKernel::Matern52(l) => l,
+        }
+    }
+
+    /// Evaluates the correlation at the distance `r >= 0`.
+    fn correlation(&self, r: V) -> V {
+        match *self {
+            GpKernel::Rbf(l) => {
+                let s = r / l;
+                (-s * s / V::from_integer(2)).exp()
+            }
+            GpKernel::Matern12(l) => (-r / l).exp(),
+            GpKernel::Matern32(l) => {
+                let s = V::from_integer(3).sqrt() * r / l;
+                (V::one() + s) * (-s).exp()
+            }
+            GpKernel::Matern52(l) => {
+                let s = V::from_integer(5).sqrt() * r / l;
+                (V::one() + s + s * s / V::from_integer(3)) * (-s).exp()
+            }
+        }
+    }
+}
+
+/// The posterior of a Gaussian process with a constant prior mean, conditioned on observations
+/// `y_i = f(x_i) + ε_i` with independent noise `ε_i` of the variance `σ_n²`.
+///
+/// The prior covariance of `f` is `σ² ρ(|x - x'|)`, and its prior mean is the average of the
+/// observations. The [`Interpolator`] value is the posterior mean, and
+/// [`Self::predict_with_std`] adds the posterior standard deviation of `f`, which vanishes at
+/// noise-free observations and grows back to `σ` away from the data. Construction factors the
+/// dense covariance matrix, so it is `O(n³)`, and every prediction is `O(n²)`.
+///
+/// Unlike the splines, the process is defined on the whole real line, and queries outside the
+/// observations revert to the prior mean.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::gp::{GaussianProcess, GpKernel};
+///
+/// let points = [(0.0, 1.0), (1.0, 2.0), (3.0, 0.5)];
+/// let gp = GaussianProcess::try_new(&points, GpKernel::Matern52(1.0), 1.0, 0.0).unwrap();
+/// let (mean, std) = gp.predict_with_std(1.0).unwrap();
+/// assert!((mean - 2.0_f64).abs() < 1e-9 && std < 1e-6);
+/// let (_, std) = gp.predict_with_std(2.0).unwrap();
+/// assert!(std > 0.1);
+/// ```
+pub struct GaussianProcess<V: TranscendentalValue> {
+    xs: Vec<V>,
+    // the covariance of the observations, K + σ_n² I, factored as L Lᵀ with L row-major
+    cholesky: Vec<V>,
+    // (K + σ_n² I)⁻¹ (y - mean)
+    weights: Vec<V>,
+    mean: V,
+    kernel: GpKernel<V>,
+    signal_variance: V,
+    noise_variance: V,
+    metadata: Metadata,
+}
+
+impl<V: TranscendentalValue> GaussianProcess<V> {
+    /// Conditions a Gaussian process on the points `(x, y)`, which need not be ordered or
+    /// distinct.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The observations `(x_i, y_i)`.
+    /// * `kernel` - The correlation of the process and its length scale.
+    /// * `signal_variance` - The prior variance `σ²` of the process.
+    /// * `noise_variance` - The variance `σ_n²` of the observation noise, zero to interpolate.
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(0)` - If no points are given.
+    /// * `InterpolationError::NonFiniteError(i)` - If the `i`-th point has a NaN or infinite
+    ///   coordinate.
+    /// * `InterpolationError::HyperparameterError(v)` - If the length scale or the signal variance
+    ///   `v` is not positive and finite, or the noise variance `v` is negative or not finite.
+    /// * `InterpolationError::SingularMatrixError` - If the covariance matrix is not numerically
+    ///   positive definite, e.g. for a repeated `x` without noise.
+    pub fn try_new(
+        points: &[(V, V)],
+        kernel: GpKernel<V>,
+        signal_variance: V,
+        noise_variance: V,
+    ) -> Result<Self, InterpolationError<V>> {
+        let n = points.len();
+        if n == 0 {
+            return Err(InterpolationError::InsufficientPointsError(n));
+        }
+        check_finite(points.iter().map(|&(x, y)| [x, y]))?;
+        for parameter in [kernel.length_scale(), signal_variance] {
+            if !(parameter > V::zero() && parameter.is_finite()) {
+                return Err(InterpolationError::HyperparameterError(parameter));
+            }
+        }
+        if !(noise_variance >= V::zero() && noise_variance.is_finite()) {
+            return Err(InterpolationError::HyperparameterError(noise_variance));
+        }
+        let mut covariance = vec![V::zero(); n * n];
+        for (i, &(xi, _)) in points.iter().enumerate() {
+            for (j, &(xj, _)) in points.iter().enumerate().take(i + 1) {
+                covariance[i * n + j] = signal_variance * kernel.correlation(abs(xi - xj));
+            }
+            covariance[i * n + i] += noise_variance;
+        }
+        let cholesky = cholesky(&covariance, n).ok_or(InterpolationError::SingularMatrixError)?;
+        let mean = points.iter().fold(V::zero(), |acc, &(_, y)| acc + y) / V::from_count(n);
+        let mut weights: Vec<_> = points.iter().map(|&(_, y)| y - mean).collect();
+        solve_lower(&cholesky, &mut weights);
+        solve_lower_transposed(&cholesky, &mut weights);
+        Ok(Self {
+            xs: points.iter().map(|&(x, _)| x).collect(),
+            cholesky,
+            weights,
+            mean,
+            kernel,
+            signal_variance,
+            noise_variance,
+            metadata: Metadata::default(),
+        })
+    }
+
+    /// Returns the correlation kernel.
+    pub fn kernel(&self) -> GpKernel<V> {
+        self.kernel
+    }
+
+    /// Returns the prior variance `σ²` of the process.
+    pub fn signal_variance(&self) -> V {
+        self.signal_variance
+    }
+
+    /// Returns the variance `σ_n²` of the observation noise.
+    pub fn noise_variance(&self) -> V {
+        self.noise_variance
+    }
+
+    /// Attaches provenance metadata to the process.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the provenance metadata of the process.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Evaluates the posterior mean at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        check_query(x)?;
+        Ok(self
+            .covariances(x)
+            .zip(&self.weights)
+            .fold(self.mean, |acc, (k, &w)| acc + k * w))
+    }
+
+    /// Evaluates the posterior mean and standard deviation of the process at `x`, e.g. the band
+    /// `mean ± 2 std` of about 95% confidence.
+    ///
+    /// The standard deviation is that of the noise-free process `f`; the spread of a new
+    /// observation adds the noise variance to its square.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn predict_with_std(&self, x: V) -> Result<(V, V), InterpolationError<V>> {
+        check_query(x)?;
+        let mut v: Vec<_> = self.covariances(x).collect();
+        let mean = v
+            .iter()
+            .zip(&self.weights)
+            .fold(self.mean, |acc, (&k, &w)| acc + k * w);
+        solve_lower(&self.cholesky, &mut v);
+        let variance = v
+            .iter()
+            .fold(self.signal_variance, |acc, &vi| acc - vi * vi);
+        // rounding may leave a tiny negative variance at the observations
+        let std = if variance > V::zero() {
+            variance.sqrt()
+        } else {
+            V::zero()
+        };
+        Ok((mean, std))
+    }
+
+    /// Yields the prior covariances of `f(x)` with the observations.
+    fn covariances(&self, x: V) -> impl Iterator<Item = V> + '_ {
+        self.xs
+            .iter()
+            .map(move |&xi| self.signal_variance * self.kernel.correlation(abs(x - xi)))
+    }
+}
+
+fn check_query<V: TranscendentalValue>(x: V) -> Result<(), InterpolationError<V>> {
+    if x.is_finite() {
+        Ok(())
+    } else {
+        Err(InterpolationError::NonFiniteInput(x))
+    }
+}
+
+impl<V: TranscendentalValue> sealed::Sealed for GaussianProcess<V> {}
+
+impl<V: TranscendentalValue> Interpolator<V> for GaussianProcess<V> {
+    /// Evaluates the posterior mean.
+    fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        GaussianProcess::try_value(self, x)
+    }
+
+    fn method(&self) -> MethodTag {
+        MethodTag::GaussianProcess
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(GaussianProcess::metadata(self))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::gp::{GaussianProcess, GpKernel};
+    use crate::interpolation::interpolator::{Interpolator, MethodTag};
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_interpolation_with_uncertainty() {
+        let points: Vec<(f64, f64)> = [0.0_f64, 0.7, 1.5, 2.0, 3.2]
+            .iter()
+            .map(|&x| (x, x.sin()))
+            .collect();
+        for kernel in [
+            GpKernel::Rbf(1.0),
+            GpKernel::Matern12(1.0),
+            GpKernel::Matern32(1.0),
+            GpKernel::Matern52(1.0),
+        ] {
+            let gp = GaussianProcess::try_new(&points, kernel, 1.0, 0.0).unwrap();
+            for &(x, y) in &points {
+                let (mean, std) = gp.predict_with_std(x).unwrap();
+                assert!((mean - y).abs() < 1e-9, "{kernel:?}");
+                assert!(std < 1e-6, "{kernel:?}");
+            }
+            // the band widens between the observations and tends to the prior far away
+            let (_, gap) = gp.predict_with_std(2.6).unwrap();
+            let (_, near) = gp.predict_with_std(2.1).unwrap();
+            let (mean, far) = gp.predict_with_std(100.0).unwrap();
+            assert!(near < gap && gap < far, "{kernel:?}");
+            assert!((far - 1.0).abs() < 1e-9);
+            let average = points.iter().map(|&(_, y)| y).sum::<f64>() / 5.0;
+            assert!((mean - average).abs() < 1e-9);
+        }
+        let smooth = GaussianProcess::try_new(&points, GpKernel::Rbf(1.0), 1.0, 0.0).unwrap();
+        assert!((smooth.try_value(1.0).unwrap() - 1.0_f64.sin()).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_noisy_observations() {
+        // a repeated x is singular without noise and averaged with it
+        let points = [(0.0_f64, 1.0), (1.0, 0.0), (1.0, 2.0), (2.0, 1.0)];
+        assert!(matches!(
+            GaussianProcess::try_new(&points, GpKernel::Matern32(1.0), 1.0, 0.0),
+            Err(InterpolationError::SingularMatrixError)
+        ));
+        let gp = GaussianProcess::try_new(&points, GpKernel::Matern32(1.0), 1.0, 0.1).unwrap();
+        let (mean, std) = gp.predict_with_std(1.0).unwrap();
+        assert!((mean - 1.0).abs() < 1e-12);
+        assert!(std > 0.1 && std < 1.0);
+
+        let curve: Box<dyn Interpolator<f64>> = Box::new(gp);
+        assert_eq!(curve.method(), MethodTag::GaussianProcess);
+        assert!((curve.try_value(1.0).unwrap() - 1.0).abs() < 1e-12);
+        assert!(matches!(
+            curve.try_value(f64::NAN),
+            Err(InterpolationError::NonFiniteInput(_))
+        ));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_distant_points() {
+        use rust_decimal::Decimal;
+
+        // the kernel between the points, e^-200, underflows `Decimal` and is taken as zero
+        let points = [
+            (Decimal::ZERO, Decimal::ONE),
+            (Decimal::from(20), Decimal::TWO),
+        ];
+        for kernel in [
+            GpKernel::Rbf(Decimal::ONE),
+            GpKernel::Matern52(Decimal::ONE),
+        ] {
+            let gp =
+                GaussianProcess::try_new(&points, kernel, Decimal::ONE, Decimal::ZERO).unwrap();
+            for (x, y) in points {
+                let (mean, std) = gp.predict_with_std(x).unwrap();
+                assert!((mean - y).abs() < Decimal::new(1, 20), "{kernel:?}");
+                assert!(std < Decimal::new(1, 10), "{kernel:?}");
+            }
+            let (mean, _) = gp.predict_with_std(Decimal::from(10)).unwrap();
+            assert!(
+                (mean - Decimal::new(15, 1)).abs() < Decimal::new(1, 10),
+                "{kernel:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let points = [(0.0, 1.0), (1.0, 2.0)];
+        assert!(matches!(
+            GaussianProcess::try_new(&points, GpKernel::Rbf(0.0), 1.0, 0.0),
+            Err(InterpolationError::HyperparameterError(0.0))
+        ));
+        assert!(matches!(
+            GaussianProcess::try_new(&points, GpKernel::Rbf(1.0), 1.0, -1.0),
+            Err(InterpolationError::HyperparameterError(-1.0))
+        ));
+        assert!(matches!(
+            GaussianProcess::try_new(&[(0.0, f64::NAN)], GpKernel::Rbf(1.0), 1.0, 0.0),
+            Err(InterpolationError::NonFiniteError(0))
+        ));
+        assert!(matches!(
+            GaussianProcess::<f64>::try_new(&[], GpKernel::Rbf(1.0), 1.0, 0.0),
+            Err(InterpolationError::InsufficientPointsError(0))
+        ));
+    }
+}
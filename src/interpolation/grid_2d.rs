@@ -0,0 +1,592 @@
+use crate::interpolation::natural_cubic_spline::{second_derivative_rhs, second_derivative_system};
+use crate::interpolation::{check_knots, hermite_basis, segment_index};
+use crate::{InterpolationError, InterpolationValue};
+
+/// Values sampled on a rectilinear grid `xs × ys`, stored row-major
+/// (`values[i * ys.len() + j]` is the value at `(xs[i], ys[j])`).
+struct Grid<V> {
+    xs: Vec<V>,
+    ys: Vec<V>,
+    values: Vec<V>,
+}
+
+impl<V: InterpolationValue> Grid<V> {
+    fn try_new(xs: &[V], ys: &[V], values: &[V]) -> Result<Self, InterpolationError<V>> {
+        for axis in [xs, ys] {
+            if axis.len() < 2 {
+                return Err(InterpolationError::InsufficientPointsError(axis.len()));
+            }
+            check_knots(axis.iter().copied())?;
+        }
+        if values.len() != xs.len() * ys.len() {
+            return Err(InterpolationError::GridShapeError(
+                xs.len() * ys.len(),
+                values.len(),
+            ));
+        }
+        Ok(Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            values: values.to_vec(),
+        })
+    }
+
+    fn at(&self, i: usize, j: usize) -> V {
+        self.values[i * self.ys.len() + j]
+    }
+
+    /// Finds the cell containing `(x, y)` and the normalized position inside it.
+    fn locate(&self, x: V, y: V) -> Result<(usize, usize, V, V), InterpolationError<V>> {
+        let i = segment_index(&self.xs, x)?;
+        let j = segment_index(&self.ys, y)?;
+        let t = (x - self.xs[i]) / (self.xs[i + 1] - self.xs[i]);
+        let u = (y - self.ys[j]) / (self.ys[j + 1] - self.ys[j]);
+        Ok((i, j, t, u))
+    }
+}
+
+/// Bilinear interpolation on a rectilinear grid.
+pub struct BilinearInterpolator<V: InterpolationValue> {
+    grid: Grid<V>,
+}
+
+impl<V: InterpolationValue> BilinearInterpolator<V> {
+    /// Constructs a new `BilinearInterpolator` from grid axes and values.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The grid coordinates along the first axis, in ascending order.
+    /// * `ys` - The grid coordinates along the second axis, in ascending order.
+    /// * `values` - The values at the grid nodes in row-major order, i.e. `values[i * ys.len() + j]`
+    ///   is the value at `(xs[i], ys[j])`.
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(n)` - If an axis has fewer than 2 coordinates.
+    /// * `InterpolationError::PointOrderError(i)`, `DuplicatePointError(x)` or `NonFiniteError(i)` -
+    ///   If an axis is not finite and strictly ascending.
+    /// * `InterpolationError::GridShapeError(expected, actual)` - If `values` does not have
+    ///   `xs.len() * ys.len()` entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::grid_2d::BilinearInterpolator;
+    ///
+    /// let xs = [0.0, 1.0];
+    /// let ys = [0.0, 2.0];
+    /// let values = [0.0, 2.0, 1.0, 3.0];
+    /// let interpolator = BilinearInterpolator::try_new(&xs, &ys, &values).unwrap();
+    /// assert_eq!(interpolator.try_value(0.5, 1.0).unwrap(), 1.5);
+    /// ```
+    pub fn try_new(xs: &[V], ys: &[V], values: &[V]) -> Result<Self, InterpolationError<V>> {
+        Ok(Self {
+            grid: Grid::try_new(xs, ys, values)?,
+        })
+    }
+
+    /// Evaluates the interpolated surface at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(v)` or `OutOfUpperBound(v)` with the offending coordinate `v`
+    /// if `(x, y)` lies outside the grid.
+    pub fn try_value(&self, x: V, y: V) -> Result<V, InterpolationError<V>> {
+        let (i, j, t, u) = self.grid.locate(x, y)?;
+        let g = &self.grid;
+        let lower = g.at(i, j) + (g.at(i + 1, j) - g.at(i, j)) * t;
+        let upper = g.at(i, j + 1) + (g.at(i + 1, j + 1) - g.at(i, j + 1)) * t;
+        Ok(lower + (upper - lower) * u)
+    }
+
+    /// Evaluates the gradient `[∂z/∂x, ∂z/∂y]` of the surface at `(x, y)`.
+    ///
+    /// The surface is only continuous across the grid lines, where the derivatives are those of
+    /// the cell after the line, or of the last cell on the last line.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_gradient(&self, x: V, y: V) -> Result<[V; 2], InterpolationError<V>> {
+        Ok([
+            self.evaluate(x, y, [true, false])?,
+            self.evaluate(x, y, [false, true])?,
+        ])
+    }
+
+    /// Evaluates the mixed partial derivative `∂²z/∂x∂y` of the surface at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_d2zdxdy(&self, x: V, y: V) -> Result<V, InterpolationError<V>> {
+        self.evaluate(x, y, [true, true])
+    }
+
+    /// Evaluates the partial derivative of the surface of order 0 or 1 along each axis.
+    fn evaluate(&self, x: V, y: V, derivatives: [bool; 2]) -> Result<V, InterpolationError<V>> {
+        let (i, j, t, u) = self.grid.locate(x, y)?;
+        let hx = self.grid.xs[i + 1] - self.grid.xs[i];
+        let hy = self.grid.ys[j + 1] - self.grid.ys[j];
+        let (weights_t, weights_u) = (
+            linear_basis(t, hx, derivatives[0]),
+            linear_basis(u, hy, derivatives[1]),
+        );
+        let mut z = V::zero();
+        for (a, &wt) in weights_t.iter().enumerate() {
+            for (b, &wu) in weights_u.iter().enumerate() {
+                z += self.grid.at(i + a, j + b) * wt * wu;
+            }
+        }
+        Ok(z)
+    }
+}
+
+/// Bicubic Hermite interpolation on a rectilinear grid.
+///
+/// The partial derivatives at the grid nodes are estimated by finite differences (central in the
+/// interior, one-sided on the edges), so the surface is C1 and reproduces bilinear data exactly.
+pub struct BicubicInterpolator<V: InterpolationValue> {
+    grid: Grid<V>,
+    dzdx: Vec<V>,
+    dzdy: Vec<V>,
+    d2zdxdy: Vec<V>,
+}
+
+impl<V: InterpolationValue> BicubicInterpolator<V> {
+    /// Constructs a new `BicubicInterpolator` from grid axes and values.
+    ///
+    /// The arguments and errors are the same as for [`BilinearInterpolator::try_new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::grid_2d::BicubicInterpolator;
+    ///
+    /// let xs = [0.0, 1.0, 2.0];
+    /// let ys = [0.0, 1.0];
+    /// let values = [0.0, 1.0, 1.0, 2.0, 2.0, 3.0];
+    /// let interpolator = BicubicInterpolator::try_new(&xs, &ys, &values).unwrap();
+    /// assert_eq!(interpolator.try_value(1.5, 0.5).unwrap(), 2.0);
+    /// ```
+    pub fn try_new(xs: &[V], ys: &[V], values: &[V]) -> Result<Self, InterpolationError<V>> {
+        let grid = Grid::try_new(xs, ys, values)?;
+        let (nx, ny) = (grid.xs.len(), grid.ys.len());
+        let mut dzdx = Vec::with_capacity(nx * ny);
+        let mut dzdy = Vec::with_capacity(nx * ny);
+        let mut d2zdxdy = Vec::with_capacity(nx * ny);
+        for i in 0..nx {
+            let (i0, i1) = (i.saturating_sub(1), (i + 1).min(nx - 1));
+            let hx = grid.xs[i1] - grid.xs[i0];
+            for j in 0..ny {
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(ny - 1));
+                let hy = grid.ys[j1] - grid.ys[j0];
+                dzdx.push((grid.at(i1, j) - grid.at(i0, j)) / hx);
+                dzdy.push((grid.at(i, j1) - grid.at(i, j0)) / hy);
+                d2zdxdy.push(
+                    (grid.at(i1, j1) - grid.at(i1, j0) - grid.at(i0, j1) + grid.at(i0, j0))
+                        / (hx * hy),
+                );
+            }
+        }
+        Ok(Self {
+            grid,
+            dzdx,
+            dzdy,
+            d2zdxdy,
+        })
+    }
+
+    /// Evaluates the interpolated surface at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(v)` or `OutOfUpperBound(v)` with the offending coordinate `v`
+    /// if `(x, y)` lies outside the grid.
+    pub fn try_value(&self, x: V, y: V) -> Result<V, InterpolationError<V>> {
+        self.evaluate(x, y, [false, false])
+    }
+
+    /// Evaluates the gradient `[∂z/∂x, ∂z/∂y]` of the surface at `(x, y)`.
+    ///
+    /// The surface is C1, so the gradient is continuous, while the mixed partial derivative jumps
+    /// across the grid lines, where it is that of the cell after the line, or of the last cell on
+    /// the last line.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_gradient(&self, x: V, y: V) -> Result<[V; 2], InterpolationError<V>> {
+        Ok([
+            self.evaluate(x, y, [true, false])?,
+            self.evaluate(x, y, [false, true])?,
+        ])
+    }
+
+    /// Evaluates the mixed partial derivative `∂²z/∂x∂y` of the surface at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_d2zdxdy(&self, x: V, y: V) -> Result<V, InterpolationError<V>> {
+        self.evaluate(x, y, [true, true])
+    }
+
+    /// Evaluates the partial derivative of the surface of order 0 or 1 along each axis.
+    fn evaluate(&self, x: V, y: V, derivatives: [bool; 2]) -> Result<V, InterpolationError<V>> {
+        let (i, j, t, u) = self.grid.locate(x, y)?;
+        let hx = self.grid.xs[i + 1] - self.grid.xs[i];
+        let hy = self.grid.ys[j + 1] - self.grid.ys[j];
+        let (value_t, slope_t) = hermite_weights(t, hx, derivatives[0]);
+        let (value_u, slope_u) = hermite_weights(u, hy, derivatives[1]);
+        let ny = self.grid.ys.len();
+        let mut z = V::zero();
+        for (a, (&vt, &st)) in value_t.iter().zip(&slope_t).enumerate() {
+            for (b, (&vu, &su)) in value_u.iter().zip(&slope_u).enumerate() {
+                let k = (i + a) * ny + j + b;
+                z += self.grid.values[k] * vt * vu
+                    + self.dzdx[k] * st * vu
+                    + self.dzdy[k] * vt * su
+                    + self.d2zdxdy[k] * st * su;
+            }
+        }
+        Ok(z)
+    }
+}
+
+/// Tensor-product natural cubic spline on a rectilinear grid.
+///
+/// This is the 2D analogue of
+/// [`NaturalCubicSpline`](crate::interpolation::natural_cubic_spline::NaturalCubicSpline):
+/// the surface is C2, restricts to a natural cubic spline along every grid line, and is obtained
+/// by solving the tridiagonal spline systems along the rows and then along the columns.
+pub struct BicubicSpline2D<V: InterpolationValue> {
+    grid: Grid<V>,
+    d2zdx2: Vec<V>,
+    d2zdy2: Vec<V>,
+    d4zdx2dy2: Vec<V>,
+}
+
+impl<V: InterpolationValue> BicubicSpline2D<V> {
+    /// Constructs a new `BicubicSpline2D` from grid axes and values.
+    ///
+    /// The arguments are the same as for [`BilinearInterpolator::try_new`].
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(n)` - If an axis has fewer than 3 coordinates.
+    /// * `InterpolationError::PointOrderError(i)`, `DuplicatePointError(x)` or `NonFiniteError(i)` -
+    ///   If an axis is not finite and strictly ascending.
+    /// * `InterpolationError::GridShapeError(expected, actual)` - If `values` does not have
+    ///   `xs.len() * ys.len()` entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::grid_2d::BicubicSpline2D;
+    ///
+    /// let xs = [0.0, 1.0, 2.0];
+    /// let ys = [0.0, 1.0, 2.0];
+    /// let values = [0.0, 1.0, 2.0, 1.0, 2.0, 3.0, 2.0, 3.0, 4.0];
+    /// let spline = BicubicSpline2D::try_new(&xs, &ys, &values).unwrap();
+    /// assert_eq!(spline.try_value(1.5, 0.5).unwrap(), 2.0);
+    /// ```
+    pub fn try_new(xs: &[V], ys: &[V], values: &[V]) -> Result<Self, InterpolationError<V>> {
+        for axis in [xs, ys] {
+            if axis.len() < 3 {
+                return Err(InterpolationError::InsufficientPointsError(axis.len()));
+            }
+        }
+        let grid = Grid::try_new(xs, ys, values)?;
+        let (nx, ny) = (xs.len(), ys.len());
+        let (x_system, y_system) = (second_derivative_system(xs)?, second_derivative_system(ys)?);
+        // along x for every column
+        let mut d2zdx2 = vec![V::zero(); nx * ny];
+        for j in 0..ny {
+            let column: Vec<_> = (0..nx).map(|i| grid.at(i, j)).collect();
+            for (i, m) in x_system
+                .solve_unchecked(&second_derivative_rhs(xs, &column))
+                .into_iter()
+                .enumerate()
+            {
+                d2zdx2[i * ny + j] = m;
+            }
+        }
+        // along y for every row, of both the values and their x-curvatures
+        let mut d2zdy2 = Vec::with_capacity(nx * ny);
+        let mut d4zdx2dy2 = Vec::with_capacity(nx * ny);
+        for i in 0..nx {
+            let row = i * ny..(i + 1) * ny;
+            d2zdy2.extend(
+                y_system.solve_unchecked(&second_derivative_rhs(ys, &grid.values[row.clone()])),
+            );
+            d4zdx2dy2.extend(y_system.solve_unchecked(&second_derivative_rhs(ys, &d2zdx2[row])));
+        }
+        Ok(Self {
+            grid,
+            d2zdx2,
+            d2zdy2,
+            d4zdx2dy2,
+        })
+    }
+
+    /// Evaluates the spline surface at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(v)` or `OutOfUpperBound(v)` with the offending coordinate `v`
+    /// if `(x, y)` lies outside the grid.
+    pub fn try_value(&self, x: V, y: V) -> Result<V, InterpolationError<V>> {
+        self.evaluate(x, y, [false, false])
+    }
+
+    /// Evaluates the gradient `[∂z/∂x, ∂z/∂y]` of the surface at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_gradient(&self, x: V, y: V) -> Result<[V; 2], InterpolationError<V>> {
+        Ok([
+            self.evaluate(x, y, [true, false])?,
+            self.evaluate(x, y, [false, true])?,
+        ])
+    }
+
+    /// Evaluates the mixed partial derivative `∂²z/∂x∂y` of the surface at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_d2zdxdy(&self, x: V, y: V) -> Result<V, InterpolationError<V>> {
+        self.evaluate(x, y, [true, true])
+    }
+
+    /// Evaluates the partial derivative of the surface of order 0 or 1 along each axis.
+    fn evaluate(&self, x: V, y: V, derivatives: [bool; 2]) -> Result<V, InterpolationError<V>> {
+        let (i, j, t, u) = self.grid.locate(x, y)?;
+        let hx = self.grid.xs[i + 1] - self.grid.xs[i];
+        let hy = self.grid.ys[j + 1] - self.grid.ys[j];
+        let (value_t, curvature_t) = cubic_spline_basis(t, hx, derivatives[0]);
+        let (value_u, curvature_u) = cubic_spline_basis(u, hy, derivatives[1]);
+        let ny = self.grid.ys.len();
+        let mut z = V::zero();
+        for (a, (&vt, &ct)) in value_t.iter().zip(&curvature_t).enumerate() {
+            for (b, (&vu, &cu)) in value_u.iter().zip(&curvature_u).enumerate() {
+                let k = (i + a) * ny + j + b;
+                z += self.grid.values[k] * vt * vu
+                    + self.d2zdx2[k] * ct * vu
+                    + self.d2zdy2[k] * vt * cu
+                    + self.d4zdx2dy2[k] * ct * cu;
+            }
+        }
+        Ok(z)
+    }
+}
+
+/// Returns the weights of the values at both ends of an interval of width `h` for a linear
+/// interpolant at the normalized position `t`, or for its derivative if `derivative` is set.
+fn linear_basis<V: InterpolationValue>(t: V, h: V, derivative: bool) -> [V; 2] {
+    if derivative {
+        [-V::one() / h, V::one() / h]
+    } else {
+        [V::one() - t, t]
+    }
+}
+
+/// Returns the weights of the values and of the slopes at both ends of an interval of width `h`
+/// for a cubic Hermite interpolant at the normalized position `t`, or for its derivative if
+/// `derivative` is set.
+fn hermite_weights<V: InterpolationValue>(t: V, h: V, derivative: bool) -> ([V; 2], [V; 2]) {
+    if derivative {
+        let (two, three, six) = (V::from_integer(2), V::from_integer(3), V::from_integer(6));
+        let t2 = t * t;
+        let value = (six * t2 - six * t) / h;
+        (
+            [value, -value],
+            [three * t2 - two * two * t + V::one(), three * t2 - two * t],
+        )
+    } else {
+        let (values, slopes) = hermite_basis(t);
+        (values, [slopes[0] * h, slopes[1] * h])
+    }
+}
+
+/// Returns the weights of the values and of the second derivatives at both ends of an interval
+/// of width `h` for a cubic spline at the normalized position `t`, or for its derivative if
+/// `derivative` is set.
+fn cubic_spline_basis<V: InterpolationValue>(t: V, h: V, derivative: bool) -> ([V; 2], [V; 2]) {
+    let a = V::one() - t;
+    let six = V::from_integer(6);
+    if derivative {
+        let three = V::from_integer(3);
+        let scale = h / six;
+        (
+            [-V::one() / h, V::one() / h],
+            [
+                (V::one() - three * a * a) * scale,
+                (three * t * t - V::one()) * scale,
+            ],
+        )
+    } else {
+        let scale = h * h / six;
+        ([a, t], [(a * a * a - a) * scale, (t * t * t - t) * scale])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::grid_2d::{
+        BicubicInterpolator, BicubicSpline2D, BilinearInterpolator,
+    };
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::InterpolationError;
+
+    fn bilinear(x: f64, y: f64) -> f64 {
+        1.0 + 2.0 * x - 3.0 * y + 0.5 * x * y
+    }
+
+    fn grid() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let xs = vec![0.0, 0.5, 2.0, 2.5];
+        let ys = vec![-1.0, 0.0, 3.0];
+        let values = xs
+            .iter()
+            .flat_map(|&x| ys.iter().map(move |&y| bilinear(x, y)))
+            .collect();
+        (xs, ys, values)
+    }
+
+    #[test]
+    fn test_reproduces_bilinear_data() {
+        let (xs, ys, values) = grid();
+        let bilinear_interpolator = BilinearInterpolator::try_new(&xs, &ys, &values).unwrap();
+        let bicubic_interpolator = BicubicInterpolator::try_new(&xs, &ys, &values).unwrap();
+        let spline = BicubicSpline2D::try_new(&xs, &ys, &values).unwrap();
+        for (x, y) in [(0.0, -1.0), (0.3, 0.7), (1.2, 2.9), (2.5, 3.0), (2.1, -0.5)] {
+            let expected = bilinear(x, y);
+            assert!((bilinear_interpolator.try_value(x, y).unwrap() - expected).abs() < 1e-12);
+            assert!((bicubic_interpolator.try_value(x, y).unwrap() - expected).abs() < 1e-12);
+            assert!((spline.try_value(x, y).unwrap() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_spline_is_tensor_product() {
+        let xs = [0.0, 0.4, 1.0, 1.8];
+        let ys = [-1.0, 0.0, 0.5, 2.0, 2.2];
+        let f = |x: f64| (3.0 * x).sin();
+        let g = |y: f64| y * y * y - y;
+        let values: Vec<_> = xs
+            .iter()
+            .flat_map(|&x| ys.iter().map(move |&y| f(x) * g(y)))
+            .collect();
+        let spline = BicubicSpline2D::try_new(&xs, &ys, &values).unwrap();
+        let spline_x = NaturalCubicSpline::try_new(&xs.map(|x| (x, f(x)))).unwrap();
+        let spline_y = NaturalCubicSpline::try_new(&ys.map(|y| (y, g(y)))).unwrap();
+        for (x, y) in [(0.1, -0.5), (0.4, 0.2), (1.5, 2.1), (0.9, 1.0)] {
+            let expected = spline_x.try_value(x).unwrap() * spline_y.try_value(y).unwrap();
+            assert!((spline.try_value(x, y).unwrap() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let (xs, ys, values) = grid();
+        let bilinear_interpolator = BilinearInterpolator::try_new(&xs, &ys, &values).unwrap();
+        let bicubic_interpolator = BicubicInterpolator::try_new(&xs, &ys, &values).unwrap();
+        let spline = BicubicSpline2D::try_new(&xs, &ys, &values).unwrap();
+        for (x, y) in [(0.0, -1.0), (0.3, 0.7), (1.2, 2.9), (2.5, 3.0), (2.1, -0.5)] {
+            let expected = [2.0 + 0.5 * y, -3.0 + 0.5 * x];
+            for (gradient, d2zdxdy) in [
+                (
+                    bilinear_interpolator.try_gradient(x, y).unwrap(),
+                    bilinear_interpolator.try_d2zdxdy(x, y).unwrap(),
+                ),
+                (
+                    bicubic_interpolator.try_gradient(x, y).unwrap(),
+                    bicubic_interpolator.try_d2zdxdy(x, y).unwrap(),
+                ),
+                (
+                    spline.try_gradient(x, y).unwrap(),
+                    spline.try_d2zdxdy(x, y).unwrap(),
+                ),
+            ] {
+                assert!((gradient[0] - expected[0]).abs() < 1e-12);
+                assert!((gradient[1] - expected[1]).abs() < 1e-12);
+                assert!((d2zdxdy - 0.5).abs() < 1e-12);
+            }
+        }
+        assert!(matches!(
+            spline.try_gradient(-1.0, 0.0),
+            Err(InterpolationError::OutOfLowerBound(x)) if x == -1.0
+        ));
+    }
+
+    #[test]
+    fn test_spline_derivatives_are_tensor_products() {
+        let xs = [0.0, 0.4, 1.0, 1.8];
+        let ys = [-1.0, 0.0, 0.5, 2.0, 2.2];
+        let f = |x: f64| (3.0 * x).sin();
+        let g = |y: f64| y * y * y - y;
+        let values: Vec<_> = xs
+            .iter()
+            .flat_map(|&x| ys.iter().map(move |&y| f(x) * g(y)))
+            .collect();
+        let spline = BicubicSpline2D::try_new(&xs, &ys, &values).unwrap();
+        let spline_x = NaturalCubicSpline::try_new(&xs.map(|x| (x, f(x)))).unwrap();
+        let spline_y = NaturalCubicSpline::try_new(&ys.map(|y| (y, g(y)))).unwrap();
+        for (x, y) in [(0.1, -0.5), (0.4, 0.2), (1.5, 2.1), (0.9, 1.0)] {
+            let (sx, sy) = (
+                spline_x.try_eval_all(x).unwrap(),
+                spline_y.try_eval_all(y).unwrap(),
+            );
+            let gradient = spline.try_gradient(x, y).unwrap();
+            assert!((gradient[0] - sx.dydx * sy.value).abs() < 1e-12);
+            assert!((gradient[1] - sx.value * sy.dydx).abs() < 1e-12);
+            let d2zdxdy = spline.try_d2zdxdy(x, y).unwrap();
+            assert!((d2zdxdy - sx.dydx * sy.dydx).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bicubic_derivatives_match_finite_differences() {
+        let xs = [0.0_f64, 0.3, 0.9, 1.4, 2.0];
+        let ys = [-0.5, 0.2, 0.6, 1.5];
+        let values: Vec<_> = xs
+            .iter()
+            .flat_map(|&x| ys.iter().map(move |&y| (x * y).sin() + x * x))
+            .collect();
+        let interpolator = BicubicInterpolator::try_new(&xs, &ys, &values).unwrap();
+        let z = |x: f64, y: f64| interpolator.try_value(x, y).unwrap();
+        let h = 1e-5;
+        for (x, y) in [(0.1, 0.0), (0.5, 0.4), (1.7, 1.2)] {
+            let gradient = interpolator.try_gradient(x, y).unwrap();
+            assert!((gradient[0] - (z(x + h, y) - z(x - h, y)) / (2.0 * h)).abs() < 1e-8);
+            assert!((gradient[1] - (z(x, y + h) - z(x, y - h)) / (2.0 * h)).abs() < 1e-8);
+            let d2zdxdy = (z(x + h, y + h) - z(x + h, y - h) - z(x - h, y + h) + z(x - h, y - h))
+                / (4.0 * h * h);
+            assert!((interpolator.try_d2zdxdy(x, y).unwrap() - d2zdxdy).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let (xs, ys, values) = grid();
+        assert!(matches!(
+            BilinearInterpolator::try_new(&xs, &ys, &values[1..]),
+            Err(InterpolationError::GridShapeError(12, 11))
+        ));
+        assert!(matches!(
+            BicubicInterpolator::try_new(&xs, &ys[..1], &values[..4]),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+        let interpolator = BicubicInterpolator::try_new(&xs, &ys, &values).unwrap();
+        assert!(matches!(
+            interpolator.try_value(1.0, -2.0),
+            Err(InterpolationError::OutOfLowerBound(y)) if y == -2.0
+        ));
+        assert!(matches!(
+            interpolator.try_value(3.0, 0.0),
+            Err(InterpolationError::OutOfUpperBound(x)) if x == 3.0
+        ));
+    }
+}
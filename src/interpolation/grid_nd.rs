@@ -0,0 +1,150 @@
+use crate::interpolation::{check_knots, segment_index};
+use crate::{InterpolationError, InterpolationValue};
+
+/// Multilinear interpolation on an `N`-dimensional rectilinear grid.
+///
+/// `N = 1, 2, 3` give linear, bilinear and trilinear interpolation. Evaluation visits the `2^N`
+/// corners of the cell containing the query and does not allocate.
+pub struct InterpN<V: InterpolationValue, const N: usize> {
+    axes: [Vec<V>; N],
+    strides: [usize; N],
+    values: Vec<V>,
+}
+
+impl<V: InterpolationValue, const N: usize> InterpN<V, N> {
+    /// Constructs a new `InterpN` from grid axes and values.
+    ///
+    /// # Arguments
+    ///
+    /// * `axes` - The grid coordinates along each axis, each in ascending order.
+    /// * `values` - The values at the grid nodes in row-major order (the last axis varies fastest).
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(n)` - If an axis has fewer than 2 coordinates,
+    ///   or `N` is 0.
+    /// * `InterpolationError::PointOrderError(i)`, `DuplicatePointError(x)` or `NonFiniteError(i)` -
+    ///   If an axis is not finite and strictly ascending, at its `i`-th coordinate.
+    /// * `InterpolationError::GridShapeError(expected, actual)` - If `values` does not have one entry
+    ///   per grid node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::grid_nd::InterpN;
+    ///
+    /// // f(x, y, z) = x + 2y + 4z on the unit cube
+    /// let axis = [0.0, 1.0];
+    /// let values = [0.0, 4.0, 2.0, 6.0, 1.0, 5.0, 3.0, 7.0];
+    /// let table = InterpN::try_new([&axis, &axis, &axis], &values).unwrap();
+    /// assert_eq!(table.try_value([0.5, 0.25, 0.75]).unwrap(), 4.0);
+    /// ```
+    pub fn try_new(axes: [&[V]; N], values: &[V]) -> Result<Self, InterpolationError<V>> {
+        if N == 0 {
+            return Err(InterpolationError::InsufficientPointsError(0));
+        }
+        for axis in axes {
+            if axis.len() < 2 {
+                return Err(InterpolationError::InsufficientPointsError(axis.len()));
+            }
+            check_knots(axis.iter().copied())?;
+        }
+        let mut strides = [1; N];
+        for k in (0..N - 1).rev() {
+            strides[k] = strides[k + 1] * axes[k + 1].len();
+        }
+        let node_count = strides[0] * axes[0].len();
+        if values.len() != node_count {
+            return Err(InterpolationError::GridShapeError(node_count, values.len()));
+        }
+        Ok(Self {
+            axes: axes.map(<[V]>::to_vec),
+            strides,
+            values: values.to_vec(),
+        })
+    }
+
+    /// Evaluates the interpolated value at `point`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(v)` or `OutOfUpperBound(v)` with the offending coordinate `v`
+    /// if `point` lies outside the grid.
+    pub fn try_value(&self, point: [V; N]) -> Result<V, InterpolationError<V>> {
+        let mut base = 0;
+        let mut weights = [V::zero(); N];
+        for (k, (axis, &v)) in self.axes.iter().zip(&point).enumerate() {
+            let i = segment_index(axis, v)?;
+            base += i * self.strides[k];
+            weights[k] = (v - axis[i]) / (axis[i + 1] - axis[i]);
+        }
+        let mut value = V::zero();
+        for corner in 0..1_usize << N {
+            let mut index = base;
+            let mut weight = V::one();
+            for (k, &t) in weights.iter().enumerate() {
+                if corner >> (N - 1 - k) & 1 == 1 {
+                    index += self.strides[k];
+                    weight *= t;
+                } else {
+                    weight *= V::one() - t;
+                }
+            }
+            if !weight.is_zero() {
+                value += weight * self.values[index];
+            }
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::grid_nd::InterpN;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_reproduces_multilinear_data() {
+        let f = |[x, y, z, w]: [f64; 4]| 1.0 + x - 2.0 * y * z + 0.5 * x * z * w + w;
+        let xs = [0.0, 0.5, 2.0];
+        let ys = [-1.0, 1.0];
+        let zs = [0.0, 0.1, 0.2, 1.0];
+        let ws = [3.0, 4.0, 6.0];
+        let mut values = Vec::new();
+        for x in xs {
+            for y in ys {
+                for z in zs {
+                    for w in ws {
+                        values.push(f([x, y, z, w]));
+                    }
+                }
+            }
+        }
+        let table = InterpN::try_new([&xs, &ys, &zs, &ws], &values).unwrap();
+        for point in [
+            [0.0, -1.0, 0.0, 3.0],
+            [0.3, 0.2, 0.15, 5.5],
+            [2.0, 1.0, 1.0, 6.0],
+            [1.1, -0.4, 0.7, 3.2],
+        ] {
+            assert!((table.try_value(point).unwrap() - f(point)).abs() < 1e-12);
+        }
+        assert!(matches!(
+            table.try_value([0.0, 0.0, 1.5, 3.0]),
+            Err(InterpolationError::OutOfUpperBound(z)) if z == 1.5
+        ));
+    }
+
+    #[test]
+    fn test_shape_errors() {
+        let axis = [0.0, 1.0];
+        assert!(matches!(
+            InterpN::try_new([&axis, &axis], &[0.0; 3]),
+            Err(InterpolationError::GridShapeError(4, 3))
+        ));
+        assert!(matches!(
+            InterpN::try_new([&axis, &axis[..1]], &[0.0; 2]),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+    }
+}
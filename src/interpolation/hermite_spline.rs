@@ -1,16 +1,39 @@
-use crate::{HermiteSplineError, InterpolationValue};
+use crate::interpolation::bezier::CubicBezier;
+use crate::interpolation::continuity::ContinuityReport;
+use crate::interpolation::dedup::DedupPolicy;
+use crate::interpolation::difference::MaxDifference;
+use crate::interpolation::error_bound::{derivative_estimates, powi};
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::sample::SplineSample;
+#[cfg(feature = "serde")]
+use crate::interpolation::serialization::SplineDocument;
+use crate::interpolation::shape::Shape;
+use crate::interpolation::slopes::SlopeMethod;
+#[cfg(feature = "svg")]
+use crate::interpolation::svg::{self, PathSampling};
+use crate::interpolation::{
+    check_finite, check_knots, check_removal, hermite_basis, insertion_index, locate_uniform,
+    nearest_knot, segment_index, segment_index_unchecked, uniform_knots, Location,
+};
+use crate::metadata::Metadata;
+use crate::profiling::record_segment_evaluation;
+use crate::tolerance::Tolerance;
+use crate::{InterpolationError, InterpolationValue, TranscendentalValue};
 use nalgebra::{Matrix4, Vector4};
-use num_traits::Zero;
-
-struct Point3<V> {
-    pub x: V,
-    pub y: V,
-    pub dydx: V,
-}
+use std::fmt::{self, Debug, Display};
+use std::ops::RangeInclusive;
 
 pub struct HermiteSpline<V: InterpolationValue> {
-    points: Vec<Point3<V>>,
+    // the knots, values and slopes are stored apart, so the search reads xs only
+    xs: Vec<V>,
+    ys: Vec<V>,
+    dydxs: Vec<V>,
+    tolerance: Tolerance<V>,
+    metadata: Metadata,
     m: Matrix4<V>,
+    // (x0, dx) while the knots are x0 + i dx, for the constant-time lookup
+    spacing: Option<(V, V)>,
 }
 
 impl<V: InterpolationValue> HermiteSpline<V> {
@@ -19,13 +42,18 @@ impl<V: InterpolationValue> HermiteSpline<V> {
     /// # Arguments
     ///
     /// * `raw_points` - A slice of tuples representing the raw points of the spline.
-    ///                 Each tuple should contain three elements: the x-coordinate, the y-coordinate,
-    ///                 and the derivative of y with respect to x (dy/dx).
+    ///   Each tuple should contain three elements: the x-coordinate, the y-coordinate,
+    ///   and the derivative of y with respect to x (dy/dx).
     ///
     /// # Returns
     ///
     /// Returns a `Result` containing the constructed `HermiteSpline` on success,
-    /// or a `HermiteSplineError` if the raw points are not in ascending order based on x-coordinate.
+    /// or a `InterpolationError` if there are fewer than 2 points (`InsufficientPointsError(n)`),
+    /// if the raw points are not in strictly ascending order based on x-coordinate
+    /// (`PointOrderError(i)` or `DuplicatePointError(x)`), or if a coordinate is NaN or infinite
+    /// (`NonFiniteError(i)`).
+    ///
+    /// Two points make a single cubic segment matching both values and both slopes.
     ///
     /// # Example
     ///
@@ -37,26 +65,51 @@ impl<V: InterpolationValue> HermiteSpline<V> {
     /// let spline = HermiteSpline::try_new(&raw_points);
     /// assert!(spline.is_ok());
     /// ```
-    pub fn try_new(raw_points: &[(V, V, V)]) -> Result<Self, HermiteSplineError<V>> {
-        let mut temp = raw_points[0].0;
-
-        let mut points = Vec::new();
-        for &(x, y, dydx) in raw_points {
-            let point = Point3 { x, y, dydx };
-            if point.x < temp {
-                return Err(HermiteSplineError::PointOrderError);
-            }
-            temp = point.x;
-            points.push(point);
+    pub fn try_new(raw_points: &[(V, V, V)]) -> Result<Self, InterpolationError<V>> {
+        Self::try_from_iter(raw_points.iter().copied())
+    }
+
+    /// Constructs a new `HermiteSpline` from points `(x, y, dydx)` yielded by an iterator, without
+    /// collecting them into a slice first.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new`].
+    pub fn try_from_iter(
+        raw_points: impl IntoIterator<Item = (V, V, V)>,
+    ) -> Result<Self, InterpolationError<V>> {
+        let (mut xs, mut ys, mut dydxs) = (Vec::new(), Vec::new(), Vec::new());
+        for (x, y, dydx) in raw_points {
+            xs.push(x);
+            ys.push(y);
+            dydxs.push(dydx);
         }
+        Self::try_from_columns(xs, ys, dydxs)
+    }
+
+    fn try_from_columns(
+        xs: Vec<V>,
+        ys: Vec<V>,
+        dydxs: Vec<V>,
+    ) -> Result<Self, InterpolationError<V>> {
+        if xs.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(xs.len()));
+        }
+        check_finite(
+            xs.iter()
+                .zip(&ys)
+                .zip(&dydxs)
+                .map(|((&x, &y), &dydx)| [x, y, dydx]),
+        )?;
+        check_knots(xs.iter().copied())?;
         let m = Matrix4::new(
-            V::from_i8(2).unwrap(),
-            V::from_i8(-2).unwrap(),
+            V::from_integer(2),
+            V::from_integer(-2),
             V::one(),
             V::one(),
-            V::from_i8(-3).unwrap(),
-            V::from_i8(3).unwrap(),
-            V::from_i8(-2).unwrap(),
+            V::from_integer(-3),
+            V::from_integer(3),
+            V::from_integer(-2),
             -V::one(),
             V::zero(),
             V::zero(),
@@ -67,7 +120,299 @@ impl<V: InterpolationValue> HermiteSpline<V> {
             V::zero(),
             V::zero(),
         );
-        Ok(Self { points, m })
+        Ok(Self {
+            xs,
+            ys,
+            dydxs,
+            m,
+            tolerance: Tolerance::Exact,
+            spacing: None,
+            metadata: Metadata::default(),
+        })
+    }
+
+    /// Constructs a new `HermiteSpline` from parallel columns of x-coordinates, values and slopes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::LengthMismatchError(xs.len(), n)` if `ys` or `dydxs` does not
+    /// have the length of `xs`, and otherwise the errors of [`Self::try_new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+    ///
+    /// let spline = HermiteSpline::try_new_xy(&[0.0, 1.0], &[0.0, 1.0], &[1.0, 1.0]).unwrap();
+    /// assert_eq!(spline.try_value(0.5).unwrap(), 0.5);
+    /// ```
+    pub fn try_new_xy(xs: &[V], ys: &[V], dydxs: &[V]) -> Result<Self, InterpolationError<V>> {
+        for column in [ys, dydxs] {
+            if column.len() != xs.len() {
+                return Err(InterpolationError::LengthMismatchError(
+                    xs.len(),
+                    column.len(),
+                ));
+            }
+        }
+        Self::try_from_columns(xs.to_vec(), ys.to_vec(), dydxs.to_vec())
+    }
+
+    /// Constructs a new `HermiteSpline` through `(x0 + i dx, ys[i])` with slopes `dydxs[i]`, whose
+    /// evaluation finds the segment of a query in constant time as
+    /// [`NaturalCubicSpline::try_new_uniform`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::LengthMismatchError(ys.len(), dydxs.len())` if the columns
+    /// differ in length, and otherwise the errors of [`Self::try_new`] for the generated points.
+    ///
+    /// [`NaturalCubicSpline::try_new_uniform`]: crate::interpolation::natural_cubic_spline::NaturalCubicSpline::try_new_uniform
+    pub fn try_new_uniform(
+        x0: V,
+        dx: V,
+        ys: &[V],
+        dydxs: &[V],
+    ) -> Result<Self, InterpolationError<V>> {
+        if ys.len() != dydxs.len() {
+            return Err(InterpolationError::LengthMismatchError(
+                ys.len(),
+                dydxs.len(),
+            ));
+        }
+        let mut spline = Self::try_from_iter(
+            uniform_knots(x0, dx)
+                .zip(ys.iter().zip(dydxs))
+                .map(|(x, (&y, &dydx))| (x, y, dydx)),
+        )?;
+        spline.spacing = Some((x0, dx));
+        Ok(spline)
+    }
+
+    /// Constructs a new `HermiteSpline` from points `(x, y, dydx)` in any order, sorting them by
+    /// x-coordinate and merging both the values and the slopes of the points sharing one with
+    /// `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(i)` if the `i`-th point has a NaN or infinite
+    /// coordinate, and the errors of [`Self::try_new`] for the merged points.
+    pub fn try_new_unsorted(
+        raw_points: &[(V, V, V)],
+        policy: DedupPolicy,
+    ) -> Result<Self, InterpolationError<V>> {
+        let merged: Vec<_> = policy
+            .sort_and_merge_runs(raw_points.iter().map(|&(x, y, dydx)| (x, [y, dydx])))?
+            .into_iter()
+            .map(|(x, [y, dydx])| (x, y, dydx))
+            .collect();
+        Self::try_new(&merged)
+    }
+
+    /// Constructs a new `HermiteSpline` from points `(x, y)` without slopes, computing the slope
+    /// at the `i`-th knot as `slopes(i, raw_points)`.
+    ///
+    /// The points are validated before `slopes` is called, so it may rely on them being finite,
+    /// strictly ascending and at least 2.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::InsufficientPointsError(n)` if there are fewer than 2 points,
+    /// and otherwise the errors of [`Self::try_new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+    ///
+    /// // flat tangents everywhere
+    /// let spline = HermiteSpline::try_new_with_slopes(&[(0.0, 0.0), (1.0, 1.0)], |_, _| 0.0)
+    ///     .unwrap();
+    /// assert_eq!(spline.try_value(0.5).unwrap(), 0.5);
+    /// ```
+    pub fn try_new_with_slopes(
+        raw_points: &[(V, V)],
+        slopes: impl Fn(usize, &[(V, V)]) -> V,
+    ) -> Result<Self, InterpolationError<V>> {
+        if raw_points.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(
+                raw_points.len(),
+            ));
+        }
+        check_finite(raw_points.iter().map(|&(x, y)| [x, y]))?;
+        check_knots(raw_points.iter().map(|&(x, _)| x))?;
+        Self::try_from_iter(
+            raw_points
+                .iter()
+                .enumerate()
+                .map(|(i, &(x, y))| (x, y, slopes(i, raw_points))),
+        )
+    }
+
+    /// Constructs a new `HermiteSpline` from points `(x, y)` without slopes, estimating them with
+    /// one of the built-in rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new_with_slopes`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+    /// use spline_interpolation::interpolation::slopes::SlopeMethod;
+    ///
+    /// let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 1.0), (3.0, 2.0)];
+    /// let spline = HermiteSpline::try_new_with_slope_method(&points, SlopeMethod::Pchip).unwrap();
+    /// // monotone data gives a monotone interpolant
+    /// assert_eq!(spline.try_value(1.5).unwrap(), 1.0);
+    /// ```
+    pub fn try_new_with_slope_method(
+        raw_points: &[(V, V)],
+        method: SlopeMethod,
+    ) -> Result<Self, InterpolationError<V>> {
+        let (xs, ys): (Vec<_>, Vec<_>) = raw_points.iter().copied().unzip();
+        Self::try_new_with_slopes(raw_points, |i, _| method.slope(&xs, &ys, i))
+    }
+
+    /// Constructs a new `HermiteSpline` through points `(x, y)` that is guaranteed to have the
+    /// `shape` over its whole domain, e.g. a monotone discount curve or CDF.
+    ///
+    /// The convex and concave shapes may add one knot inside some segments; see [`Shape`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::ShapeError(i)` if the points themselves do not have the shape
+    /// around the `i`-th one, or if no C¹ spline of the shape interpolates them there, and
+    /// otherwise the errors of [`Self::try_new_with_slopes`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+    /// use spline_interpolation::interpolation::shape::Shape;
+    ///
+    /// let points = [(0.0, 1.0), (1.0, 0.97), (2.0, 0.93), (3.0, 0.93), (5.0, 0.8)];
+    /// let spline = HermiteSpline::try_new_with_shape(&points, Shape::Decreasing).unwrap();
+    /// // flat between the equal discount factors, where an unconstrained spline bulges
+    /// assert_eq!(spline.try_value(2.5).unwrap(), 0.93);
+    /// ```
+    pub fn try_new_with_shape(
+        raw_points: &[(V, V)],
+        shape: Shape,
+    ) -> Result<Self, InterpolationError<V>> {
+        if raw_points.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(
+                raw_points.len(),
+            ));
+        }
+        check_finite(raw_points.iter().map(|&(x, y)| [x, y]))?;
+        check_knots(raw_points.iter().map(|&(x, _)| x))?;
+        let (xs, ys): (Vec<_>, Vec<_>) = raw_points.iter().copied().unzip();
+        Self::try_from_iter(shape.knots(&xs, &ys)?)
+    }
+
+    /// Sets the tolerance within which a query is treated as hitting a knot exactly,
+    /// returning the stored value instead of evaluating a segment.
+    ///
+    /// Queries within the tolerance of the first or last knot are accepted even if they lie
+    /// slightly outside the domain. The default is [`Tolerance::Exact`].
+    pub fn with_tolerance(mut self, tolerance: Tolerance<V>) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Attaches provenance metadata to the spline.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the provenance metadata of the spline.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the provenance metadata of the spline for editing.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Returns the index of the segment containing `x`; see
+    /// [`PiecewisePolynomial::segment_index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` outside the knots, and
+    /// `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn segment_index(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        segment_index(&self.xs, x)
+    }
+
+    /// Returns the knots `(start, end)` of the `i`-th segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than the number of segments, one less than the number of knots.
+    pub fn segment_bounds(&self, i: usize) -> (V, V) {
+        (self.xs[i], self.xs[i + 1])
+    }
+
+    /// Returns the index of the knot nearest to `x`; see [`PiecewisePolynomial::nearest_knot`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn nearest_knot(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        nearest_knot(&self.xs, x)
+    }
+
+    /// Returns the highest degree of polynomials this spline reproduces exactly, which is 3:
+    /// values and slopes sampled from a cubic determine the cubic on every segment.
+    pub fn degree_exactness(&self) -> usize {
+        3
+    }
+
+    /// Inserts the point `(x, y, dydx)` at its place in the knot order.
+    ///
+    /// Only the two segments adjacent to the new point change; the spline is not refitted.
+    ///
+    /// # Returns
+    ///
+    /// The index of the new knot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::DuplicatePointError(x)` if there already is a knot at `x`, and
+    /// `InterpolationError::NonFiniteError(n)` if a coordinate of the point, which would be the
+    /// `n`-th, is NaN or infinite. The spline is then unchanged.
+    pub fn insert_point(&mut self, x: V, y: V, dydx: V) -> Result<usize, InterpolationError<V>> {
+        let i = insertion_index(&self.xs, [x, y, dydx])?;
+        self.xs.insert(i, x);
+        self.ys.insert(i, y);
+        self.dydxs.insert(i, dydx);
+        self.spacing = None;
+        Ok(i)
+    }
+
+    /// Removes the `index`-th knot and returns it as `(x, y, dydx)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if fewer than 2 points would remain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_point(&mut self, index: usize) -> Result<(V, V, V), InterpolationError<V>> {
+        check_removal(self.xs.len(), index, 2)?;
+        let point = (
+            self.xs.remove(index),
+            self.ys.remove(index),
+            self.dydxs.remove(index),
+        );
+        self.spacing = None;
+        Ok(point)
     }
 
     /// Tries to evaluate the interpolated value of Hermite spline at a given point x.
@@ -85,30 +430,355 @@ impl<V: InterpolationValue> HermiteSpline<V> {
     ///
     /// Returns `OutOfLowerBound(x)` if `x` is less than the minimum x-coordinate value of any point in the Hermite spline.
     /// Returns `OutOfUpperBound(x)` if `x` is greater than the maximum x-coordinate value of any point in the Hermite spline.
-    pub fn try_value(self, x: V) -> Result<V, HermiteSplineError<V>> {
-        match self
-            .points
-            .binary_search_by(|point| point.x.partial_cmp(&x).unwrap())
-        {
-            Ok(pos) => Ok(self.points[pos].y),
-            Err(pos) => {
-                if pos.is_zero() {
-                    return Err(HermiteSplineError::OutOfLowerBound(x));
-                }
-                if pos > self.points.len() {
-                    return Err(HermiteSplineError::OutOfUpperBound(x));
-                }
-                let pos = pos - 1;
-                let point = &self.points[pos];
-                let next_point = &self.points[pos + 1];
-                let h = next_point.x - point.x;
-                let delta = (x - point.x) / h;
-                let delta2 = delta * delta;
-                let delta3 = delta2 * delta;
-                let d = Vector4::new(delta3, delta2, delta, V::from_i8(1).unwrap());
-                let f = Vector4::new(point.y, next_point.y, point.dydx * h, next_point.dydx * h);
-                Ok((d.transpose() * self.m * f).x)
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => Ok(self.ys[pos]),
+            Location::Segment(pos) => Ok(self.value_in_segment(pos, x)),
+        }
+    }
+
+    /// Evaluates the spline at `x` without checking it, for hot loops over queries already known
+    /// to lie within the knots.
+    ///
+    /// Unlike [`Self::try_value`], a query at a knot is evaluated with the polynomial of its
+    /// segment, which may round differently from the stored value.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `x` is NaN or outside the knots. Release builds extrapolate the
+    /// end segments instead, and return NaN for a NaN query.
+    pub fn value_unchecked(&self, x: V) -> V {
+        self.value_in_segment(segment_index_unchecked(&self.xs, x), x)
+    }
+
+    /// Evaluates the polynomial of the `pos`-th segment at `x`.
+    fn value_in_segment(&self, pos: usize, x: V) -> V {
+        record_segment_evaluation();
+        let h = self.xs[pos + 1] - self.xs[pos];
+        let delta = (x - self.xs[pos]) / h;
+        let delta2 = delta * delta;
+        let delta3 = delta2 * delta;
+        let d = Vector4::new(delta3, delta2, delta, V::one());
+        (d.transpose() * self.segment_coefficients(pos)).x
+    }
+
+    /// Returns the derivative of the value at `x` with respect to each of the y-coordinates, the
+    /// slopes held fixed, e.g. for analytic bucketed deltas.
+    ///
+    /// These are the Hermite basis weights of the two values around `x`. Slopes derived from the
+    /// values, as by [`Self::try_new_with_slope_method`], are taken as given.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn value_gradient(&self, x: V) -> Result<Vec<V>, InterpolationError<V>> {
+        let mut gradient = vec![V::zero(); self.xs.len()];
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => gradient[pos] = V::one(),
+            Location::Segment(pos) => {
+                let t = (x - self.xs[pos]) / (self.xs[pos + 1] - self.xs[pos]);
+                let ([w0, w1], _) = hermite_basis(t);
+                gradient[pos] = w0;
+                gradient[pos + 1] = w1;
             }
         }
+        Ok(gradient)
+    }
+
+    /// Evaluates the spline and its first and second derivatives at `x` with a single segment
+    /// lookup.
+    ///
+    /// At a knot the derivatives are those of the segment starting there, or ending there for the
+    /// last knot.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_eval_all(&self, x: V) -> Result<SplineSample<V>, InterpolationError<V>> {
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => {
+                let segment = pos.min(self.xs.len() - 2);
+                let t = if segment == pos { V::zero() } else { V::one() };
+                Ok(SplineSample {
+                    value: self.ys[pos],
+                    ..self.sample_in_segment(segment, t)
+                })
+            }
+            Location::Segment(pos) => {
+                let t = (x - self.xs[pos]) / (self.xs[pos + 1] - self.xs[pos]);
+                Ok(self.sample_in_segment(pos, t))
+            }
+        }
+    }
+
+    /// Returns the cubic Bézier curve of each segment, e.g. to draw the spline with a vector
+    /// renderer.
+    pub fn to_bezier_segments(&self) -> Vec<CubicBezier<V>> {
+        (0..self.xs.len() - 1)
+            .map(|pos| {
+                CubicBezier::from_hermite(
+                    (self.xs[pos], self.ys[pos]),
+                    self.dydxs[pos],
+                    (self.xs[pos + 1], self.ys[pos + 1]),
+                    self.dydxs[pos + 1],
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the jumps of the spline and its first two derivatives at the interior knots, where
+    /// the spline is C1 and only the curvature jumps.
+    pub fn continuity_report(&self) -> ContinuityReport<V> {
+        ContinuityReport::from_segment_ends(
+            &self.xs,
+            (0..self.xs.len() - 1).map(|pos| {
+                (
+                    self.sample_in_segment(pos, V::zero()),
+                    self.sample_in_segment(pos, V::one()),
+                )
+            }),
+        )
+    }
+
+    /// Returns `n` points `(x, y)` of the graph of the spline equally spaced by arc length, the
+    /// first and last knots included, at least 2; see [`PiecewisePolynomial::sample_evenly`].
+    pub fn sample_evenly(&self, n: usize) -> Vec<(V, V)>
+    where
+        V: TranscendentalValue,
+    {
+        PiecewisePolynomial::from(self)
+            .evenly_spaced_xs(n)
+            .into_iter()
+            .map(|x| {
+                (
+                    x,
+                    self.try_value(x).expect("the samples lie inside the knots"),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns every `x` at which the spline crosses the level `y`, in increasing order; see
+    /// [`PiecewisePolynomial::intersect_level`].
+    pub fn intersect_level(&self, y: V) -> Vec<V> {
+        PiecewisePolynomial::from(self).intersect_level(y)
+    }
+
+    /// Returns every `x` at which the spline crosses the line `a + b x`, in increasing order; see
+    /// [`PiecewisePolynomial::intersect_line`].
+    pub fn intersect_line(&self, a: V, b: V) -> Vec<V> {
+        PiecewisePolynomial::from(self).intersect_line(a, b)
+    }
+
+    /// Returns the lowest point `(x, y)` of the spline over `range`; see
+    /// [`PiecewisePolynomial::argmin`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the knots.
+    pub fn argmin(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        let (x, _) = PiecewisePolynomial::from(self).argmin(range)?;
+        Ok((x, self.try_value(x)?))
+    }
+
+    /// Returns the highest point `(x, y)` of the spline over `range`; see
+    /// [`PiecewisePolynomial::argmax`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the knots.
+    pub fn argmax(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        let (x, _) = PiecewisePolynomial::from(self).argmax(range)?;
+        Ok((x, self.try_value(x)?))
+    }
+
+    /// Returns the largest absolute and relative differences to the reference `other`, e.g. a
+    /// spline of another method through the same points, over the common domain of both; see
+    /// [`PiecewisePolynomial::max_difference`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`PiecewisePolynomial::max_difference`].
+    pub fn max_difference(
+        &self,
+        other: impl Into<PiecewisePolynomial<V>>,
+    ) -> Result<MaxDifference<V>, InterpolationError<V>> {
+        PiecewisePolynomial::from(self).max_difference(&other.into())
+    }
+
+    /// Returns the spline as a LaTeX `cases` environment of its polynomial on each segment, see
+    /// [`PiecewisePolynomial::to_latex`].
+    pub fn to_latex(&self) -> String
+    where
+        V: Display,
+    {
+        PiecewisePolynomial::from(self).to_latex()
+    }
+
+    /// Returns the SVG path data, the `d` attribute, of the graph of the spline.
+    #[cfg(feature = "svg")]
+    pub fn to_svg_path_data(&self, sampling: PathSampling) -> String
+    where
+        V: Display,
+    {
+        match sampling {
+            PathSampling::Exact => svg::bezier_path_data(&self.to_bezier_segments()),
+            PathSampling::Polyline(points) => {
+                svg::polyline_path_data((self.xs[0], self.xs[self.xs.len() - 1]), points, |x| {
+                    self.try_value(x).expect("the samples lie inside the knots")
+                })
+            }
+        }
+    }
+
+    /// Evaluates the `pos`-th segment at the normalized position `t`.
+    fn sample_in_segment(&self, pos: usize, t: V) -> SplineSample<V> {
+        record_segment_evaluation();
+        let h = self.xs[pos + 1] - self.xs[pos];
+        SplineSample::from_normalized_cubic(&self.segment_coefficients(pos), t, h)
+    }
+
+    /// Estimates the interpolation error on each segment as `h⁴ |f⁗| / 384`, the classical bound
+    /// for cubic Hermite interpolation, with `f⁗` estimated from fourth divided differences of the
+    /// data.
+    ///
+    /// The estimate assumes the given slopes are the exact derivatives; errors in the slopes add
+    /// up to `h |Δslope| / 4` on a segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if there are fewer than 5 points.
+    pub fn error_bound_estimate(&self) -> Result<Vec<V>, InterpolationError<V>> {
+        let (xs, ys) = (&self.xs, &self.ys);
+        let constant = V::one() / V::from_integer(384);
+        Ok(derivative_estimates(xs, ys, 4)?
+            .into_iter()
+            .zip(xs.windows(2))
+            .map(|(d4, w)| constant * powi(w[1] - w[0], 4) * d4)
+            .collect())
+    }
+
+    /// Returns the coefficients of `(t^3, t^2, t, 1)` on the `pos`-th segment,
+    /// where `t` is the position normalized to `[0, 1]`.
+    fn segment_coefficients(&self, pos: usize) -> Vector4<V> {
+        let h = self.xs[pos + 1] - self.xs[pos];
+        let f = Vector4::new(
+            self.ys[pos],
+            self.ys[pos + 1],
+            self.dydxs[pos] * h,
+            self.dydxs[pos + 1] * h,
+        );
+        self.m * f
+    }
+}
+
+impl<V: InterpolationValue> From<&HermiteSpline<V>> for PiecewisePolynomial<V> {
+    fn from(spline: &HermiteSpline<V>) -> Self {
+        PiecewisePolynomial::from_normalized_cubics(
+            spline.xs.clone(),
+            (0..spline.xs.len() - 1).map(|pos| spline.segment_coefficients(pos)),
+        )
+        .with_metadata(spline.metadata.clone())
+    }
+}
+
+impl_interpolator!(HermiteSpline, Hermite, metadata);
+
+impl<V: InterpolationValue> Debug for HermiteSpline<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HermiteSpline")
+            .field("xs", &self.xs)
+            .field("ys", &self.ys)
+            .field("dydxs", &self.dydxs)
+            .field("tolerance", &self.tolerance)
+            .field("metadata", &self.metadata)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Writes the polynomial on each segment, see the [`Display`] form of [`PiecewisePolynomial`].
+impl<V: InterpolationValue + Display> Display for HermiteSpline<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&PiecewisePolynomial::from(self), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V: InterpolationValue + serde::Serialize> serde::Serialize for HermiteSpline<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SplineDocument {
+            points: (0..self.xs.len())
+                .map(|i| (self.xs[i], self.ys[i], self.dydxs[i]))
+                .collect(),
+            tolerance: self.tolerance,
+            metadata: self.metadata.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Fits the spline again through the deserialized points, failing as [`HermiteSpline::try_new`].
+#[cfg(feature = "serde")]
+impl<'de, V: InterpolationValue + Display + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for HermiteSpline<V>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = SplineDocument::<(V, V, V), V>::deserialize(deserializer)?;
+        Ok(Self::try_new(&document.points)
+            .map_err(serde::de::Error::custom)?
+            .with_tolerance(document.tolerance)
+            .with_metadata(document.metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_insert_non_finite_point() {
+        let mut spline = HermiteSpline::try_new(&[(0.0, 0.0, 1.0), (2.0, 2.0, 1.0)]).unwrap();
+        for (x, y, dydx) in [
+            (f64::NAN, 1.0, 0.0),
+            (f64::INFINITY, 1.0, 0.0),
+            (f64::NEG_INFINITY, 1.0, 0.0),
+            (1.0, f64::NAN, 0.0),
+            (1.0, 1.0, f64::INFINITY),
+        ] {
+            assert!(matches!(
+                spline.insert_point(x, y, dydx),
+                Err(InterpolationError::NonFiniteError(2))
+            ));
+        }
+        assert_eq!(spline.try_value(1.0).unwrap(), 1.0);
+        assert_eq!(spline.insert_point(1.0, 1.0, 1.0).unwrap(), 1);
+        assert_eq!(spline.try_value(0.5).unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test_point_count() {
+        assert!(matches!(
+            HermiteSpline::<f64>::try_new(&[]),
+            Err(InterpolationError::InsufficientPointsError(0))
+        ));
+        assert!(matches!(
+            HermiteSpline::try_new(&[(0.0, 1.0, 0.0)]),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+        assert!(matches!(
+            HermiteSpline::<f64>::try_new_xy(&[], &[], &[]),
+            Err(InterpolationError::InsufficientPointsError(0))
+        ));
+        // a single segment: the cubic 1 + x^3 on [0, 2]
+        let spline = HermiteSpline::try_new(&[(0.0, 1.0, 0.0), (2.0, 9.0, 12.0)]).unwrap();
+        for x in [0.0_f64, 0.5, 1.0, 1.5, 2.0] {
+            assert!((spline.try_value(x).unwrap() - (1.0 + x * x * x)).abs() < 1e-12);
+        }
+        assert!(matches!(
+            spline.try_value(2.5),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
     }
 }
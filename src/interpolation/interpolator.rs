@@ -0,0 +1,217 @@
+//! The object-safe interface shared by the one-dimensional interpolants.
+
+use crate::metadata::Metadata;
+use crate::{InterpolationError, InterpolationValue};
+use std::any::Any;
+
+/// Identifies the method behind a [`dyn Interpolator`](Interpolator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MethodTag {
+    /// [`HermiteSpline`](crate::interpolation::hermite_spline::HermiteSpline)
+    Hermite,
+    /// [`NaturalCubicSpline`](crate::interpolation::natural_cubic_spline::NaturalCubicSpline)
+    NaturalCubic,
+    /// [`CatmullRomSpline`](crate::interpolation::catmull_rom_spline::CatmullRomSpline)
+    CatmullRom,
+    /// [`PiecewisePolynomial`](crate::interpolation::piecewise_polynomial::PiecewisePolynomial)
+    PiecewisePolynomial,
+    /// [`PartitionedSpline`](crate::interpolation::partition::PartitionedSpline)
+    Partitioned,
+    /// [`StreamingHermiteSpline`](crate::interpolation::streaming::StreamingHermiteSpline)
+    StreamingHermite,
+    /// [`StreamingCatmullRomSpline`](crate::interpolation::streaming::StreamingCatmullRomSpline)
+    StreamingCatmullRom,
+    /// [`Extrapolated`](crate::interpolation::builder::Extrapolated)
+    Extrapolated,
+    /// [`SmoothingSpline`](crate::interpolation::smoothing_spline::SmoothingSpline)
+    Smoothing,
+    /// [`CompositeInterpolator`](crate::interpolation::composite::CompositeInterpolator)
+    Composite,
+    /// [`TransformedInterpolator`](crate::interpolation::transformed::TransformedInterpolator)
+    Transformed,
+    /// [`NewtonPolynomial`](crate::interpolation::newton::NewtonPolynomial)
+    Newton,
+    /// [`GaussianProcess`](crate::interpolation::gp::GaussianProcess)
+    #[cfg(feature = "gp")]
+    GaussianProcess,
+}
+
+/// A one-dimensional interpolant, usable as a trait object.
+///
+/// The trait is sealed: it is implemented by the interpolants of this crate only, so methods can
+/// be added to it without a breaking change.
+///
+/// Every interpolator is `Send + Sync`, so boxed ones can be shared across threads as they are.
+///
+/// Heterogeneous collections of boxed interpolators can recover the concrete type with
+/// [`downcast_ref`](#method.downcast_ref), dispatching on [`Interpolator::method`] first if needed.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::interpolator::{Interpolator, MethodTag};
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+///
+/// let points = [(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)];
+/// let curves: Vec<Box<dyn Interpolator<f64>>> = vec![
+///     Box::new(NaturalCubicSpline::try_new(&points).unwrap()),
+///     Box::new(CatmullRomSpline::try_new(&points).unwrap()),
+/// ];
+/// for curve in &curves {
+///     assert_eq!(curve.try_value(0.75).unwrap(), 0.25);
+/// }
+/// assert_eq!(curves[1].method(), MethodTag::CatmullRom);
+/// assert!(curves[0].downcast_ref::<NaturalCubicSpline<f64>>().is_some());
+/// assert!(curves[1].downcast_ref::<NaturalCubicSpline<f64>>().is_none());
+/// ```
+pub trait Interpolator<V: InterpolationValue>: Any + Send + Sync + sealed::Sealed {
+    /// Evaluates the interpolant at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the inherent `try_value` of the implementing type.
+    fn try_value(&self, x: V) -> Result<V, InterpolationError<V>>;
+
+    /// Returns the method of the interpolant.
+    fn method(&self) -> MethodTag;
+
+    /// Returns the provenance metadata, or `None` if the interpolant does not carry any.
+    fn metadata(&self) -> Option<&Metadata> {
+        None
+    }
+
+    /// Evaluates the interpolant at every `xs[i]` into `out[i]`, without allocating.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatchError(xs.len(), out.len())` if the views differ in length, and
+    /// otherwise the first error of [`Interpolator::try_value`]; `out` is then partially written.
+    #[cfg(feature = "ndarray")]
+    fn try_values_into(
+        &self,
+        xs: ndarray::ArrayView1<V>,
+        mut out: ndarray::ArrayViewMut1<V>,
+    ) -> Result<(), InterpolationError<V>> {
+        if xs.len() != out.len() {
+            return Err(InterpolationError::LengthMismatchError(xs.len(), out.len()));
+        }
+        for (&x, value) in xs.iter().zip(out.iter_mut()) {
+            *value = self.try_value(x)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `self` as [`Any`], for downcasting to the concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns `self` as mutable [`Any`], for downcasting to the concrete type.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<V: InterpolationValue> dyn Interpolator<V> {
+    /// Returns `true` if the interpolant is a `T`.
+    pub fn is<T: Interpolator<V>>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Returns the interpolant as a `T`, or `None` if it is of another type.
+    pub fn downcast_ref<T: Interpolator<V>>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Returns the interpolant as a mutable `T`, or `None` if it is of another type.
+    pub fn downcast_mut<T: Interpolator<V>>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+pub(crate) mod sealed {
+    /// Restricts the implementors of [`Interpolator`](super::Interpolator) to this crate.
+    pub trait Sealed {}
+}
+
+/// Implements [`Interpolator`] for a type with an inherent `try_value(&self, V)`, and with the
+/// `metadata` flag an inherent `metadata(&self) -> &Metadata`.
+macro_rules! impl_interpolator {
+    ($ty:ident, $tag:ident, metadata) => {
+        $crate::interpolation::interpolator::impl_interpolator!($ty, $tag, {
+            fn metadata(&self) -> Option<&$crate::metadata::Metadata> {
+                Some($ty::metadata(self))
+            }
+        });
+    };
+    ($ty:ident, $tag:ident) => {
+        $crate::interpolation::interpolator::impl_interpolator!($ty, $tag, {});
+    };
+    ($ty:ident, $tag:ident, { $($extra:item)* }) => {
+        impl<V: $crate::InterpolationValue> $crate::interpolation::interpolator::sealed::Sealed
+            for $ty<V>
+        {
+        }
+
+        impl<V: $crate::InterpolationValue> $crate::interpolation::interpolator::Interpolator<V>
+            for $ty<V>
+        {
+            fn try_value(&self, x: V) -> Result<V, $crate::InterpolationError<V>> {
+                $ty::try_value(self, x)
+            }
+
+            fn method(&self) -> $crate::interpolation::interpolator::MethodTag {
+                $crate::interpolation::interpolator::MethodTag::$tag
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+
+            $($extra)*
+        }
+    };
+}
+pub(crate) use impl_interpolator;
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::interpolator::{Interpolator, MethodTag};
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+
+    #[test]
+    fn test_downcast_mut() {
+        let points = [(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)];
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        let polynomial = PiecewisePolynomial::from(&natural);
+        let mut curves: Vec<Box<dyn Interpolator<f64>>> = vec![
+            Box::new(HermiteSpline::try_new(&[(0.0, 0.0, 1.0), (1.0, 1.0, 1.0)]).unwrap()),
+            Box::new(natural),
+            Box::new(polynomial),
+        ];
+        let tags: Vec<_> = curves.iter().map(|curve| curve.method()).collect();
+        assert_eq!(
+            tags,
+            [
+                MethodTag::Hermite,
+                MethodTag::NaturalCubic,
+                MethodTag::PiecewisePolynomial
+            ]
+        );
+        for curve in &mut curves {
+            if let Some(spline) = curve.downcast_mut::<NaturalCubicSpline<f64>>() {
+                spline.set_y(1, 0.0).unwrap();
+            }
+        }
+        assert!(curves[1].is::<NaturalCubicSpline<f64>>());
+        assert_eq!(curves[1].try_value(0.5).unwrap(), 0.0);
+        assert_eq!(curves[2].try_value(0.5).unwrap(), 0.5);
+        assert!(curves[0]
+            .metadata()
+            .is_some_and(|metadata| metadata.is_empty()));
+    }
+}
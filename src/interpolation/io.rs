@@ -0,0 +1,188 @@
+//! Loading points from CSV and JSON data, e.g. for scripts and tests building splines straight
+//! from data files.
+//!
+//! The JSON schema is an object with the points as `[x, y]` pairs and optional
+//! [`Metadata`](crate::metadata::Metadata) in its serde form:
+//!
+//! ```json
+//! {"points": [[0.0, 1.0], [0.5, 0.5], [1.0, 0.0]], "metadata": {"name": "discount"}}
+//! ```
+
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::metadata::Metadata;
+use crate::{InterpolationError, InterpolationValue};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::io::Read;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The errors of reading points from data.
+#[derive(Error, Debug)]
+pub enum LoadError {
+    /// The CSV data is malformed or could not be read.
+    #[error("invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+    /// The CSV header has no column of this name.
+    #[error("no column named {0:?}")]
+    MissingColumn(String),
+    /// The field in this column of this 1-based line is missing or not a number.
+    #[error("line {line}, column {column:?}: {field:?} is not a number")]
+    InvalidNumber {
+        /// The line of the field, counting the header as line 1.
+        line: u64,
+        /// The name of the column of the field.
+        column: String,
+        /// The field as read.
+        field: String,
+    },
+    /// The JSON data is malformed, does not follow the schema, or could not be read.
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Reads the points `(x, y)` from the columns named `x_col` and `y_col` of CSV data with a header
+/// row, in the order of the rows.
+///
+/// # Errors
+///
+/// Returns `LoadError` for malformed data, a missing column or a field that does not parse.
+pub fn read_csv_points<V: InterpolationValue + FromStr>(
+    reader: impl Read,
+    x_col: &str,
+    y_col: &str,
+) -> Result<Vec<(V, V)>, InterpolationError<V>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers = reader.headers().map_err(LoadError::from)?;
+    let position = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| LoadError::MissingColumn(name.to_string()))
+    };
+    let columns = [(position(x_col)?, x_col), (position(y_col)?, y_col)];
+    let mut points = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(LoadError::from)?;
+        let [x, y] = columns.map(|(index, name)| {
+            let field = record.get(index).unwrap_or_default();
+            field.parse().map_err(|_| LoadError::InvalidNumber {
+                line: record.position().map_or(0, |position| position.line()),
+                column: name.to_string(),
+                field: field.to_string(),
+            })
+        });
+        points.push((x?, y?));
+    }
+    Ok(points)
+}
+
+/// The points `(x, y)` read from data.
+type Points<V> = Vec<(V, V)>;
+
+/// The JSON schema of [`read_json_points`].
+#[derive(Deserialize)]
+#[serde(bound = "V: DeserializeOwned")]
+struct PointsDocument<V> {
+    points: Points<V>,
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+/// Reads the points and metadata of a JSON document in the [schema](self) of this module.
+///
+/// # Errors
+///
+/// Returns `LoadError::Json` for malformed data or data not following the schema.
+pub fn read_json_points<V: InterpolationValue + DeserializeOwned>(
+    reader: impl Read,
+) -> Result<(Points<V>, Metadata), InterpolationError<V>> {
+    let document: PointsDocument<V> = serde_json::from_reader(reader).map_err(LoadError::from)?;
+    Ok((document.points, document.metadata))
+}
+
+impl<V: InterpolationValue + FromStr> NaturalCubicSpline<V> {
+    /// Constructs a new `NaturalCubicSpline` through the points in the columns named `x_col` and
+    /// `y_col` of CSV data with a header row.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`read_csv_points`] and [`Self::try_new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let data = "tenor,rate\n0.0,1.0\n0.5,0.5\n1.0,0.0\n";
+    /// let spline = NaturalCubicSpline::try_from_csv(data.as_bytes(), "tenor", "rate").unwrap();
+    /// assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+    /// ```
+    pub fn try_from_csv(
+        reader: impl Read,
+        x_col: &str,
+        y_col: &str,
+    ) -> Result<Self, InterpolationError<V>> {
+        Self::try_new(&read_csv_points(reader, x_col, y_col)?)
+    }
+}
+
+impl<V: InterpolationValue + DeserializeOwned> NaturalCubicSpline<V> {
+    /// Constructs a new `NaturalCubicSpline` through the points of a JSON document in the
+    /// [schema](crate::interpolation::io) of [`read_json_points`], carrying over its metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`read_json_points`] and [`Self::try_new`].
+    pub fn try_from_json(reader: impl Read) -> Result<Self, InterpolationError<V>> {
+        let (points, metadata) = read_json_points(reader)?;
+        Ok(Self::try_new(&points)?.with_metadata(metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::io::{read_csv_points, LoadError};
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_csv() {
+        let data = "date, tenor, rate\n2024-01-01, 2.0, 0.5\n2024-01-02, 0.5, 0.25\n";
+        let points: Vec<(f64, f64)> = read_csv_points(data.as_bytes(), "tenor", "rate").unwrap();
+        assert_eq!(points, [(2.0, 0.5), (0.5, 0.25)]);
+        assert!(matches!(
+            NaturalCubicSpline::<f64>::try_from_csv(data.as_bytes(), "tenor", "rate"),
+            Err(InterpolationError::PointOrderError(1))
+        ));
+        assert!(matches!(
+            read_csv_points::<f64>(data.as_bytes(), "tenor", "yield"),
+            Err(InterpolationError::LoadError(LoadError::MissingColumn(name))) if name == "yield"
+        ));
+        let error = read_csv_points::<f64>(data.as_bytes(), "date", "rate").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to load points: line 2, column \"date\": \"2024-01-01\" is not a number"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let data = r#"{"points": [[0.0, 1.0], [0.5, 0.5], [1.0, 0.0]],
+            "metadata": {"name": "discount", "source": null, "built_at": null,
+                "attributes": {"currency": "JPY"}}}"#;
+        let spline = NaturalCubicSpline::try_from_json(data.as_bytes()).unwrap();
+        assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+        assert_eq!(spline.metadata().name(), Some("discount"));
+        assert_eq!(spline.metadata().attribute("currency"), Some("JPY"));
+        let bare = NaturalCubicSpline::try_from_json(r#"{"points": [[0, 1], [1, 3]]}"#.as_bytes())
+            .unwrap();
+        assert_eq!(bare.try_value(0.5).unwrap(), 2.0);
+        assert!(matches!(
+            NaturalCubicSpline::<f64>::try_from_json(r#"{"points": [[0, 1, 2]]}"#.as_bytes()),
+            Err(InterpolationError::LoadError(LoadError::Json(_)))
+        ));
+    }
+}
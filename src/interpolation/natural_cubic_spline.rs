@@ -1,107 +1,938 @@
-use crate::math::tridiagonal_matrix::TridiagonalMatrix;
-use crate::{HermiteSplineError, InterpolationValue};
-use num_traits::Zero;
-
-struct Point3<V> {
-    pub x: V,
-    pub y: V,
-    pub dydx: V,
-}
+use crate::interpolation::bezier::CubicBezier;
+use crate::interpolation::continuity::ContinuityReport;
+use crate::interpolation::dedup::DedupPolicy;
+use crate::interpolation::difference::MaxDifference;
+use crate::interpolation::error_bound::{derivative_estimates, powi};
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::sample::SplineSample;
+#[cfg(feature = "serde")]
+use crate::interpolation::serialization::SplineDocument;
+#[cfg(feature = "svg")]
+use crate::interpolation::svg::{self, PathSampling};
+use crate::interpolation::{
+    check_finite, check_knots, check_removal, insertion_index, locate_uniform, nearest_knot,
+    segment_index, segment_index_unchecked, uniform_knots, Location,
+};
+use crate::math::abs;
+use crate::math::roots::{horner, roots_in};
+use crate::math::tridiagonal_matrix::{MatrixValidationError, TridiagonalLU, TridiagonalMatrix};
+use crate::metadata::Metadata;
+use crate::profiling::record_segment_evaluation;
+use crate::tolerance::Tolerance;
+use crate::{InterpolationError, InterpolationValue, TranscendentalValue};
+use std::fmt::{self, Debug, Display};
+use std::ops::RangeInclusive;
 
 pub struct NaturalCubicSpline<V: InterpolationValue> {
-    points: Vec<Point3<V>>,
+    // the knots, values and second derivatives are stored apart, so the search reads xs only
+    xs: Vec<V>,
+    ys: Vec<V>,
+    second_derivatives: Vec<V>,
+    tolerance: Tolerance<V>,
+    metadata: Metadata,
+    factorization: TridiagonalLU<V>,
+    // (x0, dx) while the knots are x0 + i dx, for the constant-time lookup
+    spacing: Option<(V, V)>,
 }
 
 impl<V: InterpolationValue> NaturalCubicSpline<V> {
-    pub fn try_new(raw_points: &[(V, V)]) -> Result<Self, HermiteSplineError<V>> {
-        if raw_points.len() < 3 {
-            return Err(HermiteSplineError::InsufficientPointsError(
-                raw_points.len(),
+    /// Constructs a new `NaturalCubicSpline` through the points `(x, y)`, with zero curvature at
+    /// both ends.
+    ///
+    /// Two points make the line through them, the only cubic with zero curvature at both ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if there are fewer than 2 points,
+    /// `NonFiniteError(i)` if the `i`-th point has a NaN or infinite coordinate, and
+    /// `DuplicatePointError(x)` or `PointOrderError(i)` if the x-coordinates are not strictly
+    /// ascending.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (2.0, 0.0)]).unwrap();
+    /// assert_eq!(spline.try_value(1.0).unwrap(), 0.5);
+    /// ```
+    pub fn try_new(raw_points: &[(V, V)]) -> Result<Self, InterpolationError<V>> {
+        Self::try_from_iter(raw_points.iter().copied())
+    }
+
+    /// Constructs a new `NaturalCubicSpline` from points `(x, y)` yielded by an iterator, without
+    /// collecting them into a slice first.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let spline = NaturalCubicSpline::try_from_iter((0..=4).map(|i| (i as f64, (i * i) as f64)))
+    ///     .unwrap();
+    /// assert_eq!(spline.try_value(2.0).unwrap(), 4.0);
+    /// ```
+    pub fn try_from_iter(
+        raw_points: impl IntoIterator<Item = (V, V)>,
+    ) -> Result<Self, InterpolationError<V>> {
+        let (xs, ys) = raw_points.into_iter().unzip();
+        Self::try_from_columns(xs, ys)
+    }
+
+    /// Constructs a new `NaturalCubicSpline` from parallel columns of x- and y-coordinates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::LengthMismatchError(xs.len(), ys.len())` if the columns differ
+    /// in length, and otherwise the errors of [`Self::try_new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let spline = NaturalCubicSpline::try_new_xy(&[0.0, 0.5, 1.0], &[1.0, 0.5, 0.0]).unwrap();
+    /// assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+    /// ```
+    pub fn try_new_xy(xs: &[V], ys: &[V]) -> Result<Self, InterpolationError<V>> {
+        if xs.len() != ys.len() {
+            return Err(InterpolationError::LengthMismatchError(xs.len(), ys.len()));
+        }
+        Self::try_from_columns(xs.to_vec(), ys.to_vec())
+    }
+
+    /// Constructs a new `NaturalCubicSpline` through `(x0 + i dx, ys[i])`.
+    ///
+    /// Evaluation then finds the segment of a query from `⌊(x - x0) / dx⌋` in constant time instead
+    /// of a binary search, until the knots are changed by [`Self::insert_point`] or
+    /// [`Self::remove_point`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new`] for the generated points, e.g.
+    /// `DuplicatePointError(x0)` if `dx` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let spline = NaturalCubicSpline::try_new_uniform(0.0, 0.5, &[1.0, 0.5, 0.0]).unwrap();
+    /// assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+    /// ```
+    pub fn try_new_uniform(x0: V, dx: V, ys: &[V]) -> Result<Self, InterpolationError<V>> {
+        let mut spline = Self::try_from_iter(uniform_knots(x0, dx).zip(ys.iter().copied()))?;
+        spline.spacing = Some((x0, dx));
+        Ok(spline)
+    }
+
+    fn try_from_columns(xs: Vec<V>, ys: Vec<V>) -> Result<Self, InterpolationError<V>> {
+        if xs.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(xs.len()));
+        }
+        check_finite(xs.iter().zip(&ys).map(|(&x, &y)| [x, y]))?;
+        check_knots(xs.iter().copied())?;
+        let mut spline = Self {
+            factorization: second_derivative_system(&xs)?,
+            second_derivatives: Vec::new(),
+            xs,
+            ys,
+            tolerance: Tolerance::Exact,
+            spacing: None,
+            metadata: Metadata::default(),
+        };
+        spline.resolve();
+        Ok(spline)
+    }
+
+    /// Constructs a new `NaturalCubicSpline` from points in any order, sorting them by x-coordinate and
+    /// merging the points sharing one with `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(i)` if the `i`-th point has a NaN or infinite
+    /// coordinate, and the errors of [`Self::try_new`] for the merged points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::dedup::DedupPolicy;
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let points = [(1.0, 0.0), (0.0, 1.0), (0.5, 0.0), (0.5, 1.0)];
+    /// let spline = NaturalCubicSpline::try_new_unsorted(&points, DedupPolicy::Average).unwrap();
+    /// assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+    /// ```
+    pub fn try_new_unsorted(
+        raw_points: &[(V, V)],
+        policy: DedupPolicy,
+    ) -> Result<Self, InterpolationError<V>> {
+        Self::try_new(&policy.sort_and_apply(raw_points)?)
+    }
+
+    /// Sets the tolerance within which a query is treated as hitting a knot exactly,
+    /// returning the stored value instead of evaluating a segment.
+    ///
+    /// Queries within the tolerance of the first or last knot are accepted even if they lie
+    /// slightly outside the domain. The default is [`Tolerance::Exact`].
+    pub fn with_tolerance(mut self, tolerance: Tolerance<V>) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Attaches provenance metadata to the spline.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the provenance metadata of the spline.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the provenance metadata of the spline for editing.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Returns the index of the segment containing `x`; see
+    /// [`PiecewisePolynomial::segment_index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` outside the knots, and
+    /// `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn segment_index(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        segment_index(&self.xs, x)
+    }
+
+    /// Returns the knots `(start, end)` of the `i`-th segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than the number of segments, one less than the number of knots.
+    pub fn segment_bounds(&self, i: usize) -> (V, V) {
+        (self.xs[i], self.xs[i + 1])
+    }
+
+    /// Returns the index of the knot nearest to `x`; see [`PiecewisePolynomial::nearest_knot`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn nearest_knot(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        nearest_knot(&self.xs, x)
+    }
+
+    /// Returns the highest degree of polynomials this spline reproduces exactly, which is 1:
+    /// the natural end conditions force zero curvature at the ends, which quadratics lack.
+    pub fn degree_exactness(&self) -> usize {
+        1
+    }
+
+    /// Inserts the point `(x, y)` at its place in the knot order.
+    ///
+    /// Every second derivative depends on every point, so the tridiagonal system is solved
+    /// again; this is `O(n)` like the insertion itself.
+    ///
+    /// # Returns
+    ///
+    /// The index of the new knot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::DuplicatePointError(x)` if there already is a knot at `x`, and
+    /// `InterpolationError::NonFiniteError(n)` if a coordinate of the point, which would be the
+    /// `n`-th, is NaN or infinite. The spline is then unchanged.
+    pub fn insert_point(&mut self, x: V, y: V) -> Result<usize, InterpolationError<V>> {
+        let i = insertion_index(&self.xs, [x, y])?;
+        self.xs.insert(i, x);
+        self.ys.insert(i, y);
+        if let Err(error) = self.refit() {
+            self.xs.remove(i);
+            self.ys.remove(i);
+            return Err(error.into());
+        }
+        self.spacing = None;
+        Ok(i)
+    }
+
+    /// Removes the `index`-th knot and returns it as `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if fewer than 2 points would remain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_point(&mut self, index: usize) -> Result<(V, V), InterpolationError<V>> {
+        check_removal(self.xs.len(), index, 2)?;
+        let (x, y) = (self.xs.remove(index), self.ys.remove(index));
+        if let Err(error) = self.refit() {
+            self.xs.insert(index, x);
+            self.ys.insert(index, y);
+            return Err(error.into());
+        }
+        self.spacing = None;
+        Ok((x, y))
+    }
+
+    /// Replaces the y-coordinate of the `index`-th knot.
+    ///
+    /// The knots are unchanged, so the stored factorization of the spline system is reused and
+    /// only a forward and back substitution is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteError(index)` if `y` is NaN or infinite; the spline is then unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let mut spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.0), (1.0, 0.0)]).unwrap();
+    /// spline.set_y(1, 0.5).unwrap();
+    /// assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+    /// ```
+    pub fn set_y(&mut self, index: usize, y: V) -> Result<(), InterpolationError<V>> {
+        assert!(
+            index < self.ys.len(),
+            "index {index} should be < len {}",
+            self.ys.len()
+        );
+        if !y.is_finite() {
+            return Err(InterpolationError::NonFiniteError(index));
+        }
+        self.ys[index] = y;
+        self.resolve();
+        Ok(())
+    }
+
+    /// Replaces the y-coordinates of all knots, reusing the stored factorization as [`Self::set_y`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatchError(expected, actual)` if `ys` does not have one value per knot,
+    /// and `NonFiniteError(i)` if the `i`-th value is NaN or infinite; the spline is then
+    /// unchanged.
+    pub fn set_ys(&mut self, ys: &[V]) -> Result<(), InterpolationError<V>> {
+        if ys.len() != self.ys.len() {
+            return Err(InterpolationError::LengthMismatchError(
+                self.ys.len(),
+                ys.len(),
             ));
         }
-        let mut du = Vec::with_capacity(raw_points.len() - 1);
-        let mut d = Vec::with_capacity(raw_points.len());
-        let mut dl = Vec::with_capacity(raw_points.len() - 1);
-        for i in 0..raw_points.len() {
-            if i == 0 {
-                du.push(V::zero());
-                d.push(V::one());
-            } else if i + 1 == raw_points.len() {
-                d.push(V::one());
-                dl.push(V::zero());
-            } else {
-                let h = raw_points[i].0 - raw_points[i - 1].0;
-                let h_next = raw_points[i + 1].0 - raw_points[i].0;
-                du.push(h_next / V::from_i8(6).unwrap());
-                d.push((h + h_next) / V::from_i8(3).unwrap());
-                dl.push(h / V::from_i8(6).unwrap());
-            }
+        check_finite(ys.iter().map(|&y| [y]))?;
+        self.ys.copy_from_slice(ys);
+        self.resolve();
+        Ok(())
+    }
+
+    /// Evaluates the spline at `new_xs` and fits a new natural cubic spline through those values,
+    /// e.g. to move a dense curve onto a coarse risk grid. The tolerance and metadata carry over.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` if a new knot lies outside the
+    /// knots, and otherwise the errors of [`Self::try_new`] for the new knots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let dense = NaturalCubicSpline::try_from_iter((0..=100).map(|i| {
+    ///     let x = i as f64 / 10.0;
+    ///     (x, x.sin())
+    /// }))
+    /// .unwrap();
+    /// let coarse = dense.resample(&[0.0, 2.5, 5.0, 7.5, 10.0]).unwrap();
+    /// assert_eq!(coarse.try_value(2.5).unwrap(), dense.try_value(2.5).unwrap());
+    /// ```
+    pub fn resample(&self, new_xs: &[V]) -> Result<Self, InterpolationError<V>> {
+        let ys = new_xs
+            .iter()
+            .map(|&x| self.try_value(x))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::try_from_columns(new_xs.to_vec(), ys)?
+            .with_tolerance(self.tolerance)
+            .with_metadata(self.metadata.clone()))
+    }
+
+    /// Resamples the spline as [`Self::resample`] onto `n` equally spaced knots spanning the same
+    /// domain, which then get the constant-time lookup of [`Self::try_new_uniform`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::InsufficientPointsError(n)` if `n < 2`.
+    pub fn resample_uniform(&self, n: usize) -> Result<Self, InterpolationError<V>> {
+        if n < 2 {
+            return Err(InterpolationError::InsufficientPointsError(n));
         }
+        let (x0, last) = (self.xs[0], self.xs[self.xs.len() - 1]);
+        let dx = (last - x0) / V::from_count(n - 1);
+        // the rounding of the last generated knot must not push it out of the domain
+        let ys = uniform_knots(x0, dx)
+            .take(n)
+            .map(|x| self.try_value(if x > last { last } else { x }))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::try_new_uniform(x0, dx, &ys)?
+            .with_tolerance(self.tolerance)
+            .with_metadata(self.metadata.clone()))
+    }
 
-        let mut b = Vec::with_capacity(raw_points.len());
-        for i in 0..raw_points.len() {
-            if i == 0 || i + 1 == raw_points.len() {
-                b.push(V::zero());
-            } else {
-                b.push(
-                    (raw_points[i + 1].1 - raw_points[i].1)
-                        / (raw_points[i + 1].0 - raw_points[i].0)
-                        - (raw_points[i].1 - raw_points[i - 1].1)
-                            / (raw_points[i].0 - raw_points[i - 1].0),
-                )
+    /// Drops knots while the simplified spline stays within `tol` of this one, e.g. to store dense
+    /// telemetry compactly.
+    ///
+    /// The knots are reinserted Douglas–Peucker style: starting from the first, middle and last
+    /// knot, every stretch between kept knots where the simplified spline deviates by more than
+    /// `tol` gets back the knot next to its largest deviation, until the deviation is at most `tol`
+    /// everywhere. Both splines are cubic between consecutive knots of this one, so the deviation
+    /// is maximized exactly on each of those segments, at an end or a root of its derivative.
+    ///
+    /// Each round refits the kept knots and measures the deviation once, in `O(n)`, and splits all
+    /// the stretches out of tolerance at once, so the number of rounds follows the depth of the
+    /// subdivision rather than the number of knots kept. The tolerance and metadata carry over.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let line = NaturalCubicSpline::try_from_iter((0..=100).map(|i| (i as f64, 2.0 * i as f64)))
+    ///     .unwrap();
+    /// let simplified = line.simplify(1e-9);
+    /// assert_eq!(simplified.try_value(37.0).unwrap(), 74.0);
+    /// ```
+    pub fn simplify(&self, tol: V) -> Self {
+        let n = self.xs.len();
+        let mut kept = vec![false; n];
+        for i in [0, n / 2, n - 1] {
+            kept[i] = true;
+        }
+        loop {
+            let indices: Vec<usize> = (0..n).filter(|&i| kept[i]).collect();
+            let simplified = Self::try_from_columns(
+                indices.iter().map(|&i| self.xs[i]).collect(),
+                indices.iter().map(|&i| self.ys[i]).collect(),
+            )
+            .expect("a subset of valid knots is valid");
+            let mut added = false;
+            for (k, stretch) in indices.windows(2).enumerate() {
+                // the largest deviation on the stretch, and the knot next to it
+                let (worst, knot) = (stretch[0]..stretch[1])
+                    .map(|j| {
+                        let (deviation, t) = self.max_deviation(&simplified, k, j);
+                        let near = if t + t < self.xs[j + 1] - self.xs[j] {
+                            j
+                        } else {
+                            j + 1
+                        };
+                        (deviation, near)
+                    })
+                    .fold((V::zero(), stretch[0]), |worst, candidate| {
+                        if candidate.0 > worst.0 {
+                            candidate
+                        } else {
+                            worst
+                        }
+                    });
+                if worst <= tol {
+                    continue;
+                }
+                // the nearest knot not kept yet, inside the stretch if it has any
+                let knot = if stretch[1] - stretch[0] > 1 {
+                    knot.clamp(stretch[0] + 1, stretch[1] - 1)
+                } else {
+                    match (0..stretch[0]).rev().find(|&i| !kept[i]) {
+                        Some(i) => i,
+                        None => match (stretch[1] + 1..n).find(|&i| !kept[i]) {
+                            Some(i) => i,
+                            None => continue,
+                        },
+                    }
+                };
+                added |= !kept[knot];
+                kept[knot] = true;
+            }
+            if !added {
+                return simplified
+                    .with_tolerance(self.tolerance)
+                    .with_metadata(self.metadata.clone());
             }
         }
+    }
 
-        let matrix = TridiagonalMatrix::try_new(du, d, dl).unwrap();
-        let derivatives = matrix.solve(&b);
+    /// Returns the largest absolute difference between this spline on its `j`-th segment and
+    /// `other` on its `k`-th, which covers it, with the offset into the segment where it occurs.
+    fn max_deviation(&self, other: &Self, k: usize, j: usize) -> (V, V) {
+        let (x, h) = (self.xs[j], self.xs[j + 1] - self.xs[j]);
+        let (ours, theirs) = (self.taylor(j, x), other.taylor(k, x));
+        let difference: Vec<V> = ours.iter().zip(&theirs).map(|(&a, &b)| a - b).collect();
+        let slope = [
+            V::from_integer(3) * difference[0],
+            V::from_integer(2) * difference[1],
+            difference[2],
+        ];
+        roots_in(&slope, V::zero(), h)
+            .into_iter()
+            .chain([V::zero(), h])
+            .map(|t| (abs(horner(&difference, t)), t))
+            .fold((V::zero(), V::zero()), |worst, candidate| {
+                if candidate.0 > worst.0 {
+                    candidate
+                } else {
+                    worst
+                }
+            })
+    }
 
-        let mut temp = raw_points[0].0;
-        let mut points = Vec::new();
-        for (&(x, y), dydx) in raw_points.iter().zip(derivatives) {
-            let point = Point3 { x, y, dydx };
-            if point.x < temp {
-                return Err(HermiteSplineError::PointOrderError);
-            }
-            temp = point.x;
-            points.push(point);
+    /// Returns the coefficients of the `pos`-th segment in powers of `(t - x)`, highest first.
+    fn taylor(&self, pos: usize, x: V) -> [V; 4] {
+        let h = self.xs[pos + 1] - self.xs[pos];
+        let jerk = (self.second_derivatives[pos + 1] - self.second_derivatives[pos]) / h;
+        let sample = self.sample_in_segment(pos, x);
+        [
+            jerk / V::from_integer(6),
+            sample.d2ydx2 / V::from_integer(2),
+            sample.dydx,
+            sample.value,
+        ]
+    }
+
+    /// Refactorizes the spline system and recomputes the second derivatives after the knots
+    /// changed, leaving the spline untouched if the system cannot be factorized.
+    fn refit(&mut self) -> Result<(), MatrixValidationError> {
+        self.factorization = second_derivative_system(&self.xs)?;
+        self.resolve();
+        Ok(())
+    }
+
+    /// Recomputes the second derivatives with the stored factorization after the values changed.
+    fn resolve(&mut self) {
+        self.second_derivatives = second_derivative_rhs(&self.xs, &self.ys);
+        self.factorization
+            .solve_into_unchecked(&mut self.second_derivatives);
+    }
+
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => Ok(self.ys[pos]),
+            Location::Segment(pos) => Ok(self.value_in_segment(pos, x)),
         }
+    }
 
-        Ok(Self { points })
+    /// Evaluates the spline at `x` without checking it, for hot loops over queries already known
+    /// to lie within the knots.
+    ///
+    /// Unlike [`Self::try_value`], a query at a knot is evaluated with the polynomial of its
+    /// segment, which may round differently from the stored value.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `x` is NaN or outside the knots. Release builds extrapolate the
+    /// end segments instead, and return NaN for a NaN query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)]).unwrap();
+    /// let samples: Vec<f64> = (0..=8).map(|i| spline.value_unchecked(i as f64 / 8.0)).collect();
+    /// assert_eq!(samples[6], 0.25);
+    /// ```
+    pub fn value_unchecked(&self, x: V) -> V {
+        self.value_in_segment(segment_index_unchecked(&self.xs, x), x)
     }
 
-    pub fn try_value(self, x: V) -> Result<V, HermiteSplineError<V>> {
-        match self
-            .points
-            .binary_search_by(|point| point.x.partial_cmp(&x).unwrap())
-        {
-            Ok(pos) => Ok(self.points[pos].y),
-            Err(pos) => {
-                if pos.is_zero() {
-                    return Err(HermiteSplineError::OutOfLowerBound(x));
-                }
-                if pos > self.points.len() {
-                    return Err(HermiteSplineError::OutOfUpperBound(x));
+    /// Evaluates the polynomial of the `pos`-th segment at `x`.
+    fn value_in_segment(&self, pos: usize, x: V) -> V {
+        record_segment_evaluation();
+        let (x0, x1) = (self.xs[pos], self.xs[pos + 1]);
+        let (y0, y1) = (self.ys[pos], self.ys[pos + 1]);
+        let (m0, m1) = (
+            self.second_derivatives[pos],
+            self.second_derivatives[pos + 1],
+        );
+        let h = x1 - x0;
+        let six = V::from_integer(6);
+        (x1 - x) * (x1 - x) * (x1 - x) / six / h * m0
+            + (x - x0) * (x - x0) * (x - x0) / six / h * m1
+            + (x1 - x) * (y0 / h - h / six * m0)
+            + (x - x0) * (y1 / h - h / six * m1)
+    }
+
+    /// Returns the derivative of the value at `x` with respect to each of the y-coordinates, e.g.
+    /// for analytic bucketed deltas.
+    ///
+    /// The value is linear in the y-coordinates with the knots fixed, so these are also the weights
+    /// of the y-coordinates in the value. They are found with one solve of the adjoint of the
+    /// stored spline system, which is as dense as the spline itself: every y-coordinate moves the
+    /// value anywhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    ///
+    /// let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (1.0, 3.0), (2.0, 2.0)]).unwrap();
+    /// let gradient = spline.value_gradient(0.5).unwrap();
+    /// let value: f64 = gradient.iter().zip([1.0, 3.0, 2.0]).map(|(w, y)| w * y).sum();
+    /// assert!((value - spline.try_value(0.5).unwrap()).abs() < 1e-15);
+    /// assert_eq!(spline.value_gradient(1.0).unwrap(), vec![0.0, 1.0, 0.0]);
+    /// ```
+    pub fn value_gradient(&self, x: V) -> Result<Vec<V>, InterpolationError<V>> {
+        let n = self.xs.len();
+        let mut gradient = vec![V::zero(); n];
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => gradient[pos] = V::one(),
+            Location::Segment(pos) => {
+                let (x0, x1) = (self.xs[pos], self.xs[pos + 1]);
+                let h = x1 - x0;
+                let six = V::from_integer(6);
+                let (a, b) = (x1 - x, x - x0);
+                gradient[pos] = a / h;
+                gradient[pos + 1] = b / h;
+                // the weights of the second derivatives in the value, which vanish at the ends
+                let mut weights = vec![V::zero(); n];
+                weights[pos] = a * a * a / six / h - a * h / six;
+                weights[pos + 1] = b * b * b / six / h - b * h / six;
+                weights[0] = V::zero();
+                weights[n - 1] = V::zero();
+                // the interior of the spline system is symmetric and decoupled from the end rows,
+                // so its adjoint solve is a plain solve
+                let adjoint = self.factorization.solve_unchecked(&weights);
+                for i in 1..n - 1 {
+                    let (h0, h1) = (self.xs[i] - self.xs[i - 1], self.xs[i + 1] - self.xs[i]);
+                    gradient[i - 1] += adjoint[i] / h0;
+                    gradient[i] -= adjoint[i] / h0 + adjoint[i] / h1;
+                    gradient[i + 1] += adjoint[i] / h1;
                 }
-                let pos = pos - 1;
-                let point = &self.points[pos];
-                let next_point = &self.points[pos + 1];
-                let h = next_point.x - point.x;
-                let six = V::from_i8(6).unwrap();
-                Ok(
-                    (next_point.x - x) * (next_point.x - x) * (next_point.x - x) / six / h
-                        * point.dydx
-                        + (x - point.x) * (x - point.x) * (x - point.x) / six / h * next_point.dydx
-                        + (next_point.x - x) * (point.y / h - h / six * point.dydx)
-                        + (x - point.x) * (next_point.y / h - h / six * next_point.dydx),
+            }
+        }
+        Ok(gradient)
+    }
+
+    /// Evaluates the spline and its first and second derivatives at `x` with a single segment
+    /// lookup.
+    ///
+    /// At a knot the derivatives are those of the segment starting there, or ending there for the
+    /// last knot.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_eval_all(&self, x: V) -> Result<SplineSample<V>, InterpolationError<V>> {
+        match locate_uniform(&self.xs, |&x| x, self.spacing, x, &self.tolerance)? {
+            Location::Knot(pos) => Ok(SplineSample {
+                value: self.ys[pos],
+                ..self.sample_in_segment(pos.min(self.xs.len() - 2), self.xs[pos])
+            }),
+            Location::Segment(pos) => Ok(self.sample_in_segment(pos, x)),
+        }
+    }
+
+    /// Returns the cubic Bézier curve of each segment, e.g. to draw the spline with a vector
+    /// renderer.
+    pub fn to_bezier_segments(&self) -> Vec<CubicBezier<V>> {
+        (0..self.xs.len() - 1)
+            .map(|pos| {
+                let (x0, x1) = (self.xs[pos], self.xs[pos + 1]);
+                CubicBezier::from_hermite(
+                    (x0, self.ys[pos]),
+                    self.sample_in_segment(pos, x0).dydx,
+                    (x1, self.ys[pos + 1]),
+                    self.sample_in_segment(pos, x1).dydx,
                 )
+            })
+            .collect()
+    }
+
+    /// Returns the jumps of the spline and its first two derivatives at the interior knots, where
+    /// the spline is C2, so every jump is a rounding error.
+    pub fn continuity_report(&self) -> ContinuityReport<V> {
+        ContinuityReport::from_segment_ends(
+            &self.xs,
+            self.xs.windows(2).enumerate().map(|(pos, w)| {
+                (
+                    self.sample_in_segment(pos, w[0]),
+                    self.sample_in_segment(pos, w[1]),
+                )
+            }),
+        )
+    }
+
+    /// Returns `n` points `(x, y)` of the graph of the spline equally spaced by arc length, the
+    /// first and last knots included, at least 2; see [`PiecewisePolynomial::sample_evenly`].
+    pub fn sample_evenly(&self, n: usize) -> Vec<(V, V)>
+    where
+        V: TranscendentalValue,
+    {
+        PiecewisePolynomial::from(self)
+            .evenly_spaced_xs(n)
+            .into_iter()
+            .map(|x| {
+                (
+                    x,
+                    self.try_value(x).expect("the samples lie inside the knots"),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns every `x` at which the spline crosses the level `y`, in increasing order; see
+    /// [`PiecewisePolynomial::intersect_level`].
+    pub fn intersect_level(&self, y: V) -> Vec<V> {
+        PiecewisePolynomial::from(self).intersect_level(y)
+    }
+
+    /// Returns every `x` at which the spline crosses the line `a + b x`, in increasing order; see
+    /// [`PiecewisePolynomial::intersect_line`].
+    pub fn intersect_line(&self, a: V, b: V) -> Vec<V> {
+        PiecewisePolynomial::from(self).intersect_line(a, b)
+    }
+
+    /// Returns the lowest point `(x, y)` of the spline over `range`; see
+    /// [`PiecewisePolynomial::argmin`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the knots.
+    pub fn argmin(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        let (x, _) = PiecewisePolynomial::from(self).argmin(range)?;
+        Ok((x, self.try_value(x)?))
+    }
+
+    /// Returns the highest point `(x, y)` of the spline over `range`; see
+    /// [`PiecewisePolynomial::argmax`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the knots.
+    pub fn argmax(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        let (x, _) = PiecewisePolynomial::from(self).argmax(range)?;
+        Ok((x, self.try_value(x)?))
+    }
+
+    /// Returns the largest absolute and relative differences to the reference `other`, e.g. a
+    /// spline of another method through the same points, over the common domain of both; see
+    /// [`PiecewisePolynomial::max_difference`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`PiecewisePolynomial::max_difference`].
+    pub fn max_difference(
+        &self,
+        other: impl Into<PiecewisePolynomial<V>>,
+    ) -> Result<MaxDifference<V>, InterpolationError<V>> {
+        PiecewisePolynomial::from(self).max_difference(&other.into())
+    }
+
+    /// Returns the spline as a LaTeX `cases` environment of its polynomial on each segment, see
+    /// [`PiecewisePolynomial::to_latex`].
+    pub fn to_latex(&self) -> String
+    where
+        V: Display,
+    {
+        PiecewisePolynomial::from(self).to_latex()
+    }
+
+    /// Returns the SVG path data, the `d` attribute, of the graph of the spline.
+    #[cfg(feature = "svg")]
+    pub fn to_svg_path_data(&self, sampling: PathSampling) -> String
+    where
+        V: Display,
+    {
+        match sampling {
+            PathSampling::Exact => svg::bezier_path_data(&self.to_bezier_segments()),
+            PathSampling::Polyline(points) => {
+                svg::polyline_path_data((self.xs[0], self.xs[self.xs.len() - 1]), points, |x| {
+                    self.try_value(x).expect("the samples lie inside the knots")
+                })
             }
         }
     }
+
+    fn sample_in_segment(&self, pos: usize, x: V) -> SplineSample<V> {
+        record_segment_evaluation();
+        let (x0, x1) = (self.xs[pos], self.xs[pos + 1]);
+        let (y0, y1) = (self.ys[pos], self.ys[pos + 1]);
+        let (m0, m1) = (
+            self.second_derivatives[pos],
+            self.second_derivatives[pos + 1],
+        );
+        let h = x1 - x0;
+        let (two, six) = (V::from_integer(2), V::from_integer(6));
+        let (a, b) = (x1 - x, x - x0);
+        SplineSample {
+            value: a * a * a / six / h * m0
+                + b * b * b / six / h * m1
+                + a * (y0 / h - h / six * m0)
+                + b * (y1 / h - h / six * m1),
+            dydx: (b * b * m1 - a * a * m0) / (two * h) + (y1 - y0) / h - h / six * (m1 - m0),
+            d2ydx2: (a * m0 + b * m1) / h,
+        }
+    }
+
+    /// Estimates the interpolation error on each segment as `5 h⁴ |f⁗| / 384`, the classical bound
+    /// for cubic splines, with `f⁗` estimated from fourth divided differences of the data.
+    ///
+    /// The natural end conditions add an `O(h²)` error near the ends unless `f''` vanishes there,
+    /// which this estimate does not account for.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if there are fewer than 5 points.
+    pub fn error_bound_estimate(&self) -> Result<Vec<V>, InterpolationError<V>> {
+        let (xs, ys) = (&self.xs, &self.ys);
+        let constant = V::from_integer(5) / V::from_integer(384);
+        Ok(derivative_estimates(xs, ys, 4)?
+            .into_iter()
+            .zip(xs.windows(2))
+            .map(|(d4, w)| constant * powi(w[1] - w[0], 4) * d4)
+            .collect())
+    }
+}
+
+/// Factorizes the linear system for the second derivatives of a natural cubic spline with the
+/// knots `xs`; the system matrix depends on the knots only, so it serves every set of values.
+///
+/// Needs at least 2 knots.
+pub(crate) fn second_derivative_system<V: InterpolationValue>(
+    xs: &[V],
+) -> Result<TridiagonalLU<V>, MatrixValidationError> {
+    let n = xs.len();
+    let mut du = Vec::with_capacity(n - 1);
+    let mut d = Vec::with_capacity(n);
+    let mut dl = Vec::with_capacity(n - 1);
+    for i in 0..n {
+        if i == 0 {
+            du.push(V::zero());
+            d.push(V::one());
+        } else if i + 1 == n {
+            d.push(V::one());
+            dl.push(V::zero());
+        } else {
+            let h = xs[i] - xs[i - 1];
+            let h_next = xs[i + 1] - xs[i];
+            du.push(h_next / V::from_integer(6));
+            d.push((h + h_next) / V::from_integer(3));
+            dl.push(h / V::from_integer(6));
+        }
+    }
+    TridiagonalMatrix::try_new(du, d, dl)?.factorize()
+}
+
+/// Builds the right-hand side of the linear system for the second derivatives at the knots `xs`
+/// of the natural cubic spline through `ys`.
+pub(crate) fn second_derivative_rhs<V: InterpolationValue>(xs: &[V], ys: &[V]) -> Vec<V> {
+    let n = xs.len();
+    let mut b = Vec::with_capacity(n);
+    b.push(V::zero());
+    for i in 1..n - 1 {
+        b.push(
+            (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]) - (ys[i] - ys[i - 1]) / (xs[i] - xs[i - 1]),
+        );
+    }
+    b.push(V::zero());
+    b
+}
+
+impl<V: InterpolationValue> From<&NaturalCubicSpline<V>> for PiecewisePolynomial<V> {
+    fn from(spline: &NaturalCubicSpline<V>) -> Self {
+        let six = V::from_integer(6);
+        let (xs, ys, ms) = (&spline.xs, &spline.ys, &spline.second_derivatives);
+        let mut coefficients = Vec::with_capacity((xs.len() - 1) * 4);
+        for i in 0..xs.len() - 1 {
+            let h = xs[i + 1] - xs[i];
+            coefficients.extend([
+                (ms[i + 1] - ms[i]) / (six * h),
+                ms[i] / V::from_integer(2),
+                (ys[i + 1] - ys[i]) / h - h * (V::from_integer(2) * ms[i] + ms[i + 1]) / six,
+                ys[i],
+            ]);
+        }
+        PiecewisePolynomial::new_unchecked(xs.clone(), coefficients, 4)
+            .with_metadata(spline.metadata.clone())
+    }
+}
+
+impl_interpolator!(NaturalCubicSpline, NaturalCubic, metadata);
+
+impl<V: InterpolationValue> Debug for NaturalCubicSpline<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NaturalCubicSpline")
+            .field("xs", &self.xs)
+            .field("ys", &self.ys)
+            .field("second_derivatives", &self.second_derivatives)
+            .field("tolerance", &self.tolerance)
+            .field("metadata", &self.metadata)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Writes the polynomial on each segment, see the [`Display`] form of [`PiecewisePolynomial`].
+impl<V: InterpolationValue + Display> Display for NaturalCubicSpline<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&PiecewisePolynomial::from(self), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V: InterpolationValue + serde::Serialize> serde::Serialize for NaturalCubicSpline<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SplineDocument {
+            points: self
+                .xs
+                .iter()
+                .copied()
+                .zip(self.ys.iter().copied())
+                .collect(),
+            tolerance: self.tolerance,
+            metadata: self.metadata.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Fits the spline again through the deserialized points, failing as [`NaturalCubicSpline::try_new`].
+#[cfg(feature = "serde")]
+impl<'de, V: InterpolationValue + Display + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for NaturalCubicSpline<V>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = SplineDocument::<(V, V), V>::deserialize(deserializer)?;
+        Ok(Self::try_new(&document.points)
+            .map_err(serde::de::Error::custom)?
+            .with_tolerance(document.tolerance)
+            .with_metadata(document.metadata))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::metadata::Metadata;
+    use crate::tolerance::Tolerance;
+    use crate::InterpolationError;
     #[cfg(feature = "decimal")]
     use rust_decimal::Decimal;
 
@@ -113,6 +944,218 @@ mod tests {
         assert_eq!(val, 0.25_f64);
     }
 
+    #[test]
+    fn test_out_of_bounds() {
+        let points = [(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)];
+        let interpolator = NaturalCubicSpline::try_new(&points).unwrap();
+        assert!(matches!(
+            interpolator.try_value(-0.1),
+            Err(InterpolationError::OutOfLowerBound(_))
+        ));
+        assert!(matches!(
+            interpolator.try_value(1.1),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        for (points, expected) in [
+            (
+                [(0.0, 1.0), (1.0, f64::NAN), (2.0, 0.0)],
+                "point 1 has a non-finite coordinate",
+            ),
+            (
+                [(0.0, 1.0), (1.0, 0.0), (1.0, 0.0)],
+                "duplicate x-coordinate: 1",
+            ),
+            (
+                [(0.0, 1.0), (2.0, 0.0), (1.0, 0.0)],
+                "point 2 is out of order",
+            ),
+        ] {
+            let error = NaturalCubicSpline::try_new(&points).err().unwrap();
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_knot_tolerance() {
+        let points = [(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)];
+        let interpolator = NaturalCubicSpline::try_new(&points)
+            .unwrap()
+            .with_tolerance(Tolerance::Absolute(1e-9));
+        assert_eq!(interpolator.try_value(0.5 + 1e-12).unwrap(), 0.5);
+        assert_eq!(interpolator.try_value(1.0 + 1e-12).unwrap(), 0.0);
+        assert!(matches!(
+            interpolator.try_value(1.0 + 1e-6),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+    }
+
+    #[test]
+    fn test_error_bound_estimate() {
+        let points: Vec<(f64, f64)> = (0..=8)
+            .map(|i| {
+                let x = std::f64::consts::PI * i as f64 / 8.0;
+                (x, x.sin())
+            })
+            .collect();
+        let interpolator = NaturalCubicSpline::try_new(&points).unwrap();
+        let bounds = interpolator.error_bound_estimate().unwrap();
+        assert_eq!(bounds.len(), 8);
+        for (w, bound) in points.windows(2).zip(bounds) {
+            let error = (1..10)
+                .map(|k| w[0].0 + (w[1].0 - w[0].0) * k as f64 / 10.0)
+                .map(|x| (interpolator.try_value(x).unwrap() - x.sin()).abs())
+                .fold(0.0, f64::max);
+            assert!(error <= bound);
+        }
+        assert!(matches!(
+            NaturalCubicSpline::try_new(&points[..4])
+                .unwrap()
+                .error_bound_estimate(),
+            Err(InterpolationError::InsufficientPointsError(4))
+        ));
+    }
+
+    #[test]
+    fn test_insert_and_remove_point() {
+        let points = [(0.0, 1.0), (0.5, 0.5), (2.0, 3.0), (3.0, -1.0)];
+        let mut spline = NaturalCubicSpline::try_new(&points).unwrap();
+        assert_eq!(spline.insert_point(1.2, 2.0).unwrap(), 2);
+        let mut inserted = points.to_vec();
+        inserted.insert(2, (1.2, 2.0));
+        let rebuilt = NaturalCubicSpline::try_new(&inserted).unwrap();
+        for x in [0.1, 0.7, 1.2, 1.9, 2.5] {
+            assert_eq!(spline.try_value(x).unwrap(), rebuilt.try_value(x).unwrap());
+        }
+        assert!(matches!(
+            spline.insert_point(2.0, 0.0),
+            Err(InterpolationError::DuplicatePointError(_))
+        ));
+        assert_eq!(spline.remove_point(2).unwrap(), (1.2, 2.0));
+        let original = NaturalCubicSpline::try_new(&points).unwrap();
+        assert_eq!(
+            spline.try_value(1.5).unwrap(),
+            original.try_value(1.5).unwrap()
+        );
+        spline.remove_point(0).unwrap();
+        spline.remove_point(0).unwrap();
+        assert_eq!(spline.try_value(2.5).unwrap(), 1.0);
+        assert!(matches!(
+            spline.remove_point(0),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+    }
+
+    #[test]
+    fn test_insert_non_finite_point() {
+        let points = [(0.0, 1.0), (0.5, 0.5), (2.0, 3.0)];
+        let mut spline = NaturalCubicSpline::try_new(&points).unwrap();
+        for (x, y) in [
+            (f64::NAN, 1.0),
+            (f64::INFINITY, 1.0),
+            (f64::NEG_INFINITY, 1.0),
+            (1.0, f64::NAN),
+            (1.0, f64::INFINITY),
+        ] {
+            assert!(matches!(
+                spline.insert_point(x, y),
+                Err(InterpolationError::NonFiniteError(3))
+            ));
+        }
+        let original = NaturalCubicSpline::try_new(&points).unwrap();
+        assert_eq!(spline.xs, original.xs);
+        for x in [0.0, 0.3, 1.2, 2.0] {
+            assert_eq!(spline.try_value(x).unwrap(), original.try_value(x).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_resample() {
+        let dense = NaturalCubicSpline::try_from_iter((0..=60).map(|i| {
+            let x = i as f64 * 0.05;
+            (x, (2.0 * x).cos())
+        }))
+        .unwrap()
+        .with_metadata(Metadata::new().with_name("curve"));
+        let new_xs = [0.0, 0.4, 1.1, 2.0, 3.0];
+        let coarse = dense.resample(&new_xs).unwrap();
+        assert_eq!(coarse.metadata().name(), Some("curve"));
+        for x in new_xs {
+            assert_eq!(coarse.try_value(x).unwrap(), dense.try_value(x).unwrap());
+        }
+        let uniform = dense.resample_uniform(7).unwrap();
+        for i in 0..7 {
+            let x = i as f64 * 0.5;
+            assert!((uniform.try_value(x).unwrap() - dense.try_value(x).unwrap()).abs() < 1e-12);
+        }
+        assert!(matches!(
+            dense.resample(&[0.0, 1.0, 3.5]),
+            Err(InterpolationError::OutOfUpperBound(3.5))
+        ));
+        assert!(matches!(
+            dense.resample(&[0.0, 2.0, 1.0]),
+            Err(InterpolationError::PointOrderError(2))
+        ));
+        assert!(matches!(
+            dense.resample_uniform(1),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+    }
+
+    #[test]
+    fn test_simplify() {
+        let dense = NaturalCubicSpline::try_from_iter((0..=200).map(|i| {
+            let x = i as f64 * 0.05;
+            (x, x.sin() + 0.3 * (3.0 * x).cos())
+        }))
+        .unwrap()
+        .with_metadata(Metadata::new().with_name("telemetry"));
+        let tol = 1e-4;
+        let simplified = dense.simplify(tol);
+        assert_eq!(simplified.metadata().name(), Some("telemetry"));
+        let knots = PiecewisePolynomial::from(&simplified).segment_count() + 1;
+        assert!(knots < 100, "{knots} knots kept");
+        for x in (0..=400).map(|i| i as f64 * 0.025) {
+            let deviation = (simplified.try_value(x).unwrap() - dense.try_value(x).unwrap()).abs();
+            assert!(deviation <= tol, "deviation {deviation} at {x}");
+        }
+        // a zero tolerance keeps everything but the knots the spline reproduces exactly
+        let exact = dense.simplify(0.0);
+        assert_eq!(exact.try_value(3.0).unwrap(), dense.try_value(3.0).unwrap());
+    }
+
+    #[test]
+    fn test_set_ys() {
+        let points = [(0.0_f64, 1.0), (0.5, 0.5), (2.0, 3.0), (3.0, -1.0)];
+        let mut spline = NaturalCubicSpline::try_new(&points).unwrap();
+        spline.set_y(2, 2.0).unwrap();
+        let rebuilt =
+            NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (2.0, 2.0), (3.0, -1.0)])
+                .unwrap();
+        for x in [0.1, 0.7, 1.2, 2.5] {
+            assert!((spline.try_value(x).unwrap() - rebuilt.try_value(x).unwrap()).abs() < 1e-15);
+        }
+        spline.set_ys(&[0.0, 1.0, 4.0, 6.0]).unwrap();
+        assert!((spline.try_value(1.2).unwrap() - 2.4).abs() < 1e-15);
+        assert!(matches!(
+            spline.set_ys(&[0.0; 3]),
+            Err(InterpolationError::LengthMismatchError(4, 3))
+        ));
+        assert!(matches!(
+            spline.set_y(1, f64::NAN),
+            Err(InterpolationError::NonFiniteError(1))
+        ));
+        assert!(matches!(
+            spline.set_ys(&[0.0, 1.0, f64::INFINITY, 6.0]),
+            Err(InterpolationError::NonFiniteError(2))
+        ));
+        assert_eq!(spline.ys, [0.0, 1.0, 4.0, 6.0]);
+        assert!((spline.try_value(1.2).unwrap() - 2.4).abs() < 1e-15);
+    }
+
     #[cfg(feature = "decimal")]
     #[test]
     fn test_decimal() {
@@ -125,4 +1168,28 @@ mod tests {
         let val = interpolator.try_value(Decimal::new(75, 2)).unwrap();
         assert_eq!(val, Decimal::from_str_exact("0.25").unwrap());
     }
+
+    #[test]
+    fn test_sample_evenly() {
+        // steep, with a sharp bend at the maximum near x = 1
+        let points = [(0.0_f64, 0.0), (1.0, 3.0), (1.5, -1.0), (4.0, 0.5)];
+        let spline = NaturalCubicSpline::try_new(&points).unwrap();
+        let samples = spline.sample_evenly(9);
+        assert_eq!((samples[0], samples[8]), (points[0], points[3]));
+        // the lengths of fine polylines along the graph
+        let length_to = |end: f64| -> f64 {
+            let point = |i: usize| {
+                let x = i as f64 * end / 20_000.0;
+                (x, spline.try_value(x).unwrap())
+            };
+            (0..20_000)
+                .map(|i| (point(i + 1).0 - point(i).0).hypot(point(i + 1).1 - point(i).1))
+                .sum()
+        };
+        let length = PiecewisePolynomial::from(&spline).arc_length();
+        assert!((length_to(4.0) - length).abs() < 1e-6);
+        for (k, &(x, _)) in samples.iter().enumerate() {
+            assert!((length_to(x) - length * k as f64 / 8.0).abs() < 1e-6);
+        }
+    }
 }
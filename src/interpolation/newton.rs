@@ -0,0 +1,207 @@
+//! The interpolating polynomial through a few points, in Newton form.
+
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::metadata::Metadata;
+use crate::{InterpolationError, InterpolationValue};
+
+/// The polynomial of degree `n - 1` through `n` points with distinct x-coordinates, kept in the
+/// Newton form `c_0 + c_1 (x - x_0) + ... + c_{n-1} (x - x_0) ... (x - x_{n-2})` with the divided
+/// differences `c_k = f[x_0, ..., x_k]`.
+///
+/// This is the exact polynomial of the Lagrange formula, for a handful of points: with many
+/// points, or equally spaced ones, it oscillates wildly between them (Runge's phenomenon), where a
+/// spline stays tame. The points need not be ordered, and [`Self::push`] adds one in `O(n)`
+/// without recomputing the others.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::newton::NewtonPolynomial;
+///
+/// // x^3 - 2x through 4 points
+/// let mut p = NewtonPolynomial::try_new(&[(0.0, 0.0), (2.0, 4.0), (1.0, -1.0)]).unwrap();
+/// p.push(-1.0, 1.0).unwrap();
+/// assert_eq!(p.degree(), 3);
+/// assert_eq!(p.try_value(0.5).unwrap(), -0.875);
+/// ```
+pub struct NewtonPolynomial<V: InterpolationValue> {
+    xs: Vec<V>,
+    // the divided differences f[x_0, ..., x_k]
+    coefficients: Vec<V>,
+    // the divided differences f[x_{n-1-k}, ..., x_{n-1}] ending at the last point, to add the next
+    last_row: Vec<V>,
+    // the smallest and largest x-coordinates
+    bounds: (V, V),
+    metadata: Metadata,
+}
+
+impl<V: InterpolationValue> NewtonPolynomial<V> {
+    /// Constructs the interpolating polynomial through the points `(x, y)`, in any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(0)` if no points are given, and the errors of
+    /// [`Self::push`] for the first invalid point.
+    pub fn try_new(points: &[(V, V)]) -> Result<Self, InterpolationError<V>> {
+        let (&(x, y), rest) = points
+            .split_first()
+            .ok_or(InterpolationError::InsufficientPointsError(0))?;
+        if !(x.is_finite() && y.is_finite()) {
+            return Err(InterpolationError::NonFiniteError(0));
+        }
+        let mut polynomial = Self {
+            xs: vec![x],
+            coefficients: vec![y],
+            last_row: vec![y],
+            bounds: (x, x),
+            metadata: Metadata::default(),
+        };
+        for &(x, y) in rest {
+            polynomial.push(x, y)?;
+        }
+        Ok(polynomial)
+    }
+
+    /// Adds the point `(x, y)`, raising the degree by one while keeping the polynomial through
+    /// the previous points, in `O(n)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteError(n)` if the point, the `n`-th, has a NaN or infinite coordinate, and
+    /// `DuplicatePointError(x)` if a point at `x` exists already. The polynomial is then unchanged.
+    pub fn push(&mut self, x: V, y: V) -> Result<(), InterpolationError<V>> {
+        let n = self.xs.len();
+        if !(x.is_finite() && y.is_finite()) {
+            return Err(InterpolationError::NonFiniteError(n));
+        }
+        if self.xs.contains(&x) {
+            return Err(InterpolationError::DuplicatePointError(x));
+        }
+        // f[x_{n-k}, ..., x_n] from f[x_{n-k}, ..., x_{n-1}] and f[x_{n-k+1}, ..., x_n]
+        let mut row = Vec::with_capacity(n + 1);
+        row.push(y);
+        for k in 1..=n {
+            row.push((row[k - 1] - self.last_row[k - 1]) / (x - self.xs[n - k]));
+        }
+        self.coefficients.push(row[n]);
+        self.last_row = row;
+        self.xs.push(x);
+        if x < self.bounds.0 {
+            self.bounds.0 = x;
+        } else if x > self.bounds.1 {
+            self.bounds.1 = x;
+        }
+        Ok(())
+    }
+
+    /// Returns the x-coordinates of the points, in the order they were added.
+    pub fn knots(&self) -> &[V] {
+        &self.xs
+    }
+
+    /// Returns the divided differences `f[x_0, ..., x_k]`, the coefficients of the Newton form.
+    pub fn coefficients(&self) -> &[V] {
+        &self.coefficients
+    }
+
+    /// Returns the degree, one less than the number of points.
+    pub fn degree(&self) -> usize {
+        self.xs.len() - 1
+    }
+
+    /// Attaches provenance metadata to the polynomial.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the provenance metadata of the polynomial.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Evaluates the polynomial at `x` by Horner's scheme on the Newton form.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` if `x` is less than the smallest x-coordinate,
+    /// `OutOfUpperBound(x)` if `x` is greater than the largest one, and `NonFiniteInput(x)` if `x`
+    /// is NaN or infinite.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        if !x.is_finite() {
+            return Err(InterpolationError::NonFiniteInput(x));
+        }
+        if x < self.bounds.0 {
+            return Err(InterpolationError::OutOfLowerBound(x));
+        }
+        if x > self.bounds.1 {
+            return Err(InterpolationError::OutOfUpperBound(x));
+        }
+        let n = self.xs.len();
+        Ok((0..n - 1).rev().fold(self.coefficients[n - 1], |acc, k| {
+            acc * (x - self.xs[k]) + self.coefficients[k]
+        }))
+    }
+}
+
+impl_interpolator!(NewtonPolynomial, Newton, metadata);
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::newton::NewtonPolynomial;
+    use crate::math::divided_differences;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_reproduces_polynomials() {
+        let f = |x: f64| 2.0 * x.powi(4) - x.powi(3) + 0.5 * x - 3.0;
+        let points: Vec<_> = [0.3, -1.0, 2.0, 0.0, 1.2]
+            .iter()
+            .map(|&x| (x, f(x)))
+            .collect();
+        let p = NewtonPolynomial::try_new(&points).unwrap();
+        assert_eq!(p.degree(), 4);
+        assert!((p.coefficients()[4] - 2.0).abs() < 1e-12);
+        for k in 0..=30 {
+            let x = -1.0 + k as f64 * 0.1;
+            assert!((p.try_value(x).unwrap() - f(x)).abs() < 1e-12);
+        }
+        assert!(matches!(
+            p.try_value(2.5),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+        assert!(matches!(
+            p.try_value(-1.5),
+            Err(InterpolationError::OutOfLowerBound(_))
+        ));
+        let constant = NewtonPolynomial::try_new(&[(1.0, 3.0)]).unwrap();
+        assert_eq!(constant.degree(), 0);
+        assert_eq!(constant.try_value(1.0).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_push() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (3.0, 2.0), (-2.0, 0.5)];
+        let mut incremental = NewtonPolynomial::try_new(&points[..1]).unwrap();
+        for &(x, y) in &points[1..] {
+            incremental.push(x, y).unwrap();
+        }
+        let (xs, ys): (Vec<f64>, Vec<f64>) = points.iter().copied().unzip();
+        let table = divided_differences(&xs, &ys);
+        assert_eq!(incremental.coefficients(), table);
+        assert_eq!(incremental.knots(), xs);
+        assert!(matches!(
+            incremental.push(1.0, 0.0),
+            Err(InterpolationError::DuplicatePointError(1.0))
+        ));
+        assert!(matches!(
+            incremental.push(2.0, f64::NAN),
+            Err(InterpolationError::NonFiniteError(4))
+        ));
+        assert_eq!(incremental.coefficients(), table);
+        assert!(matches!(
+            NewtonPolynomial::<f64>::try_new(&[]),
+            Err(InterpolationError::InsufficientPointsError(0))
+        ));
+    }
+}
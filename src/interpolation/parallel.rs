@@ -0,0 +1,156 @@
+//! Evaluating and constructing interpolants on the rayon thread pool.
+
+use crate::interpolation::interpolator::Interpolator;
+use crate::{InterpolationError, InterpolationValue};
+use rayon::prelude::*;
+
+/// The number of queries evaluated by one task, large enough to amortize the scheduling.
+const MIN_CHUNK_LEN: usize = 1024;
+
+/// Evaluates `interpolator` at every `xs[i]` in parallel.
+///
+/// # Errors
+///
+/// Returns the error of a query failing [`Interpolator::try_value`]; which one is unspecified if
+/// several fail.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "rayon")]
+/// # {
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::parallel;
+///
+/// let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)]).unwrap();
+/// let xs: Vec<f64> = (0..=10_000).map(|i| i as f64 / 10_000.0).collect();
+/// let values = parallel::try_values(&spline, &xs).unwrap();
+/// assert_eq!(values[7_500], 0.25);
+/// # }
+/// ```
+pub fn try_values<V, I>(interpolator: &I, xs: &[V]) -> Result<Vec<V>, InterpolationError<V>>
+where
+    V: InterpolationValue,
+    I: Interpolator<V> + ?Sized,
+{
+    xs.par_iter()
+        .with_min_len(MIN_CHUNK_LEN)
+        .map(|&x| interpolator.try_value(x))
+        .collect()
+}
+
+/// Evaluates `interpolator` at every `xs[i]` into `out[i]` in parallel, without allocating.
+///
+/// # Errors
+///
+/// Returns `LengthMismatchError(xs.len(), out.len())` if the slices differ in length, and
+/// otherwise the error of a query failing [`Interpolator::try_value`]; `out` is then partially
+/// written.
+pub fn try_values_into<V, I>(
+    interpolator: &I,
+    xs: &[V],
+    out: &mut [V],
+) -> Result<(), InterpolationError<V>>
+where
+    V: InterpolationValue,
+    I: Interpolator<V> + ?Sized,
+{
+    if xs.len() != out.len() {
+        return Err(InterpolationError::LengthMismatchError(xs.len(), out.len()));
+    }
+    xs.par_chunks(MIN_CHUNK_LEN)
+        .zip(out.par_chunks_mut(MIN_CHUNK_LEN))
+        .try_for_each(|(xs, out)| {
+            for (&x, value) in xs.iter().zip(out) {
+                *value = interpolator.try_value(x)?;
+            }
+            Ok(())
+        })
+}
+
+/// Builds one interpolant per input in parallel, e.g. many splines or surfaces calibrated
+/// independently.
+///
+/// # Errors
+///
+/// Returns the error of an input failing `build`; which one is unspecified if several fail.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "rayon")]
+/// # {
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::parallel;
+///
+/// let curves: Vec<Vec<(f64, f64)>> = (0..100)
+///     .map(|k| (0..1_000).map(|i| (i as f64, (k * i) as f64)).collect())
+///     .collect();
+/// let splines =
+///     parallel::try_build(&curves, |points| NaturalCubicSpline::try_new(points)).unwrap();
+/// assert_eq!(splines[3].try_value(2.5).unwrap(), 7.5);
+/// # }
+/// ```
+pub fn try_build<P, T, V, F>(inputs: &[P], build: F) -> Result<Vec<T>, InterpolationError<V>>
+where
+    P: Sync,
+    T: Send,
+    V: InterpolationValue,
+    F: Fn(&P) -> Result<T, InterpolationError<V>> + Sync + Send,
+{
+    inputs.par_iter().map(build).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::grid_2d::BicubicSpline2D;
+    use crate::interpolation::interpolator::Interpolator;
+    use crate::interpolation::parallel;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_matches_sequential_evaluation() {
+        let points: Vec<(f64, f64)> = (0..50).map(|i| (i as f64, (i as f64).sqrt())).collect();
+        let spline: Box<dyn Interpolator<f64>> =
+            Box::new(CatmullRomSpline::try_new(&points).unwrap());
+        let xs: Vec<f64> = (0..=4_900).map(|i| i as f64 / 100.0).collect();
+        let values = parallel::try_values(spline.as_ref(), &xs).unwrap();
+        let mut out = vec![0.0; xs.len()];
+        parallel::try_values_into(spline.as_ref(), &xs, &mut out).unwrap();
+        for ((&x, value), out) in xs.iter().zip(values).zip(out) {
+            assert_eq!(value, spline.try_value(x).unwrap());
+            assert_eq!(out, value);
+        }
+        let mut xs = xs;
+        xs[3_000] = 60.0;
+        assert!(matches!(
+            parallel::try_values(spline.as_ref(), &xs),
+            Err(InterpolationError::OutOfUpperBound(60.0))
+        ));
+        assert!(matches!(
+            parallel::try_values_into(spline.as_ref(), &xs, &mut [0.0; 3]),
+            Err(InterpolationError::LengthMismatchError(4_901, 3))
+        ));
+    }
+
+    #[test]
+    fn test_build_surfaces() {
+        let axis = [0.0, 1.0, 2.0];
+        let grids: Vec<Vec<f64>> = (0..8)
+            .map(|k| (0..9).map(|v| (k * v) as f64).collect())
+            .collect();
+        let surfaces = parallel::try_build(&grids, |values| {
+            BicubicSpline2D::try_new(&axis, &axis, values)
+        })
+        .unwrap();
+        assert_eq!(surfaces.len(), 8);
+        assert_eq!(surfaces[2].try_value(1.0, 1.0).unwrap(), 8.0);
+        assert!(matches!(
+            parallel::try_build(&[vec![0.0; 8]], |values| {
+                BicubicSpline2D::try_new(&axis, &axis, values)
+            }),
+            Err(InterpolationError::GridShapeError(9, 8))
+        ));
+    }
+}
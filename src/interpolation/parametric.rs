@@ -0,0 +1,564 @@
+//! Parametric curves through points in `D` dimensions, with their differential geometry.
+
+use crate::interpolation::arc_length::ArcLength;
+use crate::interpolation::builder::Method;
+use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+use crate::interpolation::hermite_spline::HermiteSpline;
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::segment_index;
+use crate::math::abs;
+use crate::{InterpolationError, InterpolationValue, TranscendentalValue};
+use std::cmp::Ordering;
+
+/// A curve `t ↦ r(t)` through points in `D` dimensions, interpolating each coordinate over the
+/// parameter with the spline of a [`Method`].
+///
+/// Natural cubic splines give a curve with continuous curvature and a converging third
+/// derivative, as [`Self::try_torsion`] needs; the other methods are only `C¹`, so their
+/// curvature jumps at the points.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::builder::Method;
+/// use spline_interpolation::interpolation::parametric::ParametricCurve;
+///
+/// let points = [[0.0_f64, 0.0], [1.0, 1.0], [2.0, 0.0], [3.0, 1.0]];
+/// let curve = ParametricCurve::try_new(&points, Method::CatmullRom).unwrap();
+/// assert_eq!(curve.domain(), (0.0, 3.0));
+/// assert_eq!(curve.try_point(1.0).unwrap(), [1.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParametricCurve<V: InterpolationValue, const D: usize> {
+    // one polynomial per coordinate, and their third derivatives, constant on each segment
+    coordinates: Vec<PiecewisePolynomial<V>>,
+    third_derivatives: Vec<PiecewisePolynomial<V>>,
+}
+
+/// The position and its first three derivatives with respect to the parameter.
+type Jet<V, const D: usize> = [[V; D]; 4];
+
+impl<V: InterpolationValue, const D: usize> ParametricCurve<V, D> {
+    /// Constructs a new `ParametricCurve` through `points`, the `i`-th at the parameter `t = i`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new_with_parameters`].
+    pub fn try_new(points: &[[V; D]], method: Method) -> Result<Self, InterpolationError<V>> {
+        let parameters: Vec<V> = (0..points.len()).map(V::from_count).collect();
+        Self::try_new_with_parameters(&parameters, points, method)
+    }
+
+    /// Constructs a new `ParametricCurve` through `points[i]` at the parameter `parameters[i]`,
+    /// e.g. the cumulative chord lengths for a parametrization closer to arc length.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::LengthMismatchError(parameters.len(), points.len())` if the
+    /// slices differ in length, and otherwise the errors of the constructor of `method` for each
+    /// coordinate, e.g. `PointOrderError(i)` for decreasing parameters.
+    pub fn try_new_with_parameters(
+        parameters: &[V],
+        points: &[[V; D]],
+        method: Method,
+    ) -> Result<Self, InterpolationError<V>> {
+        if parameters.len() != points.len() {
+            return Err(InterpolationError::LengthMismatchError(
+                parameters.len(),
+                points.len(),
+            ));
+        }
+        let coordinates = (0..D)
+            .map(|k| {
+                let values: Vec<V> = points.iter().map(|point| point[k]).collect();
+                coordinate_polynomial(parameters, &values, method)
+            })
+            .collect::<Result<Vec<_>, InterpolationError<V>>>()?;
+        let third_derivatives = coordinates
+            .iter()
+            .map(|coordinate| coordinate.derivative().derivative().derivative())
+            .collect();
+        Ok(Self {
+            coordinates,
+            third_derivatives,
+        })
+    }
+
+    /// Returns the first and last parameter.
+    pub fn domain(&self) -> (V, V) {
+        let breakpoints = self.coordinates[0].breakpoints();
+        (breakpoints[0], breakpoints[breakpoints.len() - 1])
+    }
+
+    /// Returns the parameters of the points, the ends of the segments.
+    pub fn parameters(&self) -> &[V] {
+        self.coordinates[0].breakpoints()
+    }
+
+    /// Evaluates the position `r(t)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(t)` or `OutOfUpperBound(t)` if `t` lies outside the domain.
+    pub fn try_point(&self, t: V) -> Result<[V; D], InterpolationError<V>> {
+        Ok(self.try_jet(t)?[0])
+    }
+
+    /// Evaluates the velocity `r'(t)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_point`].
+    pub fn try_velocity(&self, t: V) -> Result<[V; D], InterpolationError<V>> {
+        Ok(self.try_jet(t)?[1])
+    }
+
+    /// Evaluates `r(t)` and its first three derivatives with one segment lookup.
+    fn try_jet(&self, t: V) -> Result<Jet<V, D>, InterpolationError<V>> {
+        let i = segment_index(self.parameters(), t)?;
+        let mut jet = [[V::zero(); D]; 4];
+        let polynomials = self.coordinates.iter().zip(&self.third_derivatives);
+        for (k, (coordinate, third_derivative)) in polynomials.enumerate() {
+            let sample = coordinate.sample_in_segment(i, t);
+            jet[0][k] = sample.value;
+            jet[1][k] = sample.dydx;
+            jet[2][k] = sample.d2ydx2;
+            jet[3][k] = third_derivative.coefficients(i)[0];
+        }
+        Ok(jet)
+    }
+}
+
+impl<V: TranscendentalValue, const D: usize> ParametricCurve<V, D> {
+    /// Returns the curvature `κ(t) = √(|r'|²|r''|² - (r'·r'')²) / |r'|³`, the reciprocal of the
+    /// radius of the osculating circle.
+    ///
+    /// The curvature is infinite or NaN where the curve stops, i.e. `r'(t) = 0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_point`].
+    pub fn try_curvature(&self, t: V) -> Result<V, InterpolationError<V>> {
+        let [_, velocity, acceleration, _] = self.try_jet(t)?;
+        let speed_squared = dot(&velocity, &velocity);
+        let along = dot(&velocity, &acceleration);
+        let across = speed_squared * dot(&acceleration, &acceleration) - along * along;
+        // the difference is non-negative but may round below zero for straight pieces
+        let across = if across < V::zero() {
+            V::zero()
+        } else {
+            across
+        };
+        Ok(across.sqrt() / (speed_squared * speed_squared.sqrt()))
+    }
+
+    /// Returns the largest curvature found at `samples_per_segment` equally spaced parameters on
+    /// each segment, ends included, as `(t, κ(t))`.
+    ///
+    /// A path planner can compare it with the tightest turn allowed, e.g. by a steering limit.
+    pub fn max_curvature(&self, samples_per_segment: usize) -> (V, V) {
+        let last = samples_per_segment.max(2) - 1;
+        let mut max = (self.domain().0, V::zero());
+        for segment in self.parameters().windows(2) {
+            let step = (segment[1] - segment[0]) / V::from_count(last);
+            for i in 0..=last {
+                let t = if i == last {
+                    segment[1]
+                } else {
+                    segment[0] + step * V::from_count(i)
+                };
+                let curvature = self
+                    .try_curvature(t)
+                    .expect("the samples lie inside the domain");
+                if curvature > max.1 {
+                    max = (t, curvature);
+                }
+            }
+        }
+        max
+    }
+
+    /// Evaluates the unit tangent `r'(t) / |r'(t)|`, the zero vector where the curve stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_point`].
+    pub fn try_tangent(&self, t: V) -> Result<[V; D], InterpolationError<V>> {
+        Ok(normalize(self.try_jet(t)?[1]))
+    }
+
+    /// Evaluates the principal normal, the unit vector along the part of `r''(t)` perpendicular to
+    /// the tangent, pointing to the center of the osculating circle.
+    ///
+    /// The normal is the zero vector where the curvature vanishes, e.g. on straight pieces; see
+    /// [`ParametricCurve::frames`] for an orientation that is defined there too.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_point`].
+    pub fn try_normal(&self, t: V) -> Result<[V; D], InterpolationError<V>> {
+        let [_, velocity, acceleration, _] = self.try_jet(t)?;
+        let speed_squared = dot(&velocity, &velocity);
+        let along = dot(&velocity, &acceleration);
+        let mut across = acceleration;
+        for (a, &v) in across.iter_mut().zip(&velocity) {
+            *a = *a * speed_squared - v * along;
+        }
+        Ok(normalize(across))
+    }
+
+    /// Returns the length of the curve over its domain.
+    pub fn arc_length(&self) -> V {
+        self.arc_length_table().total()
+    }
+
+    /// Returns `n` points of the curve equally spaced by arc length `s`, the first and last points
+    /// included, at least 2, e.g. to place the vertices of a mesh along it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::builder::Method;
+    /// use spline_interpolation::interpolation::parametric::ParametricCurve;
+    ///
+    /// // a short step followed by a long one, at unit parameter steps
+    /// let points = [[0.0_f64, 0.0], [1.0, 0.0], [4.0, 0.0]];
+    /// let parameters = [0.0, 1.0, 2.0];
+    /// let curve =
+    ///     ParametricCurve::try_new_with_parameters(&parameters, &points, Method::CatmullRom)
+    ///         .unwrap();
+    /// let samples = curve.sample_evenly(5);
+    /// for (k, point) in samples.iter().enumerate() {
+    ///     assert!((point[0] - k as f64).abs() < 1e-9 && point[1] == 0.0);
+    /// }
+    /// ```
+    pub fn sample_evenly(&self, n: usize) -> Vec<[V; D]> {
+        self.arc_length_table()
+            .evenly_spaced_parameters(n)
+            .into_iter()
+            .map(|t| {
+                self.try_point(t)
+                    .expect("the samples lie inside the domain")
+            })
+            .collect()
+    }
+
+    fn arc_length_table(&self) -> ArcLength<V, impl Fn(usize, V) -> V + '_> {
+        ArcLength::new(self.parameters(), |i, t| {
+            let velocity: [V; D] =
+                std::array::from_fn(|k| self.coordinates[k].sample_in_segment(i, t).dydx);
+            dot(&velocity, &velocity).sqrt()
+        })
+    }
+
+    /// Returns `n` equally spaced parameters over the domain, ends included, at least 2.
+    fn equally_spaced_parameters(&self, n: usize) -> impl Iterator<Item = V> {
+        let (start, end) = self.domain();
+        let last = n.max(2) - 1;
+        let step = (end - start) / V::from_count(last);
+        (0..=last).map(move |i| {
+            if i == last {
+                end
+            } else {
+                start + step * V::from_count(i)
+            }
+        })
+    }
+}
+
+/// The position and the orientation of a [`ParametricCurve`] in three dimensions at one
+/// parameter, as returned by [`ParametricCurve::frames`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame<V: InterpolationValue> {
+    /// The parameter.
+    pub t: V,
+    /// The point `r(t)`.
+    pub position: [V; 3],
+    /// The unit tangent.
+    pub tangent: [V; 3],
+    /// The unit normal, perpendicular to the tangent.
+    pub normal: [V; 3],
+    /// The unit binormal, the tangent crossed with the normal.
+    pub binormal: [V; 3],
+}
+
+impl<V: TranscendentalValue> ParametricCurve<V, 3> {
+    /// Returns the frames at `n` equally spaced parameters over the domain, ends included, at
+    /// least 2, e.g. to sweep a profile along the curve or to orient a camera on a rail.
+    ///
+    /// The frames are rotation-minimizing: each normal follows from the previous one by the double
+    /// reflection method of Wang et al. (2008), so the frames do not twist about the tangent more
+    /// than the curve forces them to, unlike the Frenet frames, and they stay defined on straight
+    /// pieces and through inflections. The first normal is the principal normal if the curve bends
+    /// at its start, and otherwise the coordinate axis least aligned with the tangent, made
+    /// perpendicular to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::builder::Method;
+    /// use spline_interpolation::interpolation::parametric::ParametricCurve;
+    ///
+    /// let points = [[0.0_f64, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0]];
+    /// let line = ParametricCurve::try_new(&points, Method::NaturalCubic).unwrap();
+    /// let frames = line.frames(5);
+    /// assert_eq!(frames.len(), 5);
+    /// assert_eq!(frames[2].position, [1.0, 0.0, 0.0]);
+    /// assert_eq!(frames[2].tangent, [1.0, 0.0, 0.0]);
+    /// assert_eq!(frames[2].normal, [0.0, 1.0, 0.0]);
+    /// assert_eq!(frames[2].binormal, [0.0, 0.0, 1.0]);
+    /// ```
+    pub fn frames(&self, n: usize) -> Vec<Frame<V>> {
+        let mut frames: Vec<Frame<V>> = Vec::with_capacity(n.max(2));
+        for t in self.equally_spaced_parameters(n) {
+            let [position, velocity, ..] = self
+                .try_jet(t)
+                .expect("the parameters lie inside the domain");
+            let tangent = normalize(velocity);
+            let normal = match frames.last() {
+                Some(previous) => reflect_normal(previous, position, tangent),
+                None => self.initial_normal(t, tangent),
+            };
+            frames.push(Frame {
+                t,
+                position,
+                tangent,
+                normal,
+                binormal: cross(&tangent, &normal),
+            });
+        }
+        frames
+    }
+
+    /// Returns the principal normal at `t`, or the axis least aligned with `tangent` made
+    /// perpendicular to it where the curve does not bend.
+    fn initial_normal(&self, t: V, tangent: [V; 3]) -> [V; 3] {
+        let principal = self
+            .try_normal(t)
+            .expect("the parameters lie inside the domain");
+        if principal != [V::zero(); 3] {
+            return principal;
+        }
+        let k = (0..3)
+            .min_by(|&i, &j| {
+                abs(tangent[i])
+                    .partial_cmp(&abs(tangent[j]))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .expect("three axes");
+        let mut axis = [V::zero(); 3];
+        axis[k] = V::one();
+        normalize(sub(&axis, &tangent.map(|c| c * tangent[k])))
+    }
+}
+
+/// Carries the normal of `previous` to the next frame, at `position` with `tangent`, by the
+/// reflection in the bisector plane of the two positions followed by the reflection that maps the
+/// reflected tangent onto `tangent`.
+fn reflect_normal<V: InterpolationValue>(
+    previous: &Frame<V>,
+    position: [V; 3],
+    tangent: [V; 3],
+) -> [V; 3] {
+    let reflect = |v: [V; 3], axis: &[V; 3]| {
+        let norm_squared = dot(axis, axis);
+        if norm_squared == V::zero() {
+            return v;
+        }
+        let scale = V::from_integer(2) * dot(axis, &v) / norm_squared;
+        sub(&v, &axis.map(|c| c * scale))
+    };
+    let chord = sub(&position, &previous.position);
+    let normal = reflect(previous.normal, &chord);
+    let tangent_gap = sub(&tangent, &reflect(previous.tangent, &chord));
+    reflect(normal, &tangent_gap)
+}
+
+impl<V: InterpolationValue> ParametricCurve<V, 3> {
+    /// Returns the torsion `τ(t) = (r' × r'')·r''' / |r' × r''|²`, the rate at which the curve
+    /// leaves its osculating plane.
+    ///
+    /// The torsion is infinite or NaN where the curvature vanishes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_point`].
+    pub fn try_torsion(&self, t: V) -> Result<V, InterpolationError<V>> {
+        let [_, velocity, acceleration, jerk] = self.try_jet(t)?;
+        let binormal = cross(&velocity, &acceleration);
+        Ok(dot(&binormal, &jerk) / dot(&binormal, &binormal))
+    }
+}
+
+/// Interpolates one coordinate over the parameters with the spline of `method`.
+fn coordinate_polynomial<V: InterpolationValue>(
+    parameters: &[V],
+    values: &[V],
+    method: Method,
+) -> Result<PiecewisePolynomial<V>, InterpolationError<V>> {
+    Ok(match method {
+        Method::NaturalCubic => (&NaturalCubicSpline::try_new_xy(parameters, values)?).into(),
+        Method::CatmullRom => (&CatmullRomSpline::try_new_xy(parameters, values)?).into(),
+        Method::Hermite(slope_method) => {
+            let points: Vec<_> = parameters
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect();
+            (&HermiteSpline::try_new_with_slope_method(&points, slope_method)?).into()
+        }
+    })
+}
+
+/// Returns the dot product of `a` and `b`.
+pub(crate) fn dot<V: InterpolationValue, const D: usize>(a: &[V; D], b: &[V; D]) -> V {
+    a.iter().zip(b).fold(V::zero(), |acc, (&a, &b)| acc + a * b)
+}
+
+/// Returns `a - b`.
+fn sub<V: InterpolationValue, const D: usize>(a: &[V; D], b: &[V; D]) -> [V; D] {
+    std::array::from_fn(|k| a[k] - b[k])
+}
+
+/// Returns `v` scaled to unit length, or the zero vector for the zero vector.
+fn normalize<V: TranscendentalValue, const D: usize>(v: [V; D]) -> [V; D] {
+    let norm = dot(&v, &v).sqrt();
+    if norm == V::zero() {
+        return v;
+    }
+    v.map(|c| c / norm)
+}
+
+/// Returns the cross product of `a` and `b`.
+pub(crate) fn cross<V: InterpolationValue>(a: &[V; 3], b: &[V; 3]) -> [V; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::builder::Method;
+    use crate::interpolation::parametric::{cross, Frame, ParametricCurve};
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_helix() {
+        // r(t) = (cos t, sin t, t / 2), with κ = 1 / 1.25 and τ = 0.5 / 1.25
+        let parameters: Vec<f64> = (0..=100).map(|i| i as f64 * 0.05).collect();
+        let points: Vec<[f64; 3]> = parameters
+            .iter()
+            .map(|&t| [t.cos(), t.sin(), t / 2.0])
+            .collect();
+        let helix =
+            ParametricCurve::try_new_with_parameters(&parameters, &points, Method::NaturalCubic)
+                .unwrap();
+        for t in [1.0, 2.34, 3.9] {
+            assert!((helix.try_curvature(t).unwrap() - 0.8).abs() < 1e-2);
+            assert!((helix.try_torsion(t).unwrap() - 0.4).abs() < 1e-2);
+        }
+        assert!(matches!(
+            helix.try_curvature(5.5),
+            Err(InterpolationError::OutOfUpperBound(5.5))
+        ));
+    }
+
+    #[test]
+    fn test_max_curvature() {
+        // straight, then a turn at the corner (2, 0)
+        let points = [
+            [0.0_f64, 0.0],
+            [1.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 1.0],
+            [2.0, 2.0],
+        ];
+        let curve = ParametricCurve::try_new(&points, Method::CatmullRom).unwrap();
+        assert!(curve.try_curvature(0.5).unwrap().abs() < 1e-12);
+        let (t, curvature) = curve.max_curvature(16);
+        assert_eq!(t, 2.0);
+        assert!(curvature > 1.0);
+        assert!(matches!(
+            ParametricCurve::try_new_with_parameters(&[0.0, 1.0], &points, Method::CatmullRom),
+            Err(InterpolationError::LengthMismatchError(2, 5))
+        ));
+    }
+
+    #[test]
+    fn test_tangent_and_normal() {
+        let points = [
+            [1.0_f64, 0.0],
+            [0.0, 1.0],
+            [-1.0, 0.0],
+            [0.0, -1.0],
+            [1.0, 0.0],
+        ];
+        let curve = ParametricCurve::try_new(&points, Method::NaturalCubic).unwrap();
+        let tangent = curve.try_tangent(2.0).unwrap();
+        assert!((tangent[0] * tangent[0] + tangent[1] * tangent[1] - 1.0).abs() < 1e-12);
+        assert!(tangent[0].abs() < 1e-12 && tangent[1] < 0.0);
+        // pointing back to the center at (-1, 0)
+        let normal = curve.try_normal(2.0).unwrap();
+        assert!((normal[0] - 1.0).abs() < 1e-12 && normal[1].abs() < 1e-12);
+        let line = [[0.0_f64, 0.0], [1.0, 1.0], [2.0, 2.0]];
+        let line = ParametricCurve::try_new(&line, Method::NaturalCubic).unwrap();
+        assert_eq!(line.try_normal(0.5).unwrap(), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_rotation_minimizing_frames() {
+        let parameters: Vec<f64> = (0..=100).map(|i| i as f64 * 0.05).collect();
+        let points: Vec<[f64; 3]> = parameters
+            .iter()
+            .map(|&t| [t.cos(), t.sin(), t / 2.0])
+            .collect();
+        let helix =
+            ParametricCurve::try_new_with_parameters(&parameters, &points, Method::NaturalCubic)
+                .unwrap();
+        let frames = helix.frames(201);
+        assert_eq!(frames.len(), 201);
+        assert_eq!(frames[200].t, 5.0);
+        let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        for frame in &frames {
+            assert!(dot(frame.tangent, frame.normal).abs() < 1e-9);
+            assert!((dot(frame.normal, frame.normal) - 1.0).abs() < 1e-9);
+            assert!((dot(frame.binormal, frame.binormal) - 1.0).abs() < 1e-9);
+        }
+        // the frames turn against the principal normals at the rate of the torsion, 0.4 per unit
+        // of arc length, i.e. 0.4 * 1.25^0.5 per unit of t
+        let twist = |i: usize| {
+            let frame: Frame<f64> = frames[i];
+            let frenet = helix.try_normal(frame.t).unwrap();
+            let sine = dot(cross(&frenet, &frame.normal), frame.tangent);
+            sine.atan2(dot(frenet, frame.normal))
+        };
+        let (start, end) = (frames[40].t, frames[160].t);
+        assert!((start - 1.0).abs() < 1e-12 && (end - 4.0).abs() < 1e-12);
+        let rate = (twist(160) - twist(40)) / (end - start);
+        assert!((rate.abs() - 0.4 * 1.25_f64.sqrt()).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_sample_evenly() {
+        // a quarter circle of radius 2, whose arc length is π
+        let parameters: Vec<f64> = (0..=40).map(|i| i as f64 / 40.0).collect();
+        let angle = |t: f64| t * t * std::f64::consts::FRAC_PI_2;
+        let points: Vec<[f64; 2]> = parameters
+            .iter()
+            .map(|&t| [2.0 * angle(t).cos(), 2.0 * angle(t).sin()])
+            .collect();
+        let arc =
+            ParametricCurve::try_new_with_parameters(&parameters, &points, Method::NaturalCubic)
+                .unwrap();
+        assert!((arc.arc_length() - std::f64::consts::PI).abs() < 1e-4);
+        let samples = arc.sample_evenly(7);
+        assert_eq!(samples.len(), 7);
+        for (k, sample) in samples.iter().enumerate() {
+            let expected = k as f64 / 6.0 * std::f64::consts::FRAC_PI_2;
+            assert!((sample[1].atan2(sample[0]) - expected).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Fitting dense and sparse regions of the knots with different methods.
+
+use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+use crate::interpolation::hermite_spline::HermiteSpline;
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::sample::SplineSample;
+use crate::interpolation::slopes::SlopeMethod;
+use crate::{InterpolationError, InterpolationValue};
+use std::ops::Range;
+
+/// The method fitting one region of a [`PartitionedSpline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionMethod {
+    /// A natural cubic spline through the points of the region.
+    NaturalCubic,
+    /// A Catmull-Rom spline through the points of the region.
+    CatmullRom,
+    /// A Hermite spline with slopes estimated from the points of the region.
+    Hermite(SlopeMethod),
+}
+
+/// Whether the knots of a region are closely or widely spaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Density {
+    /// The intervals are narrower than the threshold of the [`DensityPartition`].
+    Dense,
+    /// The intervals are at least as wide as the threshold of the [`DensityPartition`].
+    Sparse,
+}
+
+/// The options of [`PointBuilder::build_partitioned`](crate::interpolation::streaming::PointBuilder::build_partitioned).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DensityPartition<V: InterpolationValue> {
+    /// An interval is dense if it is narrower than `threshold` times the average interval width.
+    pub threshold: V,
+    /// The method used on dense regions, where the data is often noisy.
+    pub dense: RegionMethod,
+    /// The method used on sparse regions.
+    pub sparse: RegionMethod,
+}
+
+impl<V: InterpolationValue> Default for DensityPartition<V> {
+    /// Half the average width, Akima slopes on dense regions and natural cubic splines on sparse
+    /// regions.
+    fn default() -> Self {
+        Self {
+            threshold: V::one() / V::from_integer(2),
+            dense: RegionMethod::Hermite(SlopeMethod::Akima),
+            sparse: RegionMethod::NaturalCubic,
+        }
+    }
+}
+
+/// A region of a [`PartitionedSpline`] and the method chosen for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region<V: InterpolationValue> {
+    /// The indices of the segments in the region.
+    pub segments: Range<usize>,
+    /// The x-coordinate where the region starts.
+    pub start: V,
+    /// The x-coordinate where the region ends.
+    pub end: V,
+    /// The density of the region.
+    pub density: Density,
+    /// The method fitting the region.
+    pub method: RegionMethod,
+}
+
+/// A spline whose regions are fitted with different methods depending on the knot density.
+///
+/// Every region has at least 2 segments. The spline interpolates all points and is continuous,
+/// but its derivatives may jump where two regions meet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionedSpline<V: InterpolationValue> {
+    polynomial: PiecewisePolynomial<V>,
+    regions: Vec<Region<V>>,
+}
+
+impl<V: InterpolationValue> PartitionedSpline<V> {
+    /// Partitions the sorted points into dense and sparse regions and fits each region.
+    ///
+    /// Runs of fewer than 2 intervals of one density are merged into a neighbouring region,
+    /// dense runs first so that a lone narrow interval joins the sparse region around it.
+    pub(crate) fn try_new(
+        points: &[(V, V)],
+        options: &DensityPartition<V>,
+    ) -> Result<Self, InterpolationError<V>> {
+        let n = points.len();
+        if n < 3 {
+            return Err(InterpolationError::InsufficientPointsError(n));
+        }
+        let average = (points[n - 1].0 - points[0].0) / V::from_count(n - 1);
+        let mut runs: Vec<(Density, usize)> = Vec::new();
+        for w in points.windows(2) {
+            let density = if w[1].0 - w[0].0 < options.threshold * average {
+                Density::Dense
+            } else {
+                Density::Sparse
+            };
+            match runs.last_mut() {
+                Some((last, count)) if *last == density => *count += 1,
+                _ => runs.push((density, 1)),
+            }
+        }
+        let short = |runs: &[(Density, usize)], density| {
+            runs.iter()
+                .position(|&(d, count)| d == density && count < 2)
+        };
+        while let Some(k) = short(&runs, Density::Dense).or_else(|| short(&runs, Density::Sparse)) {
+            if runs.len() == 1 {
+                break;
+            }
+            let (_, count) = runs.remove(k);
+            let target = k.saturating_sub(1);
+            runs[target].1 += count;
+            if k > 0 && k < runs.len() && runs[k].0 == runs[target].0 {
+                let (_, count) = runs.remove(k);
+                runs[target].1 += count;
+            }
+        }
+
+        let mut regions = Vec::with_capacity(runs.len());
+        let mut breakpoints = vec![points[0].0];
+        let mut coefficients = Vec::with_capacity((n - 1) * 4);
+        let mut first = 0;
+        for (density, count) in runs {
+            let method = match density {
+                Density::Dense => options.dense,
+                Density::Sparse => options.sparse,
+            };
+            let segments = first..first + count;
+            let polynomial = fit(&points[first..=segments.end], method)?;
+            breakpoints.extend_from_slice(&polynomial.breakpoints()[1..]);
+            for i in 0..polynomial.segment_count() {
+                coefficients.extend_from_slice(polynomial.coefficients(i));
+            }
+            regions.push(Region {
+                start: points[first].0,
+                end: points[segments.end].0,
+                segments,
+                density,
+                method,
+            });
+            first += count;
+        }
+        Ok(Self {
+            polynomial: PiecewisePolynomial::new_unchecked(breakpoints, coefficients, 4),
+            regions,
+        })
+    }
+
+    /// Returns the regions and the methods chosen for them, in ascending order.
+    pub fn regions(&self) -> &[Region<V>] {
+        &self.regions
+    }
+
+    /// Returns the spline as a single piecewise polynomial.
+    pub fn polynomial(&self) -> &PiecewisePolynomial<V> {
+        &self.polynomial
+    }
+
+    /// Returns the highest degree of polynomials this spline reproduces exactly, which is 1 for
+    /// every [`RegionMethod`].
+    pub fn degree_exactness(&self) -> usize {
+        1
+    }
+
+    /// Evaluates the spline at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` if `x` lies outside the points.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        self.polynomial.try_value(x)
+    }
+
+    /// Evaluates the spline and its first and second derivatives at `x` with a single segment
+    /// lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_eval_all(&self, x: V) -> Result<SplineSample<V>, InterpolationError<V>> {
+        self.polynomial.try_eval_all(x)
+    }
+}
+
+fn fit<V: InterpolationValue>(
+    points: &[(V, V)],
+    method: RegionMethod,
+) -> Result<PiecewisePolynomial<V>, InterpolationError<V>> {
+    Ok(match method {
+        RegionMethod::NaturalCubic => (&NaturalCubicSpline::try_new(points)?).into(),
+        RegionMethod::CatmullRom => (&CatmullRomSpline::try_new(points)?).into(),
+        RegionMethod::Hermite(slope_method) => {
+            let xs: Vec<_> = points.iter().map(|&(x, _)| x).collect();
+            let ys: Vec<_> = points.iter().map(|&(_, y)| y).collect();
+            let raw_points: Vec<_> = (0..points.len())
+                .map(|i| (xs[i], ys[i], slope_method.slope(&xs, &ys, i)))
+                .collect();
+            (&HermiteSpline::try_new(&raw_points)?).into()
+        }
+    })
+}
+
+impl_interpolator!(PartitionedSpline, Partitioned);
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::partition::{Density, DensityPartition, RegionMethod};
+    use crate::interpolation::slopes::SlopeMethod;
+    use crate::interpolation::streaming::PointBuilder;
+
+    #[test]
+    fn test_dense_cluster() {
+        let mut builder = PointBuilder::new();
+        let xs = [0.0, 1.0, 2.0, 3.0]
+            .into_iter()
+            .chain((1..=10).map(|i| 3.0 + i as f64 * 0.1))
+            .chain([5.0, 6.0, 7.0]);
+        for x in xs.clone() {
+            builder.push(x, (x * 0.7).sin()).unwrap();
+        }
+        let spline = builder
+            .build_partitioned(&DensityPartition::default())
+            .unwrap();
+        let regions = spline.regions();
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[0].segments, 0..3);
+        assert_eq!(regions[1].segments, 3..13);
+        assert_eq!((regions[1].start, regions[1].end), (3.0, 4.0));
+        assert_eq!(regions[1].density, Density::Dense);
+        assert_eq!(regions[1].method, RegionMethod::Hermite(SlopeMethod::Akima));
+        assert_eq!(regions[2].method, RegionMethod::NaturalCubic);
+        for x in xs {
+            assert!((spline.try_value(x).unwrap() - (x * 0.7).sin()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_short_runs_are_merged() {
+        let mut builder = PointBuilder::new();
+        for x in [0.0, 1.0, 1.1, 2.0, 3.0, 4.0] {
+            builder.push(x, x * x).unwrap();
+        }
+        let spline = builder
+            .build_partitioned(&DensityPartition::default())
+            .unwrap();
+        assert_eq!(spline.regions().len(), 1);
+        assert_eq!(spline.regions()[0].segments, 0..5);
+        assert_eq!(spline.regions()[0].density, Density::Sparse);
+    }
+}
@@ -0,0 +1,806 @@
+use crate::interpolation::arc_length::ArcLength;
+use crate::interpolation::bezier::CubicBezier;
+use crate::interpolation::continuity::ContinuityReport;
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::pretty::{self, Notation};
+use crate::interpolation::sample::SplineSample;
+#[cfg(feature = "serde")]
+use crate::interpolation::serialization::PolynomialDocument;
+#[cfg(feature = "svg")]
+use crate::interpolation::svg::{self, PathSampling};
+use crate::interpolation::{check_knots, nearest_knot, segment_index, segment_index_unchecked};
+use crate::math::roots::{horner, roots_in};
+use crate::metadata::Metadata;
+use crate::profiling::record_segment_evaluation;
+use crate::{InterpolationError, InterpolationValue, TranscendentalValue};
+use nalgebra::Vector4;
+use num_traits::Zero;
+use std::fmt::{self, Display};
+use std::ops::RangeInclusive;
+
+/// A piecewise polynomial defined by breakpoints and per-segment coefficient rows.
+///
+/// On the segment `[x_i, x_{i+1}]` the polynomial is
+/// `c[i][0] * (x - x_i)^k + c[i][1] * (x - x_i)^(k - 1) + ... + c[i][k]`,
+/// i.e. the coefficients are stored from the highest power down, as in scipy's `PPoly`.
+/// Every segment shares the same order `k + 1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewisePolynomial<V: InterpolationValue> {
+    breakpoints: Vec<V>,
+    coefficients: Vec<V>,
+    order: usize,
+    metadata: Metadata,
+}
+
+impl<V: InterpolationValue> PiecewisePolynomial<V> {
+    /// Constructs a new `PiecewisePolynomial` from breakpoints and coefficient rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `breakpoints` - The segment boundaries `x_0 < x_1 < ... < x_n`.
+    /// * `coefficients` - One row per segment, highest power first.
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(n)` - If fewer than 2 breakpoints are given.
+    /// * `InterpolationError::PointOrderError(i)` - If the `i`-th breakpoint is smaller than its
+    ///   predecessor.
+    /// * `InterpolationError::DuplicatePointError(x)` - If two breakpoints equal `x`.
+    /// * `InterpolationError::NonFiniteError(i)` - If the `i`-th breakpoint is NaN or infinite.
+    /// * `InterpolationError::CoefficientShapeError` - If the number of rows is not one per segment,
+    ///   or the rows are empty or of differing lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    ///
+    /// // x^2 on [0, 1] and 1 + 2(x - 1) on [1, 2]
+    /// let pp = PiecewisePolynomial::try_new(
+    ///     vec![0.0, 1.0, 2.0],
+    ///     vec![vec![1.0, 0.0, 0.0], vec![0.0, 2.0, 1.0]],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(pp.try_value(0.5).unwrap(), 0.25);
+    /// assert_eq!(pp.try_value(1.5).unwrap(), 2.0);
+    /// ```
+    pub fn try_new(
+        breakpoints: Vec<V>,
+        coefficients: Vec<Vec<V>>,
+    ) -> Result<Self, InterpolationError<V>> {
+        if breakpoints.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(
+                breakpoints.len(),
+            ));
+        }
+        check_knots(breakpoints.iter().copied())?;
+        if coefficients.len() + 1 != breakpoints.len() {
+            return Err(InterpolationError::CoefficientShapeError);
+        }
+        let order = coefficients[0].len();
+        if order.is_zero() || coefficients.iter().any(|row| row.len() != order) {
+            return Err(InterpolationError::CoefficientShapeError);
+        }
+        Ok(Self::new_unchecked(
+            breakpoints,
+            coefficients.into_iter().flatten().collect(),
+            order,
+        ))
+    }
+
+    /// Builds a piecewise polynomial from already validated, flattened coefficient rows.
+    pub(crate) fn new_unchecked(breakpoints: Vec<V>, coefficients: Vec<V>, order: usize) -> Self {
+        debug_assert_eq!(coefficients.len(), (breakpoints.len() - 1) * order);
+        Self {
+            breakpoints,
+            coefficients,
+            order,
+            metadata: Metadata::default(),
+        }
+    }
+
+    /// Builds a cubic piecewise polynomial from segments expressed in the normalized variable
+    /// `t = (x - x_i) / (x_{i+1} - x_i)`, each given as `(t^3, t^2, t, 1)` coefficients.
+    pub(crate) fn from_normalized_cubics(
+        breakpoints: Vec<V>,
+        segments: impl IntoIterator<Item = Vector4<V>>,
+    ) -> Self {
+        let mut coefficients = Vec::with_capacity((breakpoints.len() - 1) * 4);
+        for (w, c) in breakpoints.windows(2).zip(segments) {
+            let h = w[1] - w[0];
+            coefficients.extend([c.x / (h * h * h), c.y / (h * h), c.z / h, c.w]);
+        }
+        Self::new_unchecked(breakpoints, coefficients, 4)
+    }
+
+    /// Returns the segment boundaries.
+    pub fn breakpoints(&self) -> &[V] {
+        &self.breakpoints
+    }
+
+    /// Returns the number of segments.
+    pub fn segment_count(&self) -> usize {
+        self.breakpoints.len() - 1
+    }
+
+    /// Returns the index of the segment containing `x`, e.g. to attribute a value to the data
+    /// points spanning it.
+    ///
+    /// A breakpoint belongs to the segment starting there, except for the last one, which closes
+    /// the last segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` outside the breakpoints, and
+    /// `NonFiniteInput(x)` if `x` is NaN or infinite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    ///
+    /// // a step function on [0, 1), [1, 3) and [3, 6]
+    /// let steps = vec![vec![1.0], vec![0.5], vec![0.25]];
+    /// let pp = PiecewisePolynomial::try_new(vec![0.0, 1.0, 3.0, 6.0], steps).unwrap();
+    /// let i = pp.segment_index(2.0).unwrap();
+    /// assert_eq!((i, pp.segment_bounds(i)), (1, (1.0, 3.0)));
+    /// assert_eq!(pp.segment_index(3.0).unwrap(), 2);
+    /// assert_eq!(pp.segment_index(6.0).unwrap(), 2);
+    /// assert_eq!(pp.nearest_knot(2.5).unwrap(), 2);
+    /// assert_eq!(pp.nearest_knot(-4.0).unwrap(), 0);
+    /// ```
+    pub fn segment_index(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        segment_index(&self.breakpoints, x)
+    }
+
+    /// Returns the breakpoints `(start, end)` of the `i`-th segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than [`Self::segment_count`].
+    pub fn segment_bounds(&self, i: usize) -> (V, V) {
+        (self.breakpoints[i], self.breakpoints[i + 1])
+    }
+
+    /// Returns the index of the breakpoint nearest to `x`, the lower one of two equally near;
+    /// queries outside the breakpoints get the nearer end.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn nearest_knot(&self, x: V) -> Result<usize, InterpolationError<V>> {
+        nearest_knot(&self.breakpoints, x)
+    }
+
+    /// Returns the polynomial degree shared by every segment.
+    pub fn degree(&self) -> usize {
+        self.order - 1
+    }
+
+    /// Returns the coefficient row of the `i`-th segment, highest power first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= self.segment_count()`.
+    pub fn coefficients(&self, i: usize) -> &[V] {
+        &self.coefficients[i * self.order..(i + 1) * self.order]
+    }
+
+    /// Attaches provenance metadata to the piecewise polynomial; conversions from splines keep theirs.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the provenance metadata of the piecewise polynomial.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the provenance metadata of the piecewise polynomial for editing.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Evaluates the piecewise polynomial at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` if `x` is less than the first breakpoint.
+    /// Returns `OutOfUpperBound(x)` if `x` is greater than the last breakpoint.
+    /// Returns `NonFiniteInput(x)` if `x` is NaN or infinite.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        let i = segment_index(&self.breakpoints, x)?;
+        Ok(self.value_in_segment(i, x))
+    }
+
+    /// Evaluates the piecewise polynomial at `x` without checking it, for hot loops over queries
+    /// already known to lie within the breakpoints.
+    ///
+    /// A query at an inner breakpoint is evaluated with the polynomial of the segment starting
+    /// there, as by [`Self::try_value`].
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `x` is NaN or outside the breakpoints. Release builds extrapolate
+    /// the end segments instead, and return NaN for a NaN query.
+    pub fn value_unchecked(&self, x: V) -> V {
+        self.value_in_segment(segment_index_unchecked(&self.breakpoints, x), x)
+    }
+
+    /// Evaluates the piecewise polynomial and its first and second derivatives at `x` with a
+    /// single segment lookup and one pass over the coefficients.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_value`].
+    pub fn try_eval_all(&self, x: V) -> Result<SplineSample<V>, InterpolationError<V>> {
+        let i = segment_index(&self.breakpoints, x)?;
+        Ok(self.sample_in_segment(i, x))
+    }
+
+    /// Evaluates the polynomial of the `i`-th segment and its first two derivatives at `x`.
+    pub(crate) fn sample_in_segment(&self, i: usize, x: V) -> SplineSample<V> {
+        record_segment_evaluation();
+        let t = x - self.breakpoints[i];
+        // Horner's scheme carried through the first two derivatives
+        let (mut value, mut dydx, mut half_d2ydx2) = (V::zero(), V::zero(), V::zero());
+        for &c in self.coefficients(i) {
+            half_d2ydx2 = half_d2ydx2 * t + dydx;
+            dydx = dydx * t + value;
+            value = value * t + c;
+        }
+        SplineSample {
+            value,
+            dydx,
+            d2ydx2: half_d2ydx2 + half_d2ydx2,
+        }
+    }
+
+    /// Returns the cubic Bézier curve of each segment, e.g. to draw the polynomial with a vector
+    /// renderer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::CoefficientShapeError` if the degree exceeds 3.
+    pub fn try_to_bezier_segments(&self) -> Result<Vec<CubicBezier<V>>, InterpolationError<V>> {
+        if self.degree() > 3 {
+            return Err(InterpolationError::CoefficientShapeError);
+        }
+        Ok(self
+            .breakpoints
+            .windows(2)
+            .enumerate()
+            .map(|(i, w)| {
+                let (start, end) = (
+                    self.sample_in_segment(i, w[0]),
+                    self.sample_in_segment(i, w[1]),
+                );
+                CubicBezier::from_hermite(
+                    (w[0], start.value),
+                    start.dydx,
+                    (w[1], end.value),
+                    end.dydx,
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the jumps of the polynomial and its first two derivatives at the interior
+    /// breakpoints, which show how smoothly the segments join.
+    pub fn continuity_report(&self) -> ContinuityReport<V> {
+        ContinuityReport::from_segment_ends(
+            &self.breakpoints,
+            self.breakpoints.windows(2).enumerate().map(|(i, w)| {
+                (
+                    self.sample_in_segment(i, w[0]),
+                    self.sample_in_segment(i, w[1]),
+                )
+            }),
+        )
+    }
+
+    /// Returns the SVG path data, the `d` attribute, of the graph of the piecewise polynomial.
+    ///
+    /// [`PathSampling::Exact`] falls back to a polyline through 16 points per segment if the
+    /// degree exceeds 3.
+    #[cfg(feature = "svg")]
+    pub fn to_svg_path_data(&self, sampling: PathSampling) -> String
+    where
+        V: Display,
+    {
+        let points = match (sampling, self.try_to_bezier_segments()) {
+            (PathSampling::Exact, Ok(segments)) => return svg::bezier_path_data(&segments),
+            (PathSampling::Exact, Err(_)) => 16 * self.segment_count() + 1,
+            (PathSampling::Polyline(points), _) => points,
+        };
+        let (start, end) = (self.breakpoints[0], self.breakpoints[self.segment_count()]);
+        svg::polyline_path_data((start, end), points, |x| {
+            self.try_value(x)
+                .expect("the samples lie inside the breakpoints")
+        })
+    }
+
+    /// Returns the piecewise polynomial as a LaTeX `cases` environment, one row per segment in the
+    /// notation of its [`Display`] form.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    ///
+    /// let pp = PiecewisePolynomial::try_new(
+    ///     vec![0.0, 1.0, 2.0],
+    ///     vec![vec![1.0, 0.0, 0.0], vec![0.0, 2.0, 1.0]],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     pp.to_latex(),
+    ///     "\\begin{cases}\nx^{2} & 0 \\le x < 1 \\\\\n1 + 2(x - 1) & 1 \\le x \\le 2\n\\end{cases}"
+    /// );
+    /// ```
+    pub fn to_latex(&self) -> String
+    where
+        V: Display,
+    {
+        let mut latex = String::from("\\begin{cases}\n");
+        pretty::write_piecewise(&mut latex, self, Notation::Latex, None)
+            .expect("writing to a string cannot fail");
+        latex.push_str("\n\\end{cases}");
+        latex
+    }
+
+    /// Returns the derivative as a piecewise polynomial of one degree lower.
+    ///
+    /// The derivative of a piecewise constant is the zero piecewise constant.
+    pub fn derivative(&self) -> Self {
+        if self.order == 1 {
+            return Self::new_unchecked(
+                self.breakpoints.clone(),
+                vec![V::zero(); self.coefficients.len()],
+                1,
+            );
+        }
+        let degree = self.degree();
+        let mut coefficients = Vec::with_capacity(self.segment_count() * degree);
+        for i in 0..self.segment_count() {
+            let row = self.coefficients(i);
+            for (k, &c) in row[..degree].iter().enumerate() {
+                coefficients.push(c * V::from_count(degree - k));
+            }
+        }
+        Self::new_unchecked(self.breakpoints.clone(), coefficients, degree)
+    }
+
+    /// Returns the continuous antiderivative which vanishes at the first breakpoint.
+    pub fn antiderivative(&self) -> Self {
+        let order = self.order + 1;
+        let mut coefficients = Vec::with_capacity(self.segment_count() * order);
+        let mut constant = V::zero();
+        for i in 0..self.segment_count() {
+            let row = self.coefficients(i);
+            for (k, &c) in row.iter().enumerate() {
+                coefficients.push(c / V::from_count(self.order - k));
+            }
+            coefficients.push(constant);
+            let h = self.breakpoints[i + 1] - self.breakpoints[i];
+            constant = horner(&coefficients[i * order..], h);
+        }
+        Self::new_unchecked(self.breakpoints.clone(), coefficients, order)
+    }
+
+    /// Computes the definite integral from `a` to `b`.
+    ///
+    /// Swapping the bounds negates the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the breakpoints.
+    pub fn integrate(&self, a: V, b: V) -> Result<V, InterpolationError<V>> {
+        let antiderivative = self.antiderivative();
+        Ok(antiderivative.try_value(b)? - antiderivative.try_value(a)?)
+    }
+
+    /// Returns every `x` at which the piecewise polynomial crosses the level `y`, in increasing
+    /// order, from the roots of each segment.
+    ///
+    /// A segment merely touching the level is only reported where it evaluates to `y` exactly, and
+    /// a segment lying on the level yields its ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    ///
+    /// // x^2 - 1 on [-2, 2]
+    /// let pp = PiecewisePolynomial::try_new(vec![-2.0, 2.0], vec![vec![1.0, -4.0, 3.0]]).unwrap();
+    /// assert_eq!(pp.intersect_level(0.0), vec![-1.0, 1.0]);
+    /// assert_eq!(pp.intersect_line(1.0, 1.0), vec![-1.0, 2.0]);
+    /// ```
+    pub fn intersect_level(&self, y: V) -> Vec<V> {
+        self.intersect_line(y, V::zero())
+    }
+
+    /// Returns every `x` at which the piecewise polynomial crosses the line `a + b x`, as
+    /// [`Self::intersect_level`] does for a level.
+    pub fn intersect_line(&self, a: V, b: V) -> Vec<V> {
+        let order = self.order.max(2);
+        let mut crossings: Vec<V> = Vec::new();
+        for (i, segment) in self.breakpoints.windows(2).enumerate() {
+            let (start, width) = (segment[0], segment[1] - segment[0]);
+            let mut row = vec![V::zero(); order - self.order];
+            row.extend_from_slice(self.coefficients(i));
+            // in the local variable t = x - x_i the line is a + b x_i + b t
+            row[order - 1] -= a + b * start;
+            row[order - 2] -= b;
+            for t in roots_in(&row, V::zero(), width) {
+                let x = if t == width { segment[1] } else { start + t };
+                if crossings.last() != Some(&x) {
+                    crossings.push(x);
+                }
+            }
+        }
+        crossings
+    }
+
+    /// Returns the lowest point `(x, p(x))` over `range`, whose bounds may come in either order.
+    ///
+    /// The candidates are the ends of the range, the breakpoints inside it and the stationary
+    /// points of each segment, from the roots of its derivative; the first of equally low points
+    /// wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound` or `OutOfUpperBound` if either bound lies outside the breakpoints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    ///
+    /// // (x - 1)^2 on [0, 3]
+    /// let pp = PiecewisePolynomial::try_new(vec![0.0, 3.0], vec![vec![1.0, -2.0, 1.0]]).unwrap();
+    /// assert_eq!(pp.argmin(0.0..=3.0).unwrap(), (1.0, 0.0));
+    /// assert_eq!(pp.argmax(0.0..=3.0).unwrap(), (3.0, 4.0));
+    /// assert_eq!(pp.argmin(2.0..=3.0).unwrap(), (2.0, 1.0));
+    /// ```
+    pub fn argmin(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        self.extremum(range, |candidate, best| candidate < best)
+    }
+
+    /// Returns the highest point `(x, p(x))` over `range`, as [`Self::argmin`] does the lowest.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::argmin`].
+    pub fn argmax(&self, range: RangeInclusive<V>) -> Result<(V, V), InterpolationError<V>> {
+        self.extremum(range, |candidate, best| candidate > best)
+    }
+
+    /// Returns the candidate extremum of [`Self::argmin`] that is `better` than all others.
+    fn extremum(
+        &self,
+        range: RangeInclusive<V>,
+        better: impl Fn(V, V) -> bool,
+    ) -> Result<(V, V), InterpolationError<V>> {
+        let (mut start, mut end) = range.into_inner();
+        if end < start {
+            (start, end) = (end, start);
+        }
+        let (first, last) = (
+            segment_index(&self.breakpoints, start)?,
+            segment_index(&self.breakpoints, end)?,
+        );
+        let derivative = self.derivative();
+        let mut best = (start, self.value_in_segment(first, start));
+        for i in first..=last {
+            let x0 = self.breakpoints[i];
+            let (low, high) = (
+                if i == first { start } else { x0 },
+                if i == last {
+                    end
+                } else {
+                    self.breakpoints[i + 1]
+                },
+            );
+            let stationary = roots_in(derivative.coefficients(i), low - x0, high - x0);
+            for x in stationary
+                .into_iter()
+                .map(|t| x0 + t)
+                .chain([low, high])
+                .filter(|&x| low <= x && x <= high)
+            {
+                let value = self.value_in_segment(i, x);
+                if better(value, best.1) {
+                    best = (x, value);
+                }
+            }
+        }
+        Ok(best)
+    }
+
+    fn value_in_segment(&self, i: usize, x: V) -> V {
+        record_segment_evaluation();
+        horner(self.coefficients(i), x - self.breakpoints[i])
+    }
+}
+
+impl<V: TranscendentalValue> PiecewisePolynomial<V> {
+    /// Returns the length of the graph `(x, p(x))` over the breakpoints.
+    ///
+    /// The length mixes the units of `x` and `p(x)`, so it depends on their relative scale.
+    pub fn arc_length(&self) -> V {
+        self.graph_arc_length().total()
+    }
+
+    /// Returns `n` points `(x, p(x))` of the graph equally spaced by arc length, the ends of the
+    /// breakpoints included, at least 2, e.g. to render a dashed line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    ///
+    /// // flat on [0, 1], then rising at 45° on [1, 2]
+    /// let pp = PiecewisePolynomial::try_new(
+    ///     vec![0.0_f64, 1.0, 2.0],
+    ///     vec![vec![0.0, 0.0], vec![1.0, 0.0]],
+    /// )
+    /// .unwrap();
+    /// assert!((pp.arc_length() - (1.0 + 2.0_f64.sqrt())).abs() < 1e-12);
+    /// let points = pp.sample_evenly(3);
+    /// assert_eq!(points[0], (0.0, 0.0));
+    /// assert!((points[1].0 - (1.5 - 0.125_f64.sqrt())).abs() < 1e-12);
+    /// assert_eq!(points[2], (2.0, 1.0));
+    /// ```
+    pub fn sample_evenly(&self, n: usize) -> Vec<(V, V)> {
+        self.evenly_spaced_xs(n)
+            .into_iter()
+            .map(|x| {
+                let y = self
+                    .try_value(x)
+                    .expect("the samples lie inside the breakpoints");
+                (x, y)
+            })
+            .collect()
+    }
+
+    /// Returns the x-coordinates of the points of [`Self::sample_evenly`].
+    pub(crate) fn evenly_spaced_xs(&self, n: usize) -> Vec<V> {
+        self.graph_arc_length().evenly_spaced_parameters(n)
+    }
+
+    fn graph_arc_length(&self) -> ArcLength<V, impl Fn(usize, V) -> V + '_> {
+        ArcLength::new(&self.breakpoints, |i, x| {
+            let slope = self.sample_in_segment(i, x).dydx;
+            (V::one() + slope * slope).sqrt()
+        })
+    }
+}
+
+impl_interpolator!(PiecewisePolynomial, PiecewisePolynomial, metadata);
+
+/// Writes each segment on its own line as its interval and polynomial in powers of `x - x_i`,
+/// lowest power first, e.g. `[0.5, 1): 0.25 + (x - 0.5) - 2(x - 0.5)^3`.
+///
+/// A precision, as in `{:.3}`, applies to every breakpoint and coefficient.
+impl<V: InterpolationValue + Display> Display for PiecewisePolynomial<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pretty::write_piecewise(f, self, Notation::Plain, f.precision())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V: InterpolationValue + serde::Serialize> serde::Serialize for PiecewisePolynomial<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PolynomialDocument {
+            breakpoints: self.breakpoints.clone(),
+            coefficients: self
+                .coefficients
+                .chunks(self.order)
+                .map(<[V]>::to_vec)
+                .collect(),
+            metadata: self.metadata.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Validates the deserialized breakpoints and coefficients as [`PiecewisePolynomial::try_new`].
+#[cfg(feature = "serde")]
+impl<'de, V: InterpolationValue + Display + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for PiecewisePolynomial<V>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = PolynomialDocument::deserialize(deserializer)?;
+        Ok(Self::try_new(document.breakpoints, document.coefficients)
+            .map_err(serde::de::Error::custom)?
+            .with_metadata(document.metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_shape_validation() {
+        assert!(matches!(
+            PiecewisePolynomial::try_new(vec![0.0, 1.0], vec![vec![1.0], vec![2.0]]),
+            Err(InterpolationError::CoefficientShapeError)
+        ));
+        assert!(matches!(
+            PiecewisePolynomial::try_new(vec![0.0, 1.0, 2.0], vec![vec![1.0], vec![2.0, 1.0]]),
+            Err(InterpolationError::CoefficientShapeError)
+        ));
+        assert!(matches!(
+            PiecewisePolynomial::try_new(vec![1.0, 0.0], vec![vec![1.0]]),
+            Err(InterpolationError::PointOrderError(1))
+        ));
+    }
+
+    #[test]
+    fn test_calculus() {
+        // x^2 on [0, 1], continued by its tangent 1 + 2(x - 1) on [1, 2]
+        let pp = PiecewisePolynomial::try_new(
+            vec![0.0_f64, 1.0, 2.0],
+            vec![vec![1.0, 0.0, 0.0], vec![0.0, 2.0, 1.0]],
+        )
+        .unwrap();
+        assert!(matches!(
+            pp.try_value(2.5),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+        assert_eq!(pp.try_value(2.0).unwrap(), 3.0);
+
+        let derivative = pp.derivative();
+        assert_eq!(derivative.degree(), 1);
+        assert_eq!(derivative.try_value(0.5).unwrap(), 1.0);
+        assert_eq!(derivative.try_value(1.5).unwrap(), 2.0);
+
+        // 1/3 + (1 + 2) / 2 * 1
+        let integral = pp.integrate(0.0, 2.0).unwrap();
+        assert!((integral - (1.0 / 3.0 + 2.0)).abs() < f64::EPSILON);
+        assert!((pp.integrate(2.0, 0.0).unwrap() + integral).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_conversion() {
+        let xs = [0.0_f64, 0.3, 0.5, 1.2, 1.5];
+        let ys = [1.0, 0.2, 0.5, -0.4, 0.0];
+        let probes = [0.1, 0.3, 0.45, 0.9, 1.3, 1.5];
+
+        let points: Vec<_> = xs.iter().copied().zip(ys).collect();
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        let pp = PiecewisePolynomial::from(&natural);
+        for x in probes {
+            let expected = natural.try_value(x).unwrap();
+            assert!((pp.try_value(x).unwrap() - expected).abs() < 1e-12);
+        }
+
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        let pp = PiecewisePolynomial::from(&catmull_rom);
+        for x in probes {
+            let expected = catmull_rom.try_value(x).unwrap();
+            assert!((pp.try_value(x).unwrap() - expected).abs() < 1e-12);
+        }
+
+        let points: Vec<_> = points.iter().map(|&(x, y)| (x, y, x - y)).collect();
+        let hermite = HermiteSpline::try_new(&points).unwrap();
+        let pp = PiecewisePolynomial::from(&hermite);
+        for x in probes {
+            let expected = hermite.try_value(x).unwrap();
+            assert!((pp.try_value(x).unwrap() - expected).abs() < 1e-12);
+        }
+        assert!((pp.derivative().try_value(0.3).unwrap() - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_display() {
+        let pp = PiecewisePolynomial::try_new(
+            vec![0.0, 0.5, 1.0],
+            vec![vec![-1.0, 3.0, -2.5, 1.0], vec![0.0, 0.0, 0.0, 0.0]],
+        )
+        .unwrap();
+        assert_eq!(
+            pp.to_string(),
+            "[0, 0.5): 1 - 2.5x + 3x^2 - x^3\n[0.5, 1]: 0"
+        );
+        let shifted =
+            PiecewisePolynomial::try_new(vec![-0.5, 1.0], vec![vec![0.25, -1.0 / 3.0]]).unwrap();
+        assert_eq!(
+            format!("{shifted:.2}"),
+            "[-0.50, 1.00]: -0.33 + 0.25(x + 0.50)"
+        );
+        assert_eq!(
+            shifted.to_latex(),
+            "\\begin{cases}\n-0.3333333333333333 + 0.25(x + 0.5) & -0.5 \\le x \\le 1\n\\end{cases}"
+        );
+
+        // the splines print their piecewise polynomial and debug-print their data
+        let spline = NaturalCubicSpline::try_new(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]).unwrap();
+        assert_eq!(
+            spline.to_string(),
+            "[0, 1): 1.5x - 0.5x^3\n[1, 2]: 1 - 1.5(x - 1)^2 + 0.5(x - 1)^3"
+        );
+        assert_eq!(
+            spline.to_latex(),
+            PiecewisePolynomial::from(&spline).to_latex()
+        );
+        assert!(format!("{spline:?}").starts_with(
+            "NaturalCubicSpline { xs: [0.0, 1.0, 2.0], ys: [0.0, 1.0, 0.0], \
+             second_derivatives: [0.0, -3.0, 0.0]"
+        ));
+    }
+
+    #[test]
+    fn test_intersections() {
+        // a P&L curve over the spot, with losses at both ends
+        let points = [
+            (80.0_f64, -5.0),
+            (90.0, -1.0),
+            (100.0, 2.0),
+            (110.0, 1.0),
+            (120.0, -3.0),
+        ];
+        let spline = NaturalCubicSpline::try_new(&points).unwrap();
+        let break_even = spline.intersect_level(0.0);
+        assert_eq!(break_even.len(), 2);
+        assert!(break_even[0] > 90.0 && break_even[0] < 100.0);
+        assert!(break_even[1] > 110.0 && break_even[1] < 120.0);
+        for x in break_even {
+            assert!(spline.try_value(x).unwrap().abs() < 1e-12);
+        }
+        // a hurdle growing with the spot
+        let hurdle = |x: f64| -4.0 + 0.05 * (x - 80.0);
+        let crossings = spline.intersect_line(-8.0, 0.05);
+        assert_eq!(crossings.len(), 2);
+        for x in crossings {
+            assert!((spline.try_value(x).unwrap() - hurdle(x)).abs() < 1e-12);
+        }
+        // a crossing at a knot is reported once
+        assert_eq!(
+            spline
+                .intersect_level(2.0)
+                .iter()
+                .filter(|&&x| x == 100.0)
+                .count(),
+            1
+        );
+        // piecewise constants
+        let steps =
+            PiecewisePolynomial::try_new(vec![0.0, 1.0, 2.0], vec![vec![-1.0], vec![1.0]]).unwrap();
+        assert!(steps.intersect_level(0.0).is_empty());
+        assert_eq!(steps.intersect_line(0.0, 1.0), vec![1.0]);
+    }
+
+    #[test]
+    fn test_extrema() {
+        // a slice of a calibration objective, lowest between the knots at 0.2 and 0.3
+        let points = [
+            (0.0_f64, 4.0),
+            (0.1, 2.0),
+            (0.2, 1.1),
+            (0.3, 1.2),
+            (0.5, 3.0),
+        ];
+        let spline = NaturalCubicSpline::try_new(&points).unwrap();
+        let (x, y) = spline.argmin(0.0..=0.5).unwrap();
+        assert!(x > 0.2 && x < 0.3);
+        assert_eq!(y, spline.try_value(x).unwrap());
+        let dense_min = (0..=5_000)
+            .map(|i| spline.try_value(i as f64 / 10_000.0).unwrap())
+            .fold(f64::INFINITY, f64::min);
+        assert!(y <= dense_min && dense_min - y < 1e-7);
+        assert_eq!(spline.argmax(0.5..=0.0).unwrap(), (0.0, 4.0));
+        assert_eq!(spline.argmin(0.05..=0.1).unwrap(), (0.1, 2.0));
+        assert!(matches!(
+            spline.argmax(0.1..=0.6),
+            Err(InterpolationError::OutOfUpperBound(0.6))
+        ));
+    }
+}
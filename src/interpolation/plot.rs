@@ -0,0 +1,163 @@
+//! Quick inspection of one-dimensional interpolants with the plotters crate.
+
+use crate::interpolation::interpolator::Interpolator;
+use crate::{InterpolationError, InterpolationValue};
+use num_traits::ToPrimitive;
+use plotters::prelude::*;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use thiserror::Error;
+
+/// The number of points sampled along the curve by [`plot`].
+const CURVE_SAMPLES: usize = 512;
+
+/// The error of [`plot`].
+#[derive(Error, Debug)]
+pub enum PlotError<V: InterpolationValue> {
+    /// The interpolant failed to evaluate, e.g. because the range exceeds its knots.
+    #[error(transparent)]
+    Interpolation(#[from] InterpolationError<V>),
+    /// The plot could not be drawn or written.
+    #[error("drawing failed: {0}")]
+    Drawing(String),
+}
+
+/// Samples `interpolator` at `samples` equally spaced points of `range`, at least 2, as a point
+/// series for e.g. plotters' `LineSeries`.
+///
+/// # Errors
+///
+/// Returns the error of a sample failing [`Interpolator::try_value`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "plotters")]
+/// # {
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::plot;
+///
+/// let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)]).unwrap();
+/// let series = plot::curve_series(&spline, 0.0..=1.0, 5).unwrap();
+/// assert_eq!(series[3], (0.75, 0.25));
+/// # }
+/// ```
+pub fn curve_series<V, I>(
+    interpolator: &I,
+    range: RangeInclusive<V>,
+    samples: usize,
+) -> Result<Vec<(f64, f64)>, InterpolationError<V>>
+where
+    V: InterpolationValue + ToPrimitive,
+    I: Interpolator<V> + ?Sized,
+{
+    let (start, end) = range.into_inner();
+    let last = samples.max(2) - 1;
+    let step = (end - start) / V::from_count(last);
+    (0..=last)
+        .map(|i| {
+            let x = if i == last {
+                end
+            } else {
+                start + step * V::from_count(i)
+            };
+            Ok((to_f64(x), to_f64(interpolator.try_value(x)?)))
+        })
+        .collect()
+}
+
+/// Draws `interpolator` over `range` as a line, overlaid with the `knots` as dots, into an SVG
+/// file at `path`.
+///
+/// # Errors
+///
+/// Returns `PlotError::Interpolation` if the interpolant fails to evaluate on `range`, and
+/// `PlotError::Drawing` if the file cannot be written.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[cfg(feature = "plotters")]
+/// # {
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::plot;
+///
+/// let smile = [(0.8, 0.24), (0.9, 0.21), (1.0, 0.2), (1.1, 0.205), (1.2, 0.22)];
+/// let spline = NaturalCubicSpline::try_new(&smile).unwrap();
+/// plot::plot(&spline, &smile, 0.8..=1.2, "smile.svg").unwrap();
+/// # }
+/// ```
+pub fn plot<V, I>(
+    interpolator: &I,
+    knots: &[(V, V)],
+    range: RangeInclusive<V>,
+    path: impl AsRef<Path>,
+) -> Result<(), PlotError<V>>
+where
+    V: InterpolationValue + ToPrimitive,
+    I: Interpolator<V> + ?Sized,
+{
+    let (x_min, x_max) = (to_f64(*range.start()), to_f64(*range.end()));
+    let curve = curve_series(interpolator, range, CURVE_SAMPLES)?;
+    let knots: Vec<(f64, f64)> = knots.iter().map(|&(x, y)| (to_f64(x), to_f64(y))).collect();
+    let (y_min, y_max) = curve
+        .iter()
+        .chain(&knots)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_, y)| {
+            (min.min(y), max.max(y))
+        });
+    // keep a flat curve off the border
+    let margin = ((y_max - y_min) * 0.05).max(1e-9);
+    let drawing = |error: &dyn std::error::Error| PlotError::Drawing(error.to_string());
+    let root = SVGBackend::new(path.as_ref(), (800, 600)).into_drawing_area();
+    root.fill(&WHITE).map_err(|e| drawing(&e))?;
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .build_cartesian_2d(x_min..x_max, y_min - margin..y_max + margin)
+        .map_err(|e| drawing(&e))?;
+    chart
+        .draw_series(LineSeries::new(curve, &BLUE))
+        .map_err(|e| drawing(&e))?;
+    chart
+        .draw_series(
+            knots
+                .into_iter()
+                .map(|knot| Circle::new(knot, 3, RED.filled())),
+        )
+        .map_err(|e| drawing(&e))?;
+    root.present().map_err(|e| drawing(&e))
+}
+
+/// Converts a coordinate for plotting, mapping the values beyond `f64` to NaN.
+fn to_f64<V: ToPrimitive>(v: V) -> f64 {
+    v.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::plot::{self, PlotError};
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_plot() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.0, 2.0), (3.0, 2.5)];
+        let spline = CatmullRomSpline::try_new(&points).unwrap();
+        let series = plot::curve_series(&spline, 0.0..=3.0, 7).unwrap();
+        assert_eq!(series.len(), 7);
+        assert_eq!(series[2], (1.0, 3.0));
+        assert_eq!(series[6], (3.0, 2.5));
+        let path = std::env::temp_dir().join("spline_interpolation_test_plot.svg");
+        plot::plot(&spline, &points, 0.0..=3.0, &path).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(svg.contains("<polyline"));
+        assert_eq!(svg.matches("<circle").count(), 4);
+        assert!(matches!(
+            plot::plot(&spline, &points, -1.0..=3.0, &path),
+            Err(PlotError::Interpolation(
+                InterpolationError::OutOfLowerBound(-1.0)
+            ))
+        ));
+    }
+}
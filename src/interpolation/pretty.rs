@@ -0,0 +1,113 @@
+//! Pretty-printing of piecewise polynomials, one interval and its polynomial at a time.
+
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::math::abs;
+use crate::InterpolationValue;
+use std::fmt::{self, Display, Write};
+
+/// How a piecewise polynomial is written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Notation {
+    /// `[0, 0.5): 1 - 2.5x + 3x^2 - x^3`, one interval per line.
+    Plain,
+    /// The rows of a LaTeX `cases` environment, `1 - 2.5x + 3x^{2} - x^{3} & 0 \le x < 0.5`.
+    Latex,
+}
+
+/// Writes every segment of `pp` with its interval, the scalars with `precision` decimals if given.
+pub(crate) fn write_piecewise<V: InterpolationValue + Display>(
+    out: &mut impl Write,
+    pp: &PiecewisePolynomial<V>,
+    notation: Notation,
+    precision: Option<usize>,
+) -> fmt::Result {
+    let number = |out: &mut dyn Write, v: V| match precision {
+        Some(precision) => write!(out, "{v:.precision$}"),
+        None => write!(out, "{v}"),
+    };
+    let n = pp.segment_count();
+    for i in 0..n {
+        let (start, end) = pp.segment_bounds(i);
+        // a breakpoint belongs to the segment starting there, except for the last one
+        let close = if i + 1 == n { ']' } else { ')' };
+        match notation {
+            Notation::Plain => {
+                if i > 0 {
+                    out.write_char('\n')?;
+                }
+                out.write_char('[')?;
+                number(out, start)?;
+                out.write_str(", ")?;
+                number(out, end)?;
+                write!(out, "{close}: ")?;
+                write_polynomial(out, pp.coefficients(i), start, notation, &number)?;
+            }
+            Notation::Latex => {
+                if i > 0 {
+                    out.write_str(" \\\\\n")?;
+                }
+                write_polynomial(out, pp.coefficients(i), start, notation, &number)?;
+                out.write_str(" & ")?;
+                number(out, start)?;
+                out.write_str(if close == ']' {
+                    " \\le x \\le "
+                } else {
+                    " \\le x < "
+                })?;
+                number(out, end)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the polynomial with the coefficients `coefficients`, highest power first, in powers of
+/// `x - origin`, lowest power first and leaving out the zero terms.
+fn write_polynomial<V: InterpolationValue>(
+    out: &mut dyn Write,
+    coefficients: &[V],
+    origin: V,
+    notation: Notation,
+    number: &dyn Fn(&mut dyn Write, V) -> fmt::Result,
+) -> fmt::Result {
+    let mut empty = true;
+    for (power, &c) in coefficients.iter().rev().enumerate() {
+        if c.is_zero() {
+            continue;
+        }
+        match (empty, c < V::zero()) {
+            (true, true) => out.write_char('-')?,
+            (true, false) => {}
+            (false, true) => out.write_str(" - ")?,
+            (false, false) => out.write_str(" + ")?,
+        }
+        empty = false;
+        let magnitude = abs(c);
+        if power == 0 || !magnitude.is_one() {
+            number(out, magnitude)?;
+        }
+        if power == 0 {
+            continue;
+        }
+        if origin.is_zero() {
+            out.write_char('x')?;
+        } else if origin < V::zero() {
+            out.write_str("(x + ")?;
+            number(out, -origin)?;
+            out.write_char(')')?;
+        } else {
+            out.write_str("(x - ")?;
+            number(out, origin)?;
+            out.write_char(')')?;
+        }
+        match (power, notation) {
+            (1, _) => {}
+            (_, Notation::Plain) => write!(out, "^{power}")?,
+            (_, Notation::Latex) => write!(out, "^{{{power}}}")?,
+        }
+    }
+    if empty {
+        out.write_char('0')?;
+    }
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use crate::math::dense_matrix::solve_dense;
+use crate::{InterpolationError, TranscendentalValue};
+
+/// The radial basis function `φ(r)` of an [`RbfInterpolator`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RbfKernel<V: TranscendentalValue> {
+    /// `φ(r) = r² ln r`, the minimal-bending-energy interpolant in 2D.
+    /// Needs [`PolynomialTail::Linear`] to be well-posed.
+    ThinPlateSpline,
+    /// `φ(r) = sqrt(1 + (εr)²)` with the shape parameter `ε`.
+    /// Needs at least [`PolynomialTail::Constant`] to be well-posed.
+    Multiquadric(V),
+    /// `φ(r) = exp(-(εr)²)` with the shape parameter `ε`.
+    Gaussian(V),
+}
+
+impl<V: TranscendentalValue> RbfKernel<V> {
+    /// Evaluates the kernel from the squared distance `r2`.
+    fn value(&self, r2: V) -> V {
+        match *self {
+            RbfKernel::ThinPlateSpline => {
+                if r2.is_zero() {
+                    V::zero()
+                } else {
+                    r2 * r2.ln() / V::from_integer(2)
+                }
+            }
+            RbfKernel::Multiquadric(epsilon) => (V::one() + epsilon * epsilon * r2).sqrt(),
+            RbfKernel::Gaussian(epsilon) => (-epsilon * epsilon * r2).exp(),
+        }
+    }
+}
+
+/// The polynomial added to the kernel expansion of an [`RbfInterpolator`].
+///
+/// The tail is reproduced exactly: e.g. with a linear tail, affine data is interpolated exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolynomialTail {
+    /// No polynomial term.
+    None,
+    /// A constant term.
+    Constant,
+    /// A constant and a linear term in every coordinate.
+    Linear,
+}
+
+impl PolynomialTail {
+    fn len(&self, dimension: usize) -> usize {
+        match self {
+            PolynomialTail::None => 0,
+            PolynomialTail::Constant => 1,
+            PolynomialTail::Linear => 1 + dimension,
+        }
+    }
+}
+
+/// Radial basis function interpolation of scattered data in `D` dimensions.
+///
+/// The interpolant is `s(x) = Σ w_i φ(|x - x_i|) + p(x)` where `p` is the optional polynomial
+/// tail; the weights solve a dense linear system, so construction is `O(n³)`.
+pub struct RbfInterpolator<V: TranscendentalValue, const D: usize> {
+    centers: Vec<[V; D]>,
+    weights: Vec<V>,
+    tail_coefficients: Vec<V>,
+    kernel: RbfKernel<V>,
+    tail: PolynomialTail,
+}
+
+impl<V: TranscendentalValue, const D: usize> RbfInterpolator<V, D> {
+    /// Constructs a new `RbfInterpolator` from scattered points.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - A slice of `(x, y)` tuples where `x` is a position in `D` dimensions.
+    /// * `kernel` - The radial basis function.
+    /// * `tail` - The polynomial tail.
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(n)` - If there are fewer points than
+    ///   coefficients in the polynomial tail, or no points at all.
+    /// * `InterpolationError::SingularMatrixError` - If the interpolation system cannot be solved,
+    ///   e.g. because two points share a position or the tail is not determined by the points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::rbf::{PolynomialTail, RbfInterpolator, RbfKernel};
+    ///
+    /// let points = [([0.0, 0.0], 1.0), ([1.0, 0.0], 2.0), ([0.0, 1.0], 0.0), ([1.0, 1.0], 3.0)];
+    /// let rbf =
+    ///     RbfInterpolator::try_new(&points, RbfKernel::ThinPlateSpline, PolynomialTail::Linear)
+    ///         .unwrap();
+    /// assert!((rbf.value([1.0, 0.0]) - 2.0_f64).abs() < 1e-12);
+    /// ```
+    pub fn try_new(
+        points: &[([V; D], V)],
+        kernel: RbfKernel<V>,
+        tail: PolynomialTail,
+    ) -> Result<Self, InterpolationError<V>> {
+        let n = points.len();
+        let m = tail.len(D);
+        if n == 0 || n < m {
+            return Err(InterpolationError::InsufficientPointsError(n));
+        }
+        let size = n + m;
+        let mut a = vec![V::zero(); size * size];
+        for (i, (xi, _)) in points.iter().enumerate() {
+            for (j, (xj, _)) in points.iter().enumerate() {
+                a[i * size + j] = kernel.value(squared_distance(xi, xj));
+            }
+            for (k, p) in tail_terms(tail, xi).enumerate() {
+                a[i * size + n + k] = p;
+                a[(n + k) * size + i] = p;
+            }
+        }
+        let mut b: Vec<_> = points.iter().map(|&(_, y)| y).collect();
+        b.resize(size, V::zero());
+        let mut coefficients = solve_dense(a, b).ok_or(InterpolationError::SingularMatrixError)?;
+        let tail_coefficients = coefficients.split_off(n);
+        Ok(Self {
+            centers: points.iter().map(|&(x, _)| x).collect(),
+            weights: coefficients,
+            tail_coefficients,
+            kernel,
+            tail,
+        })
+    }
+
+    /// Evaluates the interpolant at `x`.
+    pub fn value(&self, x: [V; D]) -> V {
+        let radial = self
+            .centers
+            .iter()
+            .zip(&self.weights)
+            .fold(V::zero(), |acc, (center, &w)| {
+                acc + w * self.kernel.value(squared_distance(&x, center))
+            });
+        tail_terms(self.tail, &x)
+            .zip(&self.tail_coefficients)
+            .fold(radial, |acc, (p, &c)| acc + p * c)
+    }
+}
+
+fn squared_distance<V: TranscendentalValue, const D: usize>(a: &[V; D], b: &[V; D]) -> V {
+    a.iter()
+        .zip(b)
+        .fold(V::zero(), |acc, (&p, &q)| acc + (p - q) * (p - q))
+}
+
+/// Yields the monomials of the tail at `x`: `1, x_1, ..., x_D` for a linear tail.
+fn tail_terms<V: TranscendentalValue, const D: usize>(
+    tail: PolynomialTail,
+    x: &[V; D],
+) -> impl Iterator<Item = V> + '_ {
+    std::iter::once(V::one())
+        .chain(x.iter().copied())
+        .take(tail.len(D))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::rbf::{PolynomialTail, RbfInterpolator, RbfKernel};
+    use crate::InterpolationError;
+    #[cfg(feature = "decimal")]
+    use rust_decimal::Decimal;
+
+    fn scattered() -> Vec<([f64; 2], f64)> {
+        [
+            [0.0, 0.0],
+            [1.0, 0.1],
+            [0.2, 0.9],
+            [0.8, 0.7],
+            [0.5, 0.4],
+            [0.1, 0.5],
+        ]
+        .iter()
+        .map(|&[x, y]: &[f64; 2]| ([x, y], (x * 3.0).sin() + y * y))
+        .collect()
+    }
+
+    #[test]
+    fn test_interpolates_data() {
+        let points = scattered();
+        for (kernel, tail) in [
+            (RbfKernel::ThinPlateSpline, PolynomialTail::Linear),
+            (RbfKernel::Multiquadric(2.0), PolynomialTail::Constant),
+            (RbfKernel::Gaussian(3.0), PolynomialTail::None),
+        ] {
+            let rbf = RbfInterpolator::try_new(&points, kernel, tail).unwrap();
+            for &(x, y) in &points {
+                assert!((rbf.value(x) - y).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_tail_reproduces_affine_data() {
+        let f = |[x, y, z]: [f64; 3]| 1.0 + 2.0 * x - y + 0.5 * z;
+        let positions = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.6, 0.3, 0.8],
+        ];
+        let points: Vec<_> = positions.iter().map(|&x| (x, f(x))).collect();
+        let rbf =
+            RbfInterpolator::try_new(&points, RbfKernel::ThinPlateSpline, PolynomialTail::Linear)
+                .unwrap();
+        for x in [[0.3, 0.3, 0.3], [2.0, -1.0, 0.5]] {
+            assert!((rbf.value(x) - f(x)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let points = [([0.0], 1.0)];
+        assert!(matches!(
+            RbfInterpolator::try_new(&points, RbfKernel::ThinPlateSpline, PolynomialTail::Linear),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+        let points = [([0.0], 1.0), ([0.0], 2.0)];
+        assert!(matches!(
+            RbfInterpolator::try_new(&points, RbfKernel::Gaussian(1.0), PolynomialTail::None),
+            Err(InterpolationError::SingularMatrixError)
+        ));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal() {
+        let points = [
+            ([Decimal::new(0, 0)], Decimal::new(1, 0)),
+            ([Decimal::new(5, 1)], Decimal::new(2, 0)),
+            ([Decimal::new(15, 1)], Decimal::new(0, 0)),
+        ];
+        let rbf = RbfInterpolator::try_new(
+            &points,
+            RbfKernel::Multiquadric(Decimal::new(1, 0)),
+            PolynomialTail::Constant,
+        )
+        .unwrap();
+        for (x, y) in points {
+            assert!((rbf.value(x) - y).abs() < Decimal::new(1, 20));
+        }
+        // the Gaussian kernel between distant centres underflows to zero rather than panicking
+        let distant = [
+            ([Decimal::new(0, 0)], Decimal::new(1, 0)),
+            ([Decimal::new(20, 0)], Decimal::new(2, 0)),
+        ];
+        let gaussian = RbfInterpolator::try_new(
+            &distant,
+            RbfKernel::Gaussian(Decimal::new(1, 0)),
+            PolynomialTail::None,
+        )
+        .unwrap();
+        for (x, y) in distant {
+            assert!((gaussian.value(x) - y).abs() < Decimal::new(1, 20));
+        }
+    }
+}
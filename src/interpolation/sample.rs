@@ -0,0 +1,97 @@
+//! The value and the derivatives of an interpolant at one point.
+
+use crate::InterpolationValue;
+use nalgebra::Vector4;
+
+/// The value, slope and curvature of a spline at one point, as returned by the `try_eval_all`
+/// methods from a single segment lookup.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+///
+/// let spline = NaturalCubicSpline::try_new(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]).unwrap();
+/// let sample = spline.try_eval_all(0.5).unwrap();
+/// assert_eq!(sample.value, 0.6875);
+/// assert_eq!(sample.dydx, 1.125);
+/// assert_eq!(sample.d2ydx2, -1.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplineSample<V: InterpolationValue> {
+    /// The value.
+    pub value: V,
+    /// The first derivative.
+    pub dydx: V,
+    /// The second derivative.
+    pub d2ydx2: V,
+}
+
+impl<V: InterpolationValue> SplineSample<V> {
+    /// Evaluates the cubic with coefficients of `(t^3, t^2, t, 1)` at `t = (x - x_i) / h`.
+    pub(crate) fn from_normalized_cubic(c: &Vector4<V>, t: V, h: V) -> Self {
+        let two = V::from_integer(2);
+        let three = V::from_integer(3);
+        Self {
+            value: ((c.x * t + c.y) * t + c.z) * t + c.w,
+            dydx: ((three * c.x * t + two * c.y) * t + c.z) / h,
+            d2ydx2: (three * two * c.x * t + two * c.y) / (h * h),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::interpolation::sample::SplineSample;
+    use crate::InterpolationError;
+
+    const XS: [f64; 6] = [0.0, 0.4, 1.0, 1.3, 2.1, 2.5];
+
+    /// Checks a spline's samples against its piecewise polynomial and the derivatives of that.
+    fn assert_samples(
+        polynomial: PiecewisePolynomial<f64>,
+        sample: impl Fn(f64) -> SplineSample<f64>,
+    ) {
+        let first = polynomial.derivative();
+        let second = first.derivative();
+        for x in (0..=25).map(|i| i as f64 * 0.1) {
+            let sample = sample(x);
+            // the derivatives at a knot are those of the following segment
+            let x_right = if XS.contains(&x) && x < 2.5 {
+                x + 1e-12
+            } else {
+                x
+            };
+            assert!((sample.value - polynomial.try_value(x).unwrap()).abs() < 1e-12);
+            assert!((sample.dydx - first.try_value(x_right).unwrap()).abs() < 1e-9);
+            assert!((sample.d2ydx2 - second.try_value(x_right).unwrap()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_matches_polynomial_derivatives() {
+        let points: Vec<_> = XS.iter().map(|&x| (x, (2.0 * x).sin())).collect();
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        assert_samples((&natural).into(), |x| natural.try_eval_all(x).unwrap());
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        assert_samples((&catmull_rom).into(), |x| {
+            catmull_rom.try_eval_all(x).unwrap()
+        });
+        let hermite_points: Vec<_> = points
+            .iter()
+            .map(|&(x, y)| (x, y, 2.0 * (2.0 * x).cos()))
+            .collect();
+        let hermite = HermiteSpline::try_new(&hermite_points).unwrap();
+        assert_samples((&hermite).into(), |x| hermite.try_eval_all(x).unwrap());
+        let polynomial = PiecewisePolynomial::from(&natural);
+        assert_samples(polynomial.clone(), |x| polynomial.try_eval_all(x).unwrap());
+        assert!(matches!(
+            natural.try_eval_all(3.0),
+            Err(InterpolationError::OutOfUpperBound(3.0))
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+//! The serde forms of the splines and piecewise polynomials.
+//!
+//! A spline is stored as the points it interpolates, with its tolerance and metadata, and is
+//! fitted again when deserialized, so a document cannot describe an invalid spline. Omitted
+//! tolerances and metadata take their defaults. With `serde_json`, its `float_roundtrip` feature
+//! makes the floats read back bit for bit.
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+//! use spline_interpolation::metadata::Metadata;
+//!
+//! let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)])
+//!     .unwrap()
+//!     .with_metadata(Metadata::new().with_name("discount"));
+//! let json = serde_json::to_string(&spline).unwrap();
+//! assert_eq!(
+//!     json,
+//!     r#"{"points":[[0.0,1.0],[0.5,0.5],[1.0,0.0]],"tolerance":"Exact","metadata":{"name":"discount","source":null,"built_at":null,"attributes":{}}}"#
+//! );
+//! let restored: NaturalCubicSpline<f64> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(restored.metadata().name(), Some("discount"));
+//! assert_eq!(restored.try_value(0.75).unwrap(), 0.25);
+//! # }
+//! ```
+
+use crate::metadata::Metadata;
+use crate::tolerance::Tolerance;
+use crate::InterpolationValue;
+use serde::{Deserialize, Serialize};
+
+/// The serde form of a spline through the points `P`.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "P: Serialize, V: Serialize",
+    deserialize = "P: Deserialize<'de>, V: Deserialize<'de>"
+))]
+pub(crate) struct SplineDocument<P, V: InterpolationValue> {
+    pub(crate) points: Vec<P>,
+    #[serde(default)]
+    pub(crate) tolerance: Tolerance<V>,
+    #[serde(default)]
+    pub(crate) metadata: Metadata,
+}
+
+/// The serde form of a piecewise polynomial, with its coefficient rows highest power first.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(serialize = "V: Serialize", deserialize = "V: Deserialize<'de>"))]
+pub(crate) struct PolynomialDocument<V> {
+    pub(crate) breakpoints: Vec<V>,
+    pub(crate) coefficients: Vec<Vec<V>>,
+    #[serde(default)]
+    pub(crate) metadata: Metadata,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::metadata::Metadata;
+    use crate::tolerance::Tolerance;
+
+    fn metadata() -> Metadata {
+        Metadata::new()
+            .with_name("curve")
+            .with_source("test")
+            .with_attribute("currency", "JPY")
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let points = [(0.0, 1.0), (0.5, 0.5), (1.25, 2.0), (2.0, -1.0)];
+        let queries = [0.0, 0.3, 0.75, 1.6, 2.0];
+
+        let natural = NaturalCubicSpline::try_new(&points)
+            .unwrap()
+            .with_tolerance(Tolerance::Ulps(2))
+            .with_metadata(metadata());
+        let json = serde_json::to_string(&natural).unwrap();
+        let restored: NaturalCubicSpline<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.metadata(), &metadata());
+        assert!(json.contains(r#""tolerance":{"Ulps":2}"#));
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        for x in queries {
+            assert_eq!(
+                restored.try_value(x).unwrap(),
+                natural.try_value(x).unwrap()
+            );
+        }
+
+        let catmull_rom = CatmullRomSpline::try_new(&points)
+            .unwrap()
+            .with_metadata(metadata());
+        let json = serde_json::to_string(&catmull_rom).unwrap();
+        let restored: CatmullRomSpline<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.metadata(), &metadata());
+        for x in queries {
+            assert_eq!(
+                restored.try_value(x).unwrap(),
+                catmull_rom.try_value(x).unwrap()
+            );
+        }
+
+        let slopes: Vec<_> = points.iter().map(|&(x, y)| (x, y, x - y)).collect();
+        let hermite = HermiteSpline::try_new(&slopes)
+            .unwrap()
+            .with_metadata(metadata());
+        let json = serde_json::to_string(&hermite).unwrap();
+        let restored: HermiteSpline<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.metadata(), &metadata());
+        for x in queries {
+            assert_eq!(
+                restored.try_value(x).unwrap(),
+                hermite.try_value(x).unwrap()
+            );
+        }
+
+        let pp = PiecewisePolynomial::from(&natural);
+        let json = serde_json::to_string(&pp).unwrap();
+        let restored: PiecewisePolynomial<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, pp);
+        assert_eq!(restored.metadata(), &metadata());
+    }
+
+    #[test]
+    fn test_defaults_and_validation() {
+        let spline: CatmullRomSpline<f64> =
+            serde_json::from_str(r#"{"points": [[0.0, 0.0], [1.0, 2.0]]}"#).unwrap();
+        assert_eq!(spline.metadata(), &Metadata::default());
+        assert_eq!(spline.try_value(0.5).unwrap(), 1.0);
+
+        let error = serde_json::from_str::<NaturalCubicSpline<f64>>(
+            r#"{"points": [[0.0, 0.0], [1.0, 2.0], [1.0, 3.0]]}"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("duplicate x-coordinate"));
+        assert!(serde_json::from_str::<PiecewisePolynomial<f64>>(
+            r#"{"breakpoints": [0.0, 1.0, 2.0], "coefficients": [[1.0, 0.0]]}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Shape constraints on interpolating splines and the Hermite data enforcing them.
+
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::{InterpolationError, InterpolationValue};
+
+/// A shape that a spline built with [`HermiteSpline::try_new_with_shape`] is guaranteed to have
+/// over its whole domain.
+///
+/// [`HermiteSpline::try_new_with_shape`]: crate::interpolation::hermite_spline::HermiteSpline::try_new_with_shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// Non-decreasing, e.g. a cumulative distribution function.
+    Increasing,
+    /// Non-increasing, e.g. a discount curve.
+    Decreasing,
+    /// Convex: the slope never decreases.
+    Convex,
+    /// Concave: the slope never increases.
+    Concave,
+}
+
+impl Shape {
+    /// Returns the knots `(x, y, dydx)` of a Hermite spline through the sorted points `(xs, ys)`
+    /// having this shape, or `ShapeError(i)` naming the first point around which none exists.
+    ///
+    /// The monotone shapes keep the knots and start from the slopes of the natural cubic spline,
+    /// clamped into the box `[0, 3 δ]` of both neighbouring secants `δ`, inside the monotonicity
+    /// region of Fritsch and Carlson. A C¹ cubic cannot always follow convex data, so the convex
+    /// shapes build Schumaker's quadratic spline instead: slopes between the neighbouring secants,
+    /// and one extra knot in the segments where a single parabola does not fit.
+    ///
+    /// Needs at least 2 points, finite and strictly ascending.
+    pub(crate) fn knots<V: InterpolationValue>(
+        &self,
+        xs: &[V],
+        ys: &[V],
+    ) -> Result<Vec<(V, V, V)>, InterpolationError<V>> {
+        let flipped: Vec<V>;
+        let ys = match self {
+            Shape::Increasing | Shape::Convex => ys,
+            Shape::Decreasing | Shape::Concave => {
+                flipped = ys.iter().map(|&y| -y).collect();
+                &flipped
+            }
+        };
+        let secants: Vec<V> = (0..xs.len() - 1)
+            .map(|j| (ys[j + 1] - ys[j]) / (xs[j + 1] - xs[j]))
+            .collect();
+        let knots = match self {
+            Shape::Increasing | Shape::Decreasing => monotone_knots(xs, ys, &secants)?,
+            Shape::Convex | Shape::Concave => convex_knots(xs, ys, &secants)?,
+        };
+        Ok(match self {
+            Shape::Increasing | Shape::Convex => knots,
+            Shape::Decreasing | Shape::Concave => knots
+                .into_iter()
+                .map(|(x, y, dydx)| (x, -y, -dydx))
+                .collect(),
+        })
+    }
+}
+
+/// The knots of a non-decreasing Hermite spline through non-decreasing data.
+fn monotone_knots<V: InterpolationValue>(
+    xs: &[V],
+    ys: &[V],
+    secants: &[V],
+) -> Result<Vec<(V, V, V)>, InterpolationError<V>> {
+    if let Some(j) = secants.iter().position(|&secant| secant < V::zero()) {
+        return Err(InterpolationError::ShapeError(j + 1));
+    }
+    let natural = NaturalCubicSpline::try_new_xy(xs, ys)?;
+    let slopes: Vec<V> = xs
+        .iter()
+        .map(|&x| natural.try_eval_all(x).map(|sample| sample.dydx))
+        .collect::<Result<_, _>>()?;
+    let three = V::from_integer(3);
+    Ok(slopes
+        .into_iter()
+        .enumerate()
+        .map(|(i, slope)| {
+            // the steepest slope keeping both neighbouring segments monotone
+            let bound = [i.checked_sub(1), (i < secants.len()).then_some(i)]
+                .into_iter()
+                .flatten()
+                .map(|j| three * secants[j])
+                .reduce(|a, b| if b < a { b } else { a })
+                .unwrap_or_else(V::zero);
+            let slope = if slope < V::zero() {
+                V::zero()
+            } else if slope > bound {
+                bound
+            } else {
+                slope
+            };
+            (xs[i], ys[i], slope)
+        })
+        .collect())
+}
+
+/// The knots of a convex spline of at most quadratic segments through convex data.
+fn convex_knots<V: InterpolationValue>(
+    xs: &[V],
+    ys: &[V],
+    secants: &[V],
+) -> Result<Vec<(V, V, V)>, InterpolationError<V>> {
+    if let Some(j) = secants.windows(2).position(|pair| pair[1] < pair[0]) {
+        return Err(InterpolationError::ShapeError(j + 1));
+    }
+    let segments = secants.len();
+    // the segments of a collinear run are straight, and so are the slopes at their ends
+    let straight: Vec<bool> = (0..segments)
+        .map(|j| {
+            (j > 0 && secants[j - 1] == secants[j])
+                || (j + 1 < segments && secants[j + 1] == secants[j])
+        })
+        .collect();
+    let mut slopes = vec![secants[0]; segments + 1];
+    for i in 1..segments {
+        slopes[i] = match (straight[i - 1], straight[i]) {
+            (true, true) if secants[i - 1] != secants[i] => {
+                return Err(InterpolationError::ShapeError(i));
+            }
+            (true, _) => secants[i - 1],
+            (_, true) => secants[i],
+            // the slope of the parabola through the three points, strictly between the secants
+            _ => {
+                let (h0, h1) = (xs[i] - xs[i - 1], xs[i + 1] - xs[i]);
+                (h1 * secants[i - 1] + h0 * secants[i]) / (h0 + h1)
+            }
+        };
+    }
+    if segments > 1 {
+        // a single parabola on the end segments
+        let two = V::from_integer(2);
+        slopes[0] = two * secants[0] - slopes[1];
+        slopes[segments] = two * secants[segments - 1] - slopes[segments - 1];
+    }
+    let mut knots = vec![(xs[0], ys[0], slopes[0])];
+    for j in 0..segments {
+        let (s0, s1, secant) = (slopes[j], slopes[j + 1], secants[j]);
+        let (below, above) = (secant - s0, s1 - secant);
+        if below != above {
+            // the extra knot at x_j + u h, with u in the middle of the range where its slope
+            // lies between s0 and s1
+            let two = V::from_integer(2);
+            let total = below + above;
+            let low = (above - below) / total;
+            let low = if low > V::zero() { low } else { V::zero() };
+            let high = two * above / total;
+            let high = if high < V::one() { high } else { V::one() };
+            let u = (low + high) / two;
+            let width = xs[j + 1] - xs[j];
+            let slope = two * secant - u * s0 - (V::one() - u) * s1;
+            knots.push((
+                xs[j] + u * width,
+                ys[j] + u * width * (s0 + slope) / two,
+                slope,
+            ));
+        }
+        knots.push((xs[j + 1], ys[j + 1], s1));
+    }
+    Ok(knots)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::shape::Shape;
+    use crate::tolerance::Tolerance;
+    use crate::InterpolationError;
+
+    /// Samples the value and slope of the spline densely over `[x0, x1]`.
+    fn samples(spline: &HermiteSpline<f64>, x0: f64, x1: f64) -> Vec<(f64, f64)> {
+        (0..=1000)
+            .map(|i| {
+                let sample = spline
+                    .try_eval_all(x0 + (x1 - x0) * i as f64 / 1000.0)
+                    .unwrap();
+                (sample.value, sample.dydx)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_monotone() {
+        // a discount curve with a flat stretch, where the natural spline overshoots
+        let points = [
+            (0.0, 1.0),
+            (0.5, 0.99),
+            (1.0, 0.97),
+            (2.0, 0.93),
+            (3.0, 0.93),
+            (5.0, 0.8),
+            (10.0, 0.55),
+        ];
+        let spline = HermiteSpline::try_new_with_shape(&points, Shape::Decreasing).unwrap();
+        for &(x, y) in &points {
+            assert_eq!(spline.try_value(x).unwrap(), y);
+        }
+        let values = samples(&spline, 0.0, 10.0);
+        assert!(values.windows(2).all(|pair| pair[1].0 <= pair[0].0 + 1e-15));
+        // a CDF rises from 0 to 1
+        let cdf: Vec<(f64, f64)> = [0.0, 0.0, 0.1, 0.5, 0.9, 1.0, 1.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &y)| (i as f64, y))
+            .collect();
+        let spline = HermiteSpline::try_new_with_shape(&cdf, Shape::Increasing).unwrap();
+        assert!(samples(&spline, 0.0, 6.0)
+            .iter()
+            .all(|&(value, slope)| (0.0..=1.0).contains(&value) && slope >= 0.0));
+        assert!(matches!(
+            HermiteSpline::try_new_with_shape(&cdf, Shape::Decreasing),
+            Err(InterpolationError::ShapeError(2))
+        ));
+    }
+
+    #[test]
+    fn test_convex() {
+        // convex data with a steep turn, which no C¹ cubic on these knots follows convexly
+        let points = [
+            (0.0_f64, 0.0),
+            (1.0, 0.0),
+            (2.0, 1.0),
+            (3.0, 101.0),
+            (4.0, 202.0),
+        ];
+        let spline = HermiteSpline::try_new_with_shape(&points, Shape::Convex).unwrap();
+        for &(x, y) in &points {
+            assert!((spline.try_value(x).unwrap() - y).abs() < 1e-12);
+        }
+        let slopes = samples(&spline, 0.0, 4.0);
+        assert!(slopes.windows(2).all(|pair| pair[1].1 >= pair[0].1 - 1e-9));
+        assert!(spline.continuity_report().is_c1(Tolerance::Absolute(1e-9)));
+        // a collinear run keeps straight segments
+        let points = [(0.0, 3.0), (1.0, 2.0), (2.0, 1.0), (3.0, 1.5), (4.0, 3.0)];
+        let spline = HermiteSpline::try_new_with_shape(&points, Shape::Convex).unwrap();
+        assert_eq!(spline.try_value(0.5).unwrap(), 2.5);
+        assert!(matches!(
+            HermiteSpline::try_new_with_shape(&points, Shape::Concave),
+            Err(InterpolationError::ShapeError(2))
+        ));
+        // two straight runs meeting at a kink leave no C¹ convex interpolant
+        let kinked = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 1.0), (4.0, 2.0)];
+        assert!(matches!(
+            HermiteSpline::try_new_with_shape(&kinked, Shape::Convex),
+            Err(InterpolationError::ShapeError(2))
+        ));
+    }
+}
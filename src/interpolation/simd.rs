@@ -0,0 +1,204 @@
+//! Batch evaluation of cubic piecewise polynomials with SIMD, for `f32` and `f64`.
+
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::segment_index;
+use crate::{InterpolationError, InterpolationValue};
+use wide::{f32x8, f64x4};
+
+/// A piecewise polynomial of degree at most 3 with its coefficients stored by power rather than
+/// by segment, so that a batch of queries is evaluated with one SIMD Horner scheme.
+///
+/// Queries are located one at a time, reusing the segment of the previous query when it still
+/// contains them, so sorted queries (as in resampling) cost amortized `O(1)` lookups each.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "simd")]
+/// # {
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+/// use spline_interpolation::interpolation::simd::SimdCubic;
+///
+/// let spline = NaturalCubicSpline::try_new(&[(0.0_f64, 1.0), (0.5, 0.5), (1.0, 0.0)]).unwrap();
+/// let cubic = SimdCubic::try_from_polynomial(&PiecewisePolynomial::from(&spline)).unwrap();
+/// let xs: Vec<f64> = (0..=100).map(|i| i as f64 / 100.0).collect();
+/// let values = cubic.try_values(&xs).unwrap();
+/// assert!((values[75] - 0.25).abs() < 1e-12);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimdCubic<V: InterpolationValue> {
+    breakpoints: Vec<V>,
+    // coefficients[k][i] multiplies (x - x_i)^(3 - k) on the i-th segment
+    coefficients: [Vec<V>; 4],
+}
+
+impl<V: InterpolationValue> SimdCubic<V> {
+    /// Rearranges the coefficients of a piecewise polynomial of degree at most 3.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::CoefficientShapeError` if the degree exceeds 3.
+    pub fn try_from_polynomial(
+        polynomial: &PiecewisePolynomial<V>,
+    ) -> Result<Self, InterpolationError<V>> {
+        let degree = polynomial.degree();
+        if degree > 3 {
+            return Err(InterpolationError::CoefficientShapeError);
+        }
+        let segment_count = polynomial.segment_count();
+        let coefficients = std::array::from_fn(|k| match (k + degree).checked_sub(3) {
+            Some(power) => (0..segment_count)
+                .map(|i| polynomial.coefficients(i)[power])
+                .collect(),
+            None => vec![V::zero(); segment_count],
+        });
+        Ok(Self {
+            breakpoints: polynomial.breakpoints().to_vec(),
+            coefficients,
+        })
+    }
+
+    /// Evaluates the polynomial at a single `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` if `x` lies outside the breakpoints.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        let i = segment_index(&self.breakpoints, x)?;
+        let t = x - self.breakpoints[i];
+        Ok(self
+            .coefficients
+            .iter()
+            .fold(V::zero(), |acc, c| acc * t + c[i]))
+    }
+
+    /// Returns the segment containing `x`, trying `hint` first.
+    fn locate(&self, hint: usize, x: V) -> Result<usize, InterpolationError<V>> {
+        if self.breakpoints[hint] <= x && x < self.breakpoints[hint + 1] {
+            Ok(hint)
+        } else {
+            segment_index(&self.breakpoints, x)
+        }
+    }
+}
+
+/// Implements the batch evaluation for a scalar type and its SIMD vector.
+macro_rules! impl_simd_cubic {
+    ($value:ty, $lanes:ty, $count:literal) => {
+        impl SimdCubic<$value> {
+            /// Evaluates the polynomial at every `xs[i]` into `out[i]`, without allocating.
+            ///
+            /// # Errors
+            ///
+            /// Returns `LengthMismatchError(xs.len(), out.len())` if the slices differ in length,
+            /// and otherwise the error of the first query outside the breakpoints; `out` is then
+            /// partially written.
+            pub fn try_values_into(
+                &self,
+                xs: &[$value],
+                out: &mut [$value],
+            ) -> Result<(), InterpolationError<$value>> {
+                if xs.len() != out.len() {
+                    return Err(InterpolationError::LengthMismatchError(xs.len(), out.len()));
+                }
+                let mut segment = 0;
+                let mut chunks = xs.chunks_exact($count);
+                let mut outs = out.chunks_exact_mut($count);
+                for (xs, out) in (&mut chunks).zip(&mut outs) {
+                    let mut t = [0.0; $count];
+                    let mut c = [[0.0; $count]; 4];
+                    for (lane, &x) in xs.iter().enumerate() {
+                        segment = self.locate(segment, x)?;
+                        t[lane] = x - self.breakpoints[segment];
+                        for (c, coefficients) in c.iter_mut().zip(&self.coefficients) {
+                            c[lane] = coefficients[segment];
+                        }
+                    }
+                    let t = <$lanes>::new(t);
+                    let value = c[1..].iter().fold(<$lanes>::new(c[0]), |acc, &c| {
+                        acc.mul_add(t, <$lanes>::new(c))
+                    });
+                    out.copy_from_slice(&value.to_array());
+                }
+                for (&x, out) in chunks.remainder().iter().zip(outs.into_remainder()) {
+                    *out = self.try_value(x)?;
+                }
+                Ok(())
+            }
+
+            /// Evaluates the polynomial at every `xs[i]`.
+            ///
+            /// # Errors
+            ///
+            /// Returns the error of the first query outside the breakpoints.
+            pub fn try_values(
+                &self,
+                xs: &[$value],
+            ) -> Result<Vec<$value>, InterpolationError<$value>> {
+                let mut out = vec![0.0; xs.len()];
+                self.try_values_into(xs, &mut out)?;
+                Ok(out)
+            }
+        }
+    };
+}
+
+impl_simd_cubic!(f32, f32x8, 8);
+impl_simd_cubic!(f64, f64x4, 4);
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::interpolation::simd::SimdCubic;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_matches_scalar_evaluation() {
+        let points: Vec<(f64, f64)> = (0..20)
+            .map(|i| (i as f64 * 0.7, (i as f64 * 0.3).sin()))
+            .collect();
+        let polynomial = PiecewisePolynomial::from(&CatmullRomSpline::try_new(&points).unwrap());
+        let cubic = SimdCubic::try_from_polynomial(&polynomial).unwrap();
+        // unsorted, hitting both ends, and not a multiple of the lane count
+        let end = points[19].0;
+        let xs: Vec<f64> = (0..=133)
+            .map(|i| (i * 37 % 134) as f64 / 133.0 * end)
+            .collect();
+        let values = cubic.try_values(&xs).unwrap();
+        for (&x, value) in xs.iter().zip(values) {
+            assert!((value - polynomial.try_value(x).unwrap()).abs() < 1e-12);
+        }
+        let mut out = [0.0; 5];
+        assert!(matches!(
+            cubic.try_values_into(&xs[..4], &mut out),
+            Err(InterpolationError::LengthMismatchError(4, 5))
+        ));
+        assert!(matches!(
+            cubic.try_values_into(&[0.0, 1.0, 14.0, 2.0, 3.0], &mut out),
+            Err(InterpolationError::OutOfUpperBound(14.0))
+        ));
+    }
+
+    #[test]
+    fn test_low_degree_f32() {
+        // x^2 on [0, 1] and 1 + 2(x - 1) on [1, 2]
+        let polynomial = PiecewisePolynomial::try_new(
+            vec![0.0_f32, 1.0, 2.0],
+            vec![vec![1.0, 0.0, 0.0], vec![0.0, 2.0, 1.0]],
+        )
+        .unwrap();
+        let cubic = SimdCubic::try_from_polynomial(&polynomial).unwrap();
+        let xs: Vec<f32> = (0..=20).map(|i| i as f32 / 10.0).collect();
+        for (&x, value) in xs.iter().zip(cubic.try_values(&xs).unwrap()) {
+            assert!((value - polynomial.try_value(x).unwrap()).abs() < 1e-6);
+        }
+        let quartic = PiecewisePolynomial::try_new(vec![0.0_f32, 1.0], vec![vec![1.0; 5]]).unwrap();
+        assert!(matches!(
+            SimdCubic::try_from_polynomial(&quartic),
+            Err(InterpolationError::CoefficientShapeError)
+        ));
+    }
+}
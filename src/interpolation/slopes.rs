@@ -0,0 +1,203 @@
+use crate::math::abs;
+use crate::InterpolationValue;
+
+/// A rule estimating the slope at each knot of a Hermite spline from the surrounding points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlopeMethod {
+    /// The three-point finite difference: the average of the secants on both sides of the knot,
+    /// and the one-sided secant at the ends.
+    FiniteDifference,
+    /// The Catmull–Rom rule: the slope of the chord between the neighbouring knots, and the
+    /// one-sided secant at the ends.
+    CatmullRom,
+    /// Akima's rule, which weights neighbouring secants by how much their neighbours vary,
+    /// suppressing the wiggles of global splines around outliers.
+    Akima,
+    /// The monotonicity-preserving rule of Fritsch and Butland (scipy's `PchipInterpolator`):
+    /// the interpolant never overshoots the data.
+    Pchip,
+}
+
+impl SlopeMethod {
+    /// Returns how many points after the `i`-th one its slope depends on.
+    ///
+    /// The slope at a knot is final once this many further points exist; before that it is
+    /// computed with end conditions that the next points will replace.
+    pub fn lookahead(&self) -> usize {
+        match self {
+            SlopeMethod::Akima => 2,
+            SlopeMethod::FiniteDifference | SlopeMethod::CatmullRom | SlopeMethod::Pchip => 1,
+        }
+    }
+
+    /// Computes the slope at the `i`-th of the sorted points `(xs, ys)`, treating the ends of the
+    /// slices as the ends of the data.
+    ///
+    /// Needs at least 2 points.
+    pub(crate) fn slope<V: InterpolationValue>(&self, xs: &[V], ys: &[V], i: usize) -> V {
+        debug_assert!(xs.len() >= 2 && xs.len() == ys.len());
+        if xs.len() == 2 {
+            return secant(xs, ys, 0);
+        }
+        let n = xs.len();
+        match self {
+            SlopeMethod::FiniteDifference | SlopeMethod::CatmullRom if i == 0 => secant(xs, ys, 0),
+            SlopeMethod::FiniteDifference | SlopeMethod::CatmullRom if i == n - 1 => {
+                secant(xs, ys, n - 2)
+            }
+            SlopeMethod::FiniteDifference => {
+                (secant(xs, ys, i - 1) + secant(xs, ys, i)) / V::from_integer(2)
+            }
+            SlopeMethod::CatmullRom => (ys[i + 1] - ys[i - 1]) / (xs[i + 1] - xs[i - 1]),
+            SlopeMethod::Akima => akima_slope(xs, ys, i),
+            SlopeMethod::Pchip => pchip_slope(xs, ys, i),
+        }
+    }
+}
+
+fn secant<V: InterpolationValue>(xs: &[V], ys: &[V], j: usize) -> V {
+    (ys[j + 1] - ys[j]) / (xs[j + 1] - xs[j])
+}
+
+fn same_sign<V: InterpolationValue>(a: V, b: V) -> bool {
+    (a > V::zero() && b > V::zero()) || (a < V::zero() && b < V::zero())
+}
+
+/// The `j`-th secant, extended linearly past both ends as in Akima's original paper.
+fn akima_secant<V: InterpolationValue>(xs: &[V], ys: &[V], j: isize) -> V {
+    let last = xs.len() as isize - 2;
+    let two = V::from_integer(2);
+    let three = V::from_integer(3);
+    match j {
+        -2 => three * secant(xs, ys, 0) - two * secant(xs, ys, 1),
+        -1 => two * secant(xs, ys, 0) - secant(xs, ys, 1),
+        j if j == last + 1 => {
+            two * secant(xs, ys, last as usize) - secant(xs, ys, last as usize - 1)
+        }
+        j if j == last + 2 => {
+            three * secant(xs, ys, last as usize) - two * secant(xs, ys, last as usize - 1)
+        }
+        j => secant(xs, ys, j as usize),
+    }
+}
+
+fn akima_slope<V: InterpolationValue>(xs: &[V], ys: &[V], i: usize) -> V {
+    let i = i as isize;
+    let m = |j| akima_secant(xs, ys, j);
+    let (m0, m1, m2, m3) = (m(i - 2), m(i - 1), m(i), m(i + 1));
+    let w1 = abs(m3 - m2);
+    let w2 = abs(m1 - m0);
+    if (w1 + w2).is_zero() {
+        (m1 + m2) / V::from_integer(2)
+    } else {
+        (w1 * m1 + w2 * m2) / (w1 + w2)
+    }
+}
+
+fn pchip_slope<V: InterpolationValue>(xs: &[V], ys: &[V], i: usize) -> V {
+    let n = xs.len();
+    if i == 0 {
+        return pchip_end_slope(
+            xs[1] - xs[0],
+            xs[2] - xs[1],
+            secant(xs, ys, 0),
+            secant(xs, ys, 1),
+        );
+    }
+    if i == n - 1 {
+        return pchip_end_slope(
+            xs[n - 1] - xs[n - 2],
+            xs[n - 2] - xs[n - 3],
+            secant(xs, ys, n - 2),
+            secant(xs, ys, n - 3),
+        );
+    }
+    let (h0, h1) = (xs[i] - xs[i - 1], xs[i + 1] - xs[i]);
+    let (d0, d1) = (secant(xs, ys, i - 1), secant(xs, ys, i));
+    if !same_sign(d0, d1) {
+        return V::zero();
+    }
+    let two = V::from_integer(2);
+    let w0 = two * h1 + h0;
+    let w1 = h1 + two * h0;
+    (w0 + w1) / (w0 / d0 + w1 / d1)
+}
+
+/// The shape-preserving three-point end condition, where `h0`, `d0` belong to the end interval.
+fn pchip_end_slope<V: InterpolationValue>(h0: V, h1: V, d0: V, d1: V) -> V {
+    let two = V::from_integer(2);
+    let three = V::from_integer(3);
+    let d = ((two * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+    if !same_sign(d, d0) {
+        V::zero()
+    } else if !same_sign(d0, d1) && abs(d) > abs(three * d0) {
+        three * d0
+    } else {
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::slopes::SlopeMethod;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_linear_data() {
+        let xs = [0.0, 0.5, 1.5, 2.0, 3.5];
+        let ys: Vec<f64> = xs.iter().map(|x| 1.0 - 2.0 * x).collect();
+        for method in [
+            SlopeMethod::FiniteDifference,
+            SlopeMethod::CatmullRom,
+            SlopeMethod::Akima,
+            SlopeMethod::Pchip,
+        ] {
+            for i in 0..xs.len() {
+                assert!((method.slope(&xs, &ys, i) + 2.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_uneven_spacing() {
+        let xs = [0.0, 1.0, 3.0];
+        let ys = [0.0, 1.0, 5.0];
+        assert_eq!(SlopeMethod::FiniteDifference.slope(&xs, &ys, 1), 1.5);
+        assert_eq!(SlopeMethod::CatmullRom.slope(&xs, &ys, 1), 5.0 / 3.0);
+        assert_eq!(SlopeMethod::CatmullRom.slope(&xs, &ys, 0), 1.0);
+        assert_eq!(SlopeMethod::CatmullRom.slope(&xs, &ys, 2), 2.0);
+    }
+
+    #[test]
+    fn test_pchip_flattens_extrema() {
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [0.0, 1.0, 0.5, 2.0];
+        assert_eq!(SlopeMethod::Pchip.slope(&xs, &ys, 1), 0.0);
+        assert_eq!(SlopeMethod::Pchip.slope(&xs, &ys, 2), 0.0);
+    }
+
+    #[test]
+    fn test_hermite_with_slopes() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (3.0, 5.0)];
+        let spline =
+            HermiteSpline::try_new_with_slope_method(&points, SlopeMethod::CatmullRom).unwrap();
+        let custom = HermiteSpline::try_new_with_slopes(&points, |i, points| match i {
+            1 => (points[2].1 - points[0].1) / (points[2].0 - points[0].0),
+            0 => 1.0,
+            _ => 2.0,
+        })
+        .unwrap();
+        for x in [0.0, 0.5, 1.0, 2.0, 3.0] {
+            assert_eq!(spline.try_value(x).unwrap(), custom.try_value(x).unwrap());
+        }
+        assert!(matches!(
+            HermiteSpline::try_new_with_slopes(&[(0.0, 0.0)], |_, _| 0.0),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+        assert!(matches!(
+            HermiteSpline::try_new_with_slope_method(&[(1.0, 0.0), (0.0, 0.0)], SlopeMethod::Akima),
+            Err(InterpolationError::PointOrderError(1))
+        ));
+    }
+}
@@ -0,0 +1,537 @@
+//! Cubic smoothing splines, trading the fit to noisy data against the roughness of the curve.
+
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::interpolation::{check_finite, check_knots};
+use crate::metadata::Metadata;
+use crate::{InterpolationError, InterpolationValue, TranscendentalValue};
+
+/// The natural cubic spline `f` minimizing `Σ w_i (y_i - f(x_i))² + λ ∫ f''(x)² dx`, fitted by
+/// Reinsch's algorithm.
+///
+/// A zero `λ` interpolates the points, and a growing `λ` flattens the curve towards the weighted
+/// least-squares line. With weights proportional to the inverse variances of the `y_i`, the noisy
+/// points pull less on the curve than the reliable ones.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::smoothing_spline::SmoothingSpline;
+///
+/// let points = [(0.0_f64, 0.0), (1.0, 1.2), (2.0, 1.8), (3.0, 3.1), (4.0, 4.0)];
+/// let exact = SmoothingSpline::try_new(&points, 0.0).unwrap();
+/// assert!((exact.try_value(1.0).unwrap() - 1.2).abs() < 1e-12);
+/// let smooth = SmoothingSpline::try_new(&points, 1.0).unwrap();
+/// assert!((smooth.try_value(1.0).unwrap() - 1.2).abs() > 0.01);
+/// ```
+pub struct SmoothingSpline<V: InterpolationValue> {
+    spline: NaturalCubicSpline<V>,
+    lambda: V,
+}
+
+impl<V: InterpolationValue> SmoothingSpline<V> {
+    /// Fits a `SmoothingSpline` with the roughness penalty `lambda` to equally weighted points.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new_weighted`].
+    pub fn try_new(points: &[(V, V)], lambda: V) -> Result<Self, InterpolationError<V>> {
+        Self::try_new_weighted(points, &vec![V::one(); points.len()], lambda)
+    }
+
+    /// Fits a `SmoothingSpline` with the roughness penalty `lambda` to points weighted by
+    /// `weights`, e.g. the inverse variances of their measurement errors.
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(n)` - If fewer than 3 points are given.
+    /// * `InterpolationError::LengthMismatchError(n, m)` - If the `n` points come with `m` weights.
+    /// * `InterpolationError::InvalidWeightError(i)` - If the `i`-th weight is not positive and
+    ///   finite.
+    /// * `InterpolationError::SmoothingParameterError(lambda)` - If `lambda` is negative or not
+    ///   finite.
+    /// * The errors of [`NaturalCubicSpline::try_new`] for unordered or non-finite points.
+    pub fn try_new_weighted(
+        points: &[(V, V)],
+        weights: &[V],
+        lambda: V,
+    ) -> Result<Self, InterpolationError<V>> {
+        let (xs, ys) = checked_columns(points, weights)?;
+        if !(lambda >= V::zero() && lambda.is_finite()) {
+            return Err(InterpolationError::SmoothingParameterError(lambda));
+        }
+        let fitted = fit(&xs, &ys, weights, lambda)?;
+        Ok(Self {
+            spline: NaturalCubicSpline::try_new_xy(&xs, &fitted.values)?,
+            lambda,
+        })
+    }
+
+    /// Returns the roughness penalty `λ`.
+    pub fn lambda(&self) -> V {
+        self.lambda
+    }
+
+    /// Returns the fitted curve.
+    pub fn spline(&self) -> &NaturalCubicSpline<V> {
+        &self.spline
+    }
+
+    /// Returns the fitted curve, dropping the penalty.
+    pub fn into_spline(self) -> NaturalCubicSpline<V> {
+        self.spline
+    }
+
+    /// Returns the metadata of the fitted curve.
+    pub fn metadata(&self) -> &Metadata {
+        self.spline.metadata()
+    }
+
+    /// Evaluates the fitted curve at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`NaturalCubicSpline::try_value`].
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        self.spline.try_value(x)
+    }
+}
+
+impl<V: TranscendentalValue> SmoothingSpline<V> {
+    /// Fits a `SmoothingSpline` to equally weighted points with the penalty `λ` chosen by
+    /// generalized cross-validation; see [`Self::fit_gcv_weighted`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::fit_gcv_weighted`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::smoothing_spline::SmoothingSpline;
+    ///
+    /// // sin(x) with a deterministic pseudo-noise
+    /// let points: Vec<(f64, f64)> = (0..40)
+    ///     .map(|i| {
+    ///         let x = i as f64 * 0.15;
+    ///         (x, x.sin() + 0.1 * ((i * 7919 % 13) as f64 / 6.0 - 1.0))
+    ///     })
+    ///     .collect();
+    /// let spline = SmoothingSpline::fit_gcv(&points).unwrap();
+    /// assert!(spline.lambda() > 0.0);
+    /// assert!((spline.try_value(3.0).unwrap() - 3.0_f64.sin()).abs() < 0.05);
+    /// ```
+    pub fn fit_gcv(points: &[(V, V)]) -> Result<Self, InterpolationError<V>> {
+        Self::fit_gcv_weighted(points, &vec![V::one(); points.len()])
+    }
+
+    /// Fits a `SmoothingSpline` to weighted points with the penalty `λ` minimizing the generalized
+    /// cross-validation score `n Σ w_i (y_i - f(x_i))² / (n - tr A(λ))²`, where the trace of the
+    /// hat matrix `A(λ)` counts the effective degrees of freedom of the fit.
+    ///
+    /// The search runs over `λ = ρ tr(R) / tr(Qᵀ W⁻¹ Q)`, which makes `ρ` free of the scales of
+    /// `x` and of the weights: a grid of `ρ` from `10⁻⁸` to `10⁸` brackets the lowest score, which a
+    /// golden-section search then refines.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::try_new_weighted`] other than the one on `λ`.
+    pub fn fit_gcv_weighted(
+        points: &[(V, V)],
+        weights: &[V],
+    ) -> Result<Self, InterpolationError<V>> {
+        let (xs, ys) = checked_columns(points, weights)?;
+        let q = second_differences(&xs);
+        let roughness = xs
+            .windows(3)
+            .fold(V::zero(), |acc, w| acc + (w[2] - w[0]) / V::from_integer(3));
+        let differences = (0..xs.len()).fold(V::zero(), |acc, k| {
+            q_row(&q, k).fold(acc, |acc, (_, a)| acc + a * a / weights[k])
+        });
+        let scale = roughness / differences;
+        let ln_ten = V::from_integer(10).ln();
+        let lambda = |exponent: V| scale * (exponent * ln_ten).exp();
+        // the score, or None where the fit fails or leaves no residual degrees of freedom
+        let score = |exponent: V| -> Option<(V, Fit<V>)> {
+            let fitted = fit(&xs, &ys, weights, lambda(exponent)).ok()?;
+            let n = V::from_count(xs.len());
+            let residual_freedom = n - fitted.hat_trace;
+            if !(residual_freedom.is_finite() && residual_freedom > V::zero()) {
+                return None;
+            }
+            let rss = (0..xs.len()).fold(V::zero(), |acc, k| {
+                let residual = ys[k] - fitted.values[k];
+                acc + weights[k] * residual * residual
+            });
+            Some((n * rss / (residual_freedom * residual_freedom), fitted))
+        };
+        let better = |a: &Option<(V, Fit<V>)>, b: &Option<(V, Fit<V>)>| match (a, b) {
+            (Some((a, _)), Some((b, _))) => a < b,
+            (Some(_), None) => true,
+            _ => false,
+        };
+        let half = V::one() / V::from_integer(2);
+        let grid_point = |i: usize| V::from_count(i) * half - V::from_integer(8);
+        let (mut best_index, mut best) = (0, score(grid_point(0)));
+        for i in 1..=GCV_GRID_POINTS {
+            let candidate = score(grid_point(i));
+            if better(&candidate, &best) {
+                (best_index, best) = (i, candidate);
+            }
+        }
+        // golden-section search between the neighbours of the best grid point
+        let (mut low, mut high) = (
+            grid_point(best_index.saturating_sub(1)),
+            grid_point((best_index + 1).min(GCV_GRID_POINTS)),
+        );
+        let ratio = (V::from_integer(5).sqrt() - V::one()) * half;
+        let (mut left, mut right) = (high - ratio * (high - low), low + ratio * (high - low));
+        let (mut left_score, mut right_score) = (score(left), score(right));
+        for _ in 0..GOLDEN_SECTION_STEPS {
+            if better(&left_score, &right_score) {
+                (high, right, right_score) = (right, left, left_score);
+                left = high - ratio * (high - low);
+                left_score = score(left);
+            } else {
+                (low, left, left_score) = (left, right, right_score);
+                right = low + ratio * (high - low);
+                right_score = score(right);
+            }
+        }
+        let mut exponent = grid_point(best_index);
+        for (candidate, candidate_exponent) in [(left_score, left), (right_score, right)] {
+            if better(&candidate, &best) {
+                (best, exponent) = (candidate, candidate_exponent);
+            }
+        }
+        let (_, fitted) = best.ok_or(InterpolationError::SingularMatrixError)?;
+        Ok(Self {
+            spline: NaturalCubicSpline::try_new_xy(&xs, &fitted.values)?,
+            lambda: lambda(exponent),
+        })
+    }
+}
+
+impl_interpolator!(SmoothingSpline, Smoothing, metadata);
+
+/// The number of steps of the grid of `log₁₀ ρ` over `[-8, 8]` in [`SmoothingSpline::fit_gcv`].
+const GCV_GRID_POINTS: usize = 32;
+
+/// The number of golden-section steps refining `log₁₀ ρ`, shrinking its bracket of width 1 below
+/// `10⁻⁸`.
+const GOLDEN_SECTION_STEPS: usize = 40;
+
+/// Checks the points and weights of a smoothing spline and splits the points into columns.
+fn checked_columns<V: InterpolationValue>(
+    points: &[(V, V)],
+    weights: &[V],
+) -> Result<(Vec<V>, Vec<V>), InterpolationError<V>> {
+    let n = points.len();
+    if n < 3 {
+        return Err(InterpolationError::InsufficientPointsError(n));
+    }
+    if weights.len() != n {
+        return Err(InterpolationError::LengthMismatchError(n, weights.len()));
+    }
+    check_finite(points.iter().map(|&(x, y)| [x, y]))?;
+    check_knots(points.iter().map(|&(x, _)| x))?;
+    if let Some(i) = weights
+        .iter()
+        .position(|&w| !(w.is_finite() && w > V::zero()))
+    {
+        return Err(InterpolationError::InvalidWeightError(i));
+    }
+    Ok(points.iter().copied().unzip())
+}
+
+/// The second differences `Q` of the values at the interior knots, as the three nonzero entries
+/// `(Q[j - 1][j - 1], Q[j][j - 1], Q[j + 1][j - 1])` of each column `j - 1`.
+fn second_differences<V: InterpolationValue>(xs: &[V]) -> Vec<[V; 3]> {
+    xs.windows(3)
+        .map(|w| {
+            let (left, right) = (V::one() / (w[1] - w[0]), V::one() / (w[2] - w[1]));
+            [left, -left - right, right]
+        })
+        .collect()
+}
+
+/// The nonzero entries `(c, Q[k][c])` of the row `k` of `Q`.
+fn q_row<V: InterpolationValue>(q: &[[V; 3]], k: usize) -> impl Iterator<Item = (usize, V)> + '_ {
+    (k.saturating_sub(2)..=k.min(q.len() - 1)).map(move |c| (c, q[c][k - c]))
+}
+
+/// The smoothing spline at the knots for one `λ`.
+struct Fit<V: InterpolationValue> {
+    values: Vec<V>,
+    // the trace of the hat matrix mapping the data to the values, the effective degrees of
+    // freedom
+    hat_trace: V,
+}
+
+/// Solves `(R + λ Qᵀ W⁻¹ Q) γ = Qᵀ y` for the second derivatives `γ` at the interior knots and
+/// returns the values `y - λ W⁻¹ Q γ` of the smoothing spline at the knots.
+///
+/// The symmetric positive definite pentadiagonal system is factorized as `L D Lᵀ`, which also
+/// gives the central band of its inverse, and with it the trace of the hat matrix, in `O(n)` by
+/// the recursion of Hutchinson and de Hoog.
+fn fit<V: InterpolationValue>(
+    xs: &[V],
+    ys: &[V],
+    weights: &[V],
+    lambda: V,
+) -> Result<Fit<V>, InterpolationError<V>> {
+    let q = second_differences(xs);
+    let (n, m) = (xs.len(), q.len());
+    // the diagonal and the first two super-diagonals
+    let mut bands = [vec![V::zero(); m], vec![V::zero(); m], vec![V::zero(); m]];
+    for c in 0..m {
+        // R integrates the products of the hat functions of the second derivative
+        let (h0, h1) = (xs[c + 1] - xs[c], xs[c + 2] - xs[c + 1]);
+        bands[0][c] += (h0 + h1) / V::from_integer(3);
+        if c + 1 < m {
+            bands[1][c] += h1 / V::from_integer(6);
+        }
+    }
+    for (k, &weight) in weights.iter().enumerate() {
+        for (c, a) in q_row(&q, k) {
+            for (d, b) in q_row(&q, k).filter(|&(d, _)| d >= c) {
+                bands[d - c][c] += lambda * a * b / weight;
+            }
+        }
+    }
+    let factors = LdlFactors::factorize(&bands)?;
+    let rhs: Vec<V> = q
+        .iter()
+        .enumerate()
+        .map(|(c, column)| (0..3).fold(V::zero(), |acc, r| acc + column[r] * ys[c + r]))
+        .collect();
+    let gamma = factors.solve(rhs);
+    let inverse = factors.inverse_band();
+    let mut smoothed = V::zero();
+    let values = (0..n)
+        .map(|k| {
+            let q_gamma = q_row(&q, k).fold(V::zero(), |acc, (c, a)| acc + a * gamma[c]);
+            for (c, a) in q_row(&q, k) {
+                for (d, b) in q_row(&q, k) {
+                    smoothed += a * b * inverse(c, d) / weights[k];
+                }
+            }
+            ys[k] - lambda * q_gamma / weights[k]
+        })
+        .collect();
+    Ok(Fit {
+        values,
+        hat_trace: V::from_count(n) - lambda * smoothed,
+    })
+}
+
+/// The factorization `L D Lᵀ` of a symmetric pentadiagonal matrix, with `L` unit lower
+/// triangular.
+struct LdlFactors<V: InterpolationValue> {
+    diagonal: Vec<V>,
+    // the entries L[i + 1][i] and L[i + 2][i], zero past the end
+    lower: [Vec<V>; 2],
+}
+
+impl<V: InterpolationValue> LdlFactors<V> {
+    /// Factorizes the matrix with the diagonal and the first two super-diagonals `bands`.
+    fn factorize(bands: &[Vec<V>; 3]) -> Result<Self, InterpolationError<V>> {
+        let m = bands[0].len();
+        let mut factors = Self {
+            diagonal: Vec::with_capacity(m),
+            lower: [vec![V::zero(); m], vec![V::zero(); m]],
+        };
+        let rows = bands[0].iter().zip(&bands[1]).zip(&bands[2]);
+        for (i, ((&diagonal, &upper), &outer)) in rows.enumerate() {
+            let (mut pivot, mut next) = (diagonal, upper);
+            if i >= 1 {
+                let (l1, l2, d) = (
+                    factors.lower[0][i - 1],
+                    factors.lower[1][i - 1],
+                    factors.diagonal[i - 1],
+                );
+                pivot -= l1 * l1 * d;
+                next -= l1 * l2 * d;
+            }
+            if i >= 2 {
+                let (l2, d) = (factors.lower[1][i - 2], factors.diagonal[i - 2]);
+                pivot -= l2 * l2 * d;
+            }
+            if !(pivot.is_finite() && pivot > V::zero()) {
+                return Err(InterpolationError::SingularMatrixError);
+            }
+            factors.diagonal.push(pivot);
+            factors.lower[0][i] = next / pivot;
+            factors.lower[1][i] = outer / pivot;
+        }
+        Ok(factors)
+    }
+
+    /// Solves the system for the right-hand side `b`.
+    fn solve(&self, mut b: Vec<V>) -> Vec<V> {
+        let m = b.len();
+        for i in 0..m {
+            if i >= 1 {
+                let eliminated = self.lower[0][i - 1] * b[i - 1];
+                b[i] -= eliminated;
+            }
+            if i >= 2 {
+                let eliminated = self.lower[1][i - 2] * b[i - 2];
+                b[i] -= eliminated;
+            }
+        }
+        for i in (0..m).rev() {
+            b[i] /= self.diagonal[i];
+            if i + 1 < m {
+                let eliminated = self.lower[0][i] * b[i + 1];
+                b[i] -= eliminated;
+            }
+            if i + 2 < m {
+                let eliminated = self.lower[1][i] * b[i + 2];
+                b[i] -= eliminated;
+            }
+        }
+        b
+    }
+
+    /// Returns the entries of the inverse within two diagonals of the main diagonal, by
+    /// `S = D⁻¹ L⁻¹ + (I - Lᵀ) S` read from the last row up.
+    fn inverse_band(&self) -> impl Fn(usize, usize) -> V {
+        let m = self.diagonal.len();
+        // S[i][i + k] for k = 0, 1, 2, padded with zeros past the end
+        let mut band = [
+            vec![V::zero(); m + 2],
+            vec![V::zero(); m + 2],
+            vec![V::zero(); m + 2],
+        ];
+        let at = |band: &[Vec<V>; 3], i: usize, j: usize| {
+            let (i, j) = (i.min(j), i.max(j));
+            band[j - i][i]
+        };
+        for i in (0..m).rev() {
+            let (l1, l2) = (self.lower[0][i], self.lower[1][i]);
+            band[2][i] = -l1 * at(&band, i + 1, i + 2) - l2 * at(&band, i + 2, i + 2);
+            band[1][i] = -l1 * at(&band, i + 1, i + 1) - l2 * at(&band, i + 2, i + 1);
+            band[0][i] = V::one() / self.diagonal[i] - l1 * band[1][i] - l2 * band[2][i];
+        }
+        move |i, j| at(&band, i, j)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::smoothing_spline::{fit, SmoothingSpline};
+    use crate::InterpolationError;
+
+    const NOISY: [(f64, f64); 7] = [
+        (0.0, 0.1),
+        (1.0, 0.9),
+        (2.0, 2.2),
+        (3.0, 2.8),
+        (4.0, 4.3),
+        (5.0, 4.9),
+        (6.0, 6.0),
+    ];
+
+    #[test]
+    fn test_limits() {
+        let interpolating = SmoothingSpline::try_new(&NOISY, 0.0).unwrap();
+        for &(x, y) in &NOISY {
+            assert!((interpolating.try_value(x).unwrap() - y).abs() < 1e-12);
+        }
+        // a huge penalty leaves the least-squares line
+        let (n, sx, sy) = NOISY.iter().fold((0.0, 0.0, 0.0), |(n, sx, sy), &(x, y)| {
+            (n + 1.0, sx + x, sy + y)
+        });
+        let (sxx, sxy) = NOISY
+            .iter()
+            .fold((0.0, 0.0), |(sxx, sxy), &(x, y)| (sxx + x * x, sxy + x * y));
+        let slope = (n * sxy - sx * sy) / (n * sxx - sx * sx);
+        let intercept = (sy - slope * sx) / n;
+        let flat = SmoothingSpline::try_new(&NOISY, 1e9).unwrap();
+        for x in [0.0, 2.5, 6.0] {
+            assert!((flat.try_value(x).unwrap() - (intercept + slope * x)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_weights() {
+        // an outlier at x = 3 pulls much less once its weight reflects its large variance
+        let mut points = NOISY;
+        points[3].1 = 6.0;
+        let uniform = SmoothingSpline::try_new(&points, 0.5).unwrap();
+        let mut weights = [1.0; 7];
+        weights[3] = 1e-4;
+        let weighted = SmoothingSpline::try_new_weighted(&points, &weights, 0.5).unwrap();
+        let miss = |spline: &SmoothingSpline<f64>, x: f64| (spline.try_value(x).unwrap() - x).abs();
+        assert!(miss(&weighted, 3.0) < 0.3);
+        assert!(miss(&uniform, 3.0) > 1.0);
+        assert!(miss(&weighted, 2.0) < miss(&uniform, 2.0));
+        assert!(matches!(
+            SmoothingSpline::try_new_weighted(&points, &[1.0; 6], 0.5),
+            Err(InterpolationError::LengthMismatchError(7, 6))
+        ));
+        weights[5] = 0.0;
+        assert!(matches!(
+            SmoothingSpline::try_new_weighted(&points, &weights, 0.5),
+            Err(InterpolationError::InvalidWeightError(5))
+        ));
+        assert!(matches!(
+            SmoothingSpline::try_new(&points, -1.0),
+            Err(InterpolationError::SmoothingParameterError(-1.0))
+        ));
+    }
+
+    #[test]
+    fn test_hat_trace() {
+        // the trace from the band of the inverse matches the fits of the unit vectors
+        let xs = [0.0_f64, 0.3, 1.0, 1.2, 2.0, 3.5, 4.0];
+        let weights = [1.0, 2.0, 0.5, 1.0, 3.0, 1.0, 0.25];
+        let lambda = 0.7;
+        let mut trace = 0.0;
+        for k in 0..xs.len() {
+            let mut unit = [0.0; 7];
+            unit[k] = 1.0;
+            trace += fit(&xs, &unit, &weights, lambda).unwrap().values[k];
+        }
+        let hat_trace = fit(&xs, &[0.0; 7], &weights, lambda).unwrap().hat_trace;
+        assert!((hat_trace - trace).abs() < 1e-12);
+        assert!(hat_trace > 2.0 && hat_trace < 7.0);
+    }
+
+    #[test]
+    fn test_gcv() {
+        // a smooth signal with uniform noise in [-0.2, 0.2) from a linear congruential generator
+        let signal = |x: f64| (2.0 * x).sin() + x / 2.0;
+        let mut state = 1_u64;
+        let points: Vec<(f64, f64)> = (0..60)
+            .map(|i| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                let noise = 0.4 * ((state >> 11) as f64 / (1_u64 << 53) as f64 - 0.5);
+                let x = i as f64 * 0.05;
+                (x, signal(x) + noise)
+            })
+            .collect();
+        let smooth = SmoothingSpline::fit_gcv(&points).unwrap();
+        let interpolating = SmoothingSpline::try_new(&points, 0.0).unwrap();
+        let error = |spline: &SmoothingSpline<f64>| {
+            (0..=290)
+                .map(|i| {
+                    let x = i as f64 * 0.01;
+                    (spline.try_value(x).unwrap() - signal(x)).powi(2)
+                })
+                .sum::<f64>()
+        };
+        assert!(smooth.lambda() > 0.0);
+        assert!(error(&smooth) < 0.1 * error(&interpolating));
+        // straight data leaves nothing to smooth but the noise
+        let line: Vec<(f64, f64)> = points
+            .iter()
+            .map(|&(x, y)| (x, 1.0 + x + y - signal(x)))
+            .collect();
+        let flat = SmoothingSpline::fit_gcv(&line).unwrap();
+        assert!(flat.spline().try_eval_all(1.5).unwrap().d2ydx2.abs() < 0.1);
+    }
+}
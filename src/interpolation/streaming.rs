@@ -0,0 +1,531 @@
+use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+use crate::interpolation::dedup::DedupPolicy;
+use crate::interpolation::interpolator::impl_interpolator;
+use crate::interpolation::partition::{DensityPartition, PartitionedSpline};
+use crate::interpolation::slopes::SlopeMethod;
+use crate::interpolation::{check_append, hermite_basis, segment_index};
+use crate::{InterpolationError, InterpolationValue};
+use std::ops::Range;
+
+/// A Hermite spline with Akima or PCHIP slopes, built from points arriving one at a time.
+///
+/// Each slope depends on a bounded number of following points (see [`SlopeMethod::lookahead`]),
+/// so segments become *final* with a bounded delay and never change afterwards: with PCHIP,
+/// segment `i` is final once point `i + 2` has arrived, with Akima once point `i + 3` has.
+/// The trailing segments are *provisional*: they are evaluated with end conditions which the
+/// next points replace.
+pub struct StreamingHermiteSpline<V: InterpolationValue> {
+    method: SlopeMethod,
+    xs: Vec<V>,
+    ys: Vec<V>,
+    final_slopes: Vec<V>,
+}
+
+impl<V: InterpolationValue> StreamingHermiteSpline<V> {
+    /// Creates an empty streaming spline using the given slope rule.
+    pub fn new(method: SlopeMethod) -> Self {
+        Self {
+            method,
+            xs: Vec::new(),
+            ys: Vec::new(),
+            final_slopes: Vec::new(),
+        }
+    }
+
+    /// Appends a point to the spline.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the segments which became final with this point.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(len)` if a coordinate is NaN or infinite,
+    /// `InterpolationError::DuplicatePointError(x)` if `x` equals the last x-coordinate and
+    /// `InterpolationError::PointOrderError(len)` if it is smaller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::slopes::SlopeMethod;
+    /// use spline_interpolation::interpolation::streaming::StreamingHermiteSpline;
+    ///
+    /// let mut spline = StreamingHermiteSpline::new(SlopeMethod::Pchip);
+    /// assert!(spline.push(0.0, 0.0).unwrap().is_empty());
+    /// assert!(spline.push(1.0, 1.0).unwrap().is_empty());
+    /// assert_eq!(spline.push(2.0, 4.0).unwrap(), 0..1);
+    /// assert_eq!(spline.final_range(), Some((0.0, 1.0)));
+    /// assert_eq!(spline.provisional_range(), Some((1.0, 2.0)));
+    /// ```
+    pub fn push(&mut self, x: V, y: V) -> Result<Range<usize>, InterpolationError<V>> {
+        check_push(self.xs.last().copied(), self.xs.len(), x, y)?;
+        self.xs.push(x);
+        self.ys.push(y);
+        let final_segments = self.final_segment_count();
+        let n = self.xs.len();
+        if n >= 3 {
+            while self.final_slopes.len() + self.method.lookahead() < n {
+                let i = self.final_slopes.len();
+                self.final_slopes
+                    .push(self.method.slope(&self.xs, &self.ys, i));
+            }
+        }
+        Ok(final_segments..self.final_segment_count())
+    }
+
+    /// Returns the number of points pushed so far.
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    /// Returns `true` if no point has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    /// Returns the highest degree of polynomials this spline reproduces exactly, which is 1 for
+    /// both slope rules.
+    pub fn degree_exactness(&self) -> usize {
+        1
+    }
+
+    /// Returns the x-range covered by final segments, if any.
+    pub fn final_range(&self) -> Option<(V, V)> {
+        match self.final_segment_count() {
+            0 => None,
+            count => Some((self.xs[0], self.xs[count])),
+        }
+    }
+
+    /// Returns the x-range covered by provisional segments, if any.
+    pub fn provisional_range(&self) -> Option<(V, V)> {
+        let last = self.xs.len().checked_sub(1)?;
+        let first = self.final_segment_count();
+        (first < last).then(|| (self.xs[first], self.xs[last]))
+    }
+
+    /// Evaluates the spline at `x`, using provisional end conditions on the trailing segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if fewer than 2 points have been pushed.
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` if `x` lies outside the pushed points.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        if self.xs.len() < 2 {
+            return Err(InterpolationError::InsufficientPointsError(self.xs.len()));
+        }
+        let i = segment_index(&self.xs, x)?;
+        let h = self.xs[i + 1] - self.xs[i];
+        let (values, slopes) = hermite_basis((x - self.xs[i]) / h);
+        Ok(self.ys[i] * values[0]
+            + self.ys[i + 1] * values[1]
+            + (self.slope(i) * slopes[0] + self.slope(i + 1) * slopes[1]) * h)
+    }
+
+    fn slope(&self, i: usize) -> V {
+        match self.final_slopes.get(i) {
+            Some(&slope) => slope,
+            None => self.method.slope(&self.xs, &self.ys, i),
+        }
+    }
+
+    fn final_segment_count(&self) -> usize {
+        self.final_slopes.len().saturating_sub(1)
+    }
+}
+
+/// Collects points one at a time, checking their order as they arrive, and builds a spline from
+/// them at the end.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::streaming::PointBuilder;
+///
+/// let mut builder = PointBuilder::new();
+/// builder.push(0.0, 1.0).unwrap();
+/// builder.push(0.5, 0.5).unwrap();
+/// assert!(builder.push(0.2, 0.0).is_err());
+/// builder.push(1.0, 0.0).unwrap();
+/// let spline = builder.build(NaturalCubicSpline::try_new).unwrap();
+/// assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PointBuilder<V: InterpolationValue> {
+    points: Vec<(V, V)>,
+    dedup_policy: Option<DedupPolicy>,
+    // the number of pushed points merged into the last point
+    run_len: usize,
+}
+
+impl<V: InterpolationValue> PointBuilder<V> {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            dedup_policy: None,
+            run_len: 0,
+        }
+    }
+
+    /// Merges a point repeating the last x-coordinate into the last point with `policy`, instead
+    /// of rejecting it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::dedup::DedupPolicy;
+    /// use spline_interpolation::interpolation::streaming::PointBuilder;
+    ///
+    /// let mut builder = PointBuilder::new().with_dedup_policy(DedupPolicy::Average);
+    /// for (x, y) in [(0.0, 1.0), (1.0, 1.0), (1.0, 2.0), (1.0, 6.0)] {
+    ///     builder.push(x, y).unwrap();
+    /// }
+    /// assert_eq!(builder.points(), [(0.0, 1.0), (1.0, 3.0)]);
+    /// ```
+    pub fn with_dedup_policy(mut self, policy: DedupPolicy) -> Self {
+        self.dedup_policy = Some(policy);
+        self
+    }
+
+    /// Appends a point.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(len)` if a coordinate is NaN or infinite,
+    /// `InterpolationError::DuplicatePointError(x)` if `x` equals the last x-coordinate and no
+    /// dedup policy is set, and `InterpolationError::PointOrderError(len)` if `x` is smaller than
+    /// the last x-coordinate; the point is not added.
+    pub fn push(&mut self, x: V, y: V) -> Result<(), InterpolationError<V>> {
+        if let (Some(policy), Some(last)) = (self.dedup_policy, self.points.last_mut()) {
+            if last.0 == x && y.is_finite() {
+                last.1 = policy.merge(last.1, self.run_len, y);
+                self.run_len += 1;
+                return Ok(());
+            }
+        }
+        check_push(
+            self.points.last().map(|&(last, _)| last),
+            self.points.len(),
+            x,
+            y,
+        )?;
+        self.points.push((x, y));
+        self.run_len = 1;
+        Ok(())
+    }
+
+    /// Returns the points pushed so far.
+    pub fn points(&self) -> &[(V, V)] {
+        &self.points
+    }
+
+    /// Returns the number of points pushed so far.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns `true` if no point has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Builds a spline from the points with one of the `try_new` constructors,
+    /// e.g. `builder.build(CatmullRomSpline::try_new)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the constructor, e.g. `InsufficientPointsError(n)`.
+    pub fn build<S>(
+        &self,
+        constructor: impl FnOnce(&[(V, V)]) -> Result<S, InterpolationError<V>>,
+    ) -> Result<S, InterpolationError<V>> {
+        constructor(&self.points)
+    }
+
+    /// Builds a spline fitting dense and sparse regions of the points with different methods.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if fewer than 3 points have been pushed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::partition::{Density, DensityPartition};
+    /// use spline_interpolation::interpolation::streaming::PointBuilder;
+    ///
+    /// let mut builder = PointBuilder::new();
+    /// for x in [0.0, 1.0, 2.0, 2.1, 2.2, 2.3, 3.0, 4.0] {
+    ///     builder.push(x, x * x).unwrap();
+    /// }
+    /// let spline = builder.build_partitioned(&DensityPartition::default()).unwrap();
+    /// let densities: Vec<_> = spline.regions().iter().map(|region| region.density).collect();
+    /// assert_eq!(densities, [Density::Sparse, Density::Dense, Density::Sparse]);
+    /// ```
+    pub fn build_partitioned(
+        &self,
+        options: &DensityPartition<V>,
+    ) -> Result<PartitionedSpline<V>, InterpolationError<V>> {
+        PartitionedSpline::try_new(&self.points, options)
+    }
+}
+
+/// Checks that the point `(x, y)` is finite and can be appended as the `index`-th point after the
+/// x-coordinate `last`.
+fn check_push<V: InterpolationValue>(
+    last: Option<V>,
+    index: usize,
+    x: V,
+    y: V,
+) -> Result<(), InterpolationError<V>> {
+    if !(x.is_finite() && y.is_finite()) {
+        return Err(InterpolationError::NonFiniteError(index));
+    }
+    check_append(last, index, x)
+}
+
+/// A Catmull-Rom spline built from points arriving one at a time.
+///
+/// Segment `i` depends on the points `i - 1` to `i + 2`, so it is final once point `i + 2` has
+/// arrived. Only the final segments, i.e. everything but the trailing segment, can be evaluated.
+pub struct StreamingCatmullRomSpline<V: InterpolationValue> {
+    pending: Vec<(V, V)>,
+    spline: Option<CatmullRomSpline<V>>,
+    len: usize,
+}
+
+impl<V: InterpolationValue> StreamingCatmullRomSpline<V> {
+    /// Creates an empty streaming spline.
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            spline: None,
+            len: 0,
+        }
+    }
+
+    /// Appends a point to the spline.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the segments which became final with this point.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(len)` if a coordinate is NaN or infinite,
+    /// `InterpolationError::DuplicatePointError(x)` if `x` equals the last x-coordinate and
+    /// `InterpolationError::PointOrderError(len)` if it is smaller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::streaming::StreamingCatmullRomSpline;
+    ///
+    /// let mut spline = StreamingCatmullRomSpline::new();
+    /// assert!(spline.push(0.0, 0.0).unwrap().is_empty());
+    /// assert!(spline.push(1.0, 1.0).unwrap().is_empty());
+    /// assert_eq!(spline.push(2.0, 2.0).unwrap(), 0..1);
+    /// assert_eq!(spline.try_value(0.5).unwrap(), 0.5);
+    /// assert!(spline.try_value(1.5).is_err());
+    /// ```
+    pub fn push(&mut self, x: V, y: V) -> Result<Range<usize>, InterpolationError<V>> {
+        check_push(self.last_x(), self.len, x, y)?;
+        let final_segments = self.final_segment_count();
+        match &mut self.spline {
+            Some(spline) => spline.push_unchecked(x, y),
+            None => {
+                self.pending.push((x, y));
+                if self.pending.len() == 3 {
+                    self.spline = Some(CatmullRomSpline::try_new(&self.pending)?);
+                    self.pending.clear();
+                }
+            }
+        }
+        self.len += 1;
+        Ok(final_segments..self.final_segment_count())
+    }
+
+    /// Returns the number of points pushed so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no point has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the highest degree of polynomials this spline reproduces exactly, which is 1 as for
+    /// [`CatmullRomSpline`].
+    pub fn degree_exactness(&self) -> usize {
+        1
+    }
+
+    /// Returns the x-range covered by final segments, if any.
+    pub fn final_range(&self) -> Option<(V, V)> {
+        let spline = self.spline.as_ref()?;
+        Some((spline.knot(0), spline.knot(self.final_segment_count())))
+    }
+
+    /// Evaluates the spline at `x` on the final segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientPointsError(n)` if fewer than 3 points have been pushed.
+    /// Returns `OutOfLowerBound(x)` or `OutOfUpperBound(x)` if `x` lies outside the final segments.
+    pub fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        let (Some(spline), Some((_, upper))) = (&self.spline, self.final_range()) else {
+            return Err(InterpolationError::InsufficientPointsError(self.len));
+        };
+        if x > upper {
+            return Err(InterpolationError::OutOfUpperBound(x));
+        }
+        spline.try_value(x)
+    }
+
+    fn last_x(&self) -> Option<V> {
+        match &self.spline {
+            Some(spline) => Some(spline.knot(self.len - 1)),
+            None => self.pending.last().map(|&(x, _)| x),
+        }
+    }
+
+    fn final_segment_count(&self) -> usize {
+        self.len.saturating_sub(2)
+    }
+}
+
+impl<V: InterpolationValue> Default for StreamingCatmullRomSpline<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_interpolator!(StreamingHermiteSpline, StreamingHermite);
+impl_interpolator!(StreamingCatmullRomSpline, StreamingCatmullRom);
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::dedup::DedupPolicy;
+    use crate::interpolation::slopes::SlopeMethod;
+    use crate::interpolation::streaming::{
+        PointBuilder, StreamingCatmullRomSpline, StreamingHermiteSpline,
+    };
+    use crate::InterpolationError;
+
+    const POINTS: [(f64, f64); 7] = [
+        (0.0, 0.0),
+        (0.5, 0.4),
+        (1.5, 0.5),
+        (2.0, 1.8),
+        (3.2, 2.0),
+        (4.0, 2.1),
+        (4.5, 3.0),
+    ];
+
+    #[test]
+    fn test_final_segments_are_stable() {
+        for (method, delay) in [(SlopeMethod::Pchip, 2), (SlopeMethod::Akima, 3)] {
+            let mut spline = StreamingHermiteSpline::new(method);
+            let mut snapshots = Vec::new();
+            for (n, &(x, y)) in POINTS.iter().enumerate() {
+                let emitted = spline.push(x, y).unwrap();
+                for segment in emitted {
+                    assert_eq!(segment + delay, n);
+                    let (x0, x1) = (POINTS[segment].0, POINTS[segment + 1].0);
+                    for x in [x0, 0.5 * (x0 + x1), x1] {
+                        snapshots.push((x, spline.try_value(x).unwrap()));
+                    }
+                }
+            }
+            for (x, value) in snapshots {
+                assert_eq!(spline.try_value(x).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pchip_is_monotone() {
+        let mut spline = StreamingHermiteSpline::new(SlopeMethod::Pchip);
+        for (x, y) in POINTS {
+            spline.push(x, y).unwrap();
+        }
+        let mut previous = spline.try_value(0.0).unwrap();
+        for i in 1..=90 {
+            let value = spline.try_value(i as f64 * 0.05).unwrap();
+            assert!(value >= previous);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut spline = StreamingHermiteSpline::new(SlopeMethod::Akima);
+        spline.push(1.0, 0.0).unwrap();
+        assert!(matches!(
+            spline.try_value(1.0),
+            Err(InterpolationError::InsufficientPointsError(1))
+        ));
+        assert!(matches!(
+            spline.push(1.0, 2.0),
+            Err(InterpolationError::DuplicatePointError(1.0))
+        ));
+        spline.push(2.0, 2.0).unwrap();
+        assert_eq!(spline.try_value(1.5).unwrap(), 1.0);
+        assert_eq!(spline.final_range(), None);
+    }
+
+    #[test]
+    fn test_catmull_rom_matches_batch_spline() {
+        let mut streaming = StreamingCatmullRomSpline::new();
+        let mut builder = PointBuilder::new();
+        for (n, &(x, y)) in POINTS.iter().enumerate() {
+            let emitted = streaming.push(x, y).unwrap();
+            builder.push(x, y).unwrap();
+            assert_eq!(emitted, n.saturating_sub(2)..n.saturating_sub(1));
+        }
+        let batch = builder.build(CatmullRomSpline::try_new).unwrap();
+        assert_eq!(streaming.final_range(), Some((0.0, 4.0)));
+        for i in 0..=80 {
+            let x = i as f64 * 0.05;
+            assert_eq!(streaming.try_value(x).unwrap(), batch.try_value(x).unwrap());
+        }
+        assert!(matches!(
+            streaming.try_value(4.1),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+        assert!(matches!(
+            streaming.push(4.5, 0.0),
+            Err(InterpolationError::DuplicatePointError(4.5))
+        ));
+        assert!(matches!(
+            builder.push(-1.0, 0.0),
+            Err(InterpolationError::PointOrderError(7))
+        ));
+    }
+
+    #[test]
+    fn test_non_finite_and_duplicate_points() {
+        let mut builder = PointBuilder::new().with_dedup_policy(DedupPolicy::KeepLast);
+        builder.push(0.0, 1.0).unwrap();
+        builder.push(0.0, 2.0).unwrap();
+        assert!(matches!(
+            builder.push(0.0, f64::NAN),
+            Err(InterpolationError::NonFiniteError(1))
+        ));
+        assert!(matches!(
+            builder.push(f64::INFINITY, 0.0),
+            Err(InterpolationError::NonFiniteError(1))
+        ));
+        assert_eq!(builder.points(), [(0.0, 2.0)]);
+        let mut spline = StreamingHermiteSpline::new(SlopeMethod::Pchip);
+        assert!(matches!(
+            spline.push(f64::NAN, 0.0),
+            Err(InterpolationError::NonFiniteError(0))
+        ));
+        assert!(spline.is_empty());
+    }
+}
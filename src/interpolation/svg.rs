@@ -0,0 +1,106 @@
+//! SVG path data of the interpolants, for visual debugging.
+//!
+//! The paths are in the coordinates of the data, whereas the y-axis of SVG points down; flip the
+//! drawing with e.g. `transform="scale(1, -1)"`.
+
+use crate::interpolation::bezier::CubicBezier;
+use crate::InterpolationValue;
+use std::fmt::{Display, Write};
+
+/// How `to_svg_path_data` traces an interpolant.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "svg")]
+/// # {
+/// use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+/// use spline_interpolation::interpolation::svg::PathSampling;
+///
+/// let spline = HermiteSpline::try_new(&[(0.0, 0.0, 3.0), (3.0, 6.0, 0.0)]).unwrap();
+/// assert_eq!(
+///     spline.to_svg_path_data(PathSampling::Exact),
+///     "M 0,0 C 1,3 2,6 3,6"
+/// );
+/// assert_eq!(
+///     spline.to_svg_path_data(PathSampling::Polyline(3)),
+///     "M 0,0 L 1.5,4.125 L 3,6"
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSampling {
+    /// One cubic Bézier curve per segment, tracing a cubic interpolant exactly.
+    Exact,
+    /// A polyline through the given number of equally spaced points, at least 2.
+    Polyline(usize),
+}
+
+/// Returns the path data of Bézier curves joined end to end.
+pub(crate) fn bezier_path_data<V: InterpolationValue + Display>(
+    segments: &[CubicBezier<V>],
+) -> String {
+    let mut data = String::new();
+    if let Some(first) = segments.first() {
+        let (x, y) = first.control_points[0];
+        write!(data, "M {x},{y}").unwrap();
+    }
+    for segment in segments {
+        let [_, (x1, y1), (x2, y2), (x, y)] = segment.control_points;
+        write!(data, " C {x1},{y1} {x2},{y2} {x},{y}").unwrap();
+    }
+    data
+}
+
+/// Returns the path data of the polyline through `(x, value(x))` at `points` equally spaced `x`
+/// from `start` to `end`.
+pub(crate) fn polyline_path_data<V: InterpolationValue + Display>(
+    (start, end): (V, V),
+    points: usize,
+    value: impl Fn(V) -> V,
+) -> String {
+    let last = points.max(2) - 1;
+    let step = (end - start) / V::from_count(last);
+    let mut data = format!("M {start},{}", value(start));
+    for i in 1..=last {
+        // the last point is exactly the end, free of the rounding of the steps
+        let x = if i == last {
+            end
+        } else {
+            start + step * V::from_count(i)
+        };
+        write!(data, " L {x},{}", value(x)).unwrap();
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::interpolation::svg::PathSampling;
+
+    #[test]
+    fn test_path_data() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)];
+        let natural = NaturalCubicSpline::try_new(&points).unwrap();
+        let exact = natural.to_svg_path_data(PathSampling::Exact);
+        assert!(exact.starts_with("M 0,0 C "));
+        assert_eq!(exact.matches('C').count(), 2);
+        assert!(exact.ends_with(" 2,0"));
+        let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+        assert_eq!(
+            catmull_rom.to_svg_path_data(PathSampling::Polyline(0)),
+            "M 0,0 L 2,0"
+        );
+        // a quartic falls back to a polyline even when asked for an exact path
+        let quartic =
+            PiecewisePolynomial::try_new(vec![0.0, 1.0], vec![vec![1.0, 0.0, 0.0, 0.0, 0.0]])
+                .unwrap();
+        let fallback = quartic.to_svg_path_data(PathSampling::Exact);
+        assert!(!fallback.contains('C'));
+        assert_eq!(fallback.matches('L').count(), 16);
+        assert!(fallback.ends_with("L 1,1"));
+    }
+}
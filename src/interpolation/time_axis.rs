@@ -0,0 +1,240 @@
+//! Interpolation over a `chrono` time axis, converting dates and times to numbers once.
+
+use crate::interpolation::builder::SplineBuilder;
+use crate::interpolation::interpolator::Interpolator;
+use crate::{InterpolationError, InterpolationValue};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+use std::fmt::Debug;
+
+/// A point in time usable on a [`TimeAxis`].
+pub trait TimePoint: Copy + Debug {
+    /// Returns the time elapsed from `epoch` to `self`, negative if `self` is earlier.
+    fn since(self, epoch: Self) -> TimeDelta;
+}
+
+impl TimePoint for NaiveDate {
+    fn since(self, epoch: Self) -> TimeDelta {
+        self - epoch
+    }
+}
+
+impl TimePoint for NaiveDateTime {
+    fn since(self, epoch: Self) -> TimeDelta {
+        self - epoch
+    }
+}
+
+impl TimePoint for DateTime<Utc> {
+    fn since(self, epoch: Self) -> TimeDelta {
+        self - epoch
+    }
+}
+
+/// The unit in which a [`TimeAxis`] measures the time elapsed since its epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeScale {
+    /// Days, fractional for times of day.
+    #[default]
+    Days,
+    /// Seconds.
+    Seconds,
+    /// Years of 365 days (Actual/365 Fixed).
+    Act365Fixed,
+    /// Years of 360 days (Actual/360).
+    Act360,
+}
+
+impl TimeScale {
+    /// Returns the number of seconds in one unit.
+    fn seconds(self) -> i64 {
+        const DAY: i64 = 86_400;
+        match self {
+            TimeScale::Days => DAY,
+            TimeScale::Seconds => 1,
+            TimeScale::Act365Fixed => 365 * DAY,
+            TimeScale::Act360 => 360 * DAY,
+        }
+    }
+}
+
+/// Maps points in time to numbers as the time elapsed since an epoch in a [`TimeScale`].
+///
+/// The conversion goes through whole seconds and nanoseconds, so whole days are exact for
+/// `Decimal`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::NaiveDate;
+/// use spline_interpolation::interpolation::time_axis::{TimeAxis, TimeScale};
+///
+/// let axis = TimeAxis::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), TimeScale::Act360);
+/// let t: f64 = axis.to_value(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+/// assert_eq!(t, 60.0 / 360.0);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeAxis<T: TimePoint> {
+    epoch: T,
+    scale: TimeScale,
+}
+
+impl<T: TimePoint> TimeAxis<T> {
+    /// Constructs a new `TimeAxis` measuring from `epoch` in `scale`.
+    pub fn new(epoch: T, scale: TimeScale) -> Self {
+        Self { epoch, scale }
+    }
+
+    /// Returns the point in time mapped to zero.
+    pub fn epoch(&self) -> T {
+        self.epoch
+    }
+
+    /// Returns the unit of the axis.
+    pub fn scale(&self) -> TimeScale {
+        self.scale
+    }
+
+    /// Returns the time elapsed from the epoch to `t` in the unit of the axis.
+    pub fn to_value<V: InterpolationValue>(&self, t: T) -> V {
+        let elapsed = t.since(self.epoch);
+        let seconds = V::from_integer(elapsed.num_seconds())
+            + V::from_integer(elapsed.subsec_nanos().into()) / V::from_integer(1_000_000_000);
+        match self.scale {
+            TimeScale::Seconds => seconds,
+            scale => seconds / V::from_integer(scale.seconds()),
+        }
+    }
+
+    /// Converts the points in time of `points`.
+    fn convert<V: InterpolationValue>(&self, points: &[(T, V)]) -> Vec<(V, V)> {
+        points.iter().map(|&(t, y)| (self.to_value(t), y)).collect()
+    }
+}
+
+/// An interpolant of values `(t, y)` at points in time `t`, evaluated at points in time
+/// directly.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::NaiveDate;
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::time_axis::{TimeAxis, TimeInterpolator, TimeScale};
+///
+/// let date = |month| NaiveDate::from_ymd_opt(2024, month, 1).unwrap();
+/// let points = [(date(1), 1.0), (date(2), 2.0), (date(3), 2.5), (date(4), 2.0)];
+/// let axis = TimeAxis::new(date(1), TimeScale::Act365Fixed);
+/// let curve = TimeInterpolator::try_new(&points, axis, NaturalCubicSpline::try_new).unwrap();
+/// assert_eq!(curve.try_value(date(3)).unwrap(), 2.5);
+/// assert!(curve.try_value(NaiveDate::from_ymd_opt(2024, 2, 15).unwrap()).is_ok());
+/// # }
+/// ```
+pub struct TimeInterpolator<T: TimePoint, V: InterpolationValue> {
+    axis: TimeAxis<T>,
+    inner: Box<dyn Interpolator<V>>,
+}
+
+impl<T: TimePoint, V: InterpolationValue> TimeInterpolator<T, V> {
+    /// Converts the points in time with `axis` and builds the interpolant of the resulting
+    /// points `(x, y)` with `build`, e.g. a spline constructor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `build`.
+    pub fn try_new<I: Interpolator<V>>(
+        points: &[(T, V)],
+        axis: TimeAxis<T>,
+        build: impl FnOnce(&[(V, V)]) -> Result<I, InterpolationError<V>>,
+    ) -> Result<Self, InterpolationError<V>> {
+        let inner = build(&axis.convert(points))?;
+        Ok(Self {
+            axis,
+            inner: Box::new(inner),
+        })
+    }
+
+    /// Converts the points in time with `axis` and builds the interpolant with a
+    /// [`SplineBuilder`] set up by `configure`, e.g. to extrapolate past the last date.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`SplineBuilder::build`].
+    pub fn try_with_builder(
+        points: &[(T, V)],
+        axis: TimeAxis<T>,
+        configure: impl FnOnce(SplineBuilder<V>) -> SplineBuilder<V>,
+    ) -> Result<Self, InterpolationError<V>> {
+        let inner = configure(SplineBuilder::new(&axis.convert(points))).build()?;
+        Ok(Self { axis, inner })
+    }
+
+    /// Returns the time axis.
+    pub fn axis(&self) -> &TimeAxis<T> {
+        &self.axis
+    }
+
+    /// Returns the interpolant on the numeric axis.
+    pub fn inner(&self) -> &dyn Interpolator<V> {
+        self.inner.as_ref()
+    }
+
+    /// Evaluates the interpolant at the point in time `t`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the inner interpolant, reporting `t` on the numeric axis.
+    pub fn try_value(&self, t: T) -> Result<V, InterpolationError<V>> {
+        self.inner.try_value(self.axis.to_value(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::builder::Extrapolation;
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::interpolator::MethodTag;
+    use crate::interpolation::time_axis::{TimeAxis, TimeInterpolator, TimeScale};
+    use crate::InterpolationError;
+    use chrono::{DateTime, NaiveDate, TimeDelta};
+
+    #[test]
+    fn test_scales() {
+        let epoch = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let t = epoch + TimeDelta::hours(36) + TimeDelta::milliseconds(500);
+        let seconds = 36.0 * 3_600.0 + 0.5;
+        let value = |scale| TimeAxis::new(epoch, scale).to_value::<f64>(t);
+        assert_eq!(value(TimeScale::Seconds), seconds);
+        assert_eq!(value(TimeScale::Days), seconds / 86_400.0);
+        assert_eq!(value(TimeScale::Act365Fixed), seconds / 86_400.0 / 365.0);
+        assert_eq!(value(TimeScale::Act360), seconds / 86_400.0 / 360.0);
+        assert_eq!(
+            TimeAxis::new(t, TimeScale::Seconds).to_value::<f64>(epoch),
+            -seconds
+        );
+    }
+
+    #[test]
+    fn test_interpolate_dates() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + TimeDelta::days(day);
+        let points: Vec<_> = (0..5).map(|i| (date(10 * i), 2.0 * i as f64)).collect();
+        let axis = TimeAxis::new(date(0), TimeScale::Days);
+        let curve = TimeInterpolator::try_new(&points, axis, CatmullRomSpline::try_new).unwrap();
+        assert_eq!(curve.inner().method(), MethodTag::CatmullRom);
+        // the data is linear in days, which Catmull-Rom reproduces
+        assert!((curve.try_value(date(15)).unwrap() - 3.0).abs() < 1e-12);
+        assert!(matches!(
+            curve.try_value(date(41)),
+            Err(InterpolationError::OutOfUpperBound(41.0))
+        ));
+        let extrapolated = TimeInterpolator::try_with_builder(&points, axis, |builder| {
+            builder.extrapolation(Extrapolation::Constant)
+        })
+        .unwrap();
+        assert_eq!(extrapolated.try_value(date(100)).unwrap(), 8.0);
+    }
+}
@@ -0,0 +1,174 @@
+//! Interpolation of transformed values exposed through the inverse transform, e.g. variances
+//! interpolated and volatilities returned.
+
+use crate::interpolation::check_finite;
+use crate::interpolation::interpolator::{sealed, Interpolator, MethodTag};
+use crate::metadata::Metadata;
+use crate::{InterpolationError, InterpolationValue};
+use std::any::Any;
+
+/// An interpolant `S` of transformed values `u = forward(x, y)`, answering queries with
+/// `inverse(x, u)`.
+///
+/// The maps receive the abscissa too, so they can scale by it, e.g. zero rates `r` interpolated
+/// and discount factors `exp(-r x)` returned, or total variances `σ² x` interpolated and
+/// volatilities returned. `inverse` should undo `forward` for every `x`, so that the points are
+/// reproduced.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::interpolation::transformed::TransformedInterpolator;
+///
+/// // volatilities at expiries, interpolated in total variance
+/// let vols = [(0.25_f64, 0.3), (0.5, 0.25), (1.0, 0.22), (2.0, 0.2)];
+/// let curve = TransformedInterpolator::try_new(
+///     &vols,
+///     |t, vol| vol * vol * t,
+///     |t, variance: f64| (variance / t).sqrt(),
+///     NaturalCubicSpline::try_new,
+/// )
+/// .unwrap();
+/// assert!((curve.try_value(0.5).unwrap() - 0.25).abs() < 1e-15);
+/// let variance = curve.inner().try_value(0.75).unwrap();
+/// assert_eq!(curve.try_value(0.75).unwrap(), (variance / 0.75).sqrt());
+/// ```
+pub struct TransformedInterpolator<S, F> {
+    inner: S,
+    inverse: F,
+}
+
+impl<S, F> TransformedInterpolator<S, F> {
+    /// Wraps `inner`, an interpolant of already transformed values.
+    pub fn new(inner: S, inverse: F) -> Self {
+        Self { inner, inverse }
+    }
+
+    /// Transforms the values of `points` with `forward` and interpolates them with `fit`, e.g. a
+    /// spline constructor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InterpolationError::NonFiniteError(i)` if the transformed value of the `i`-th
+    /// point is NaN or infinite, e.g. the logarithm of a non-positive value, and otherwise the
+    /// errors of `fit`.
+    pub fn try_new<V: InterpolationValue>(
+        points: &[(V, V)],
+        forward: impl Fn(V, V) -> V,
+        inverse: F,
+        fit: impl FnOnce(&[(V, V)]) -> Result<S, InterpolationError<V>>,
+    ) -> Result<Self, InterpolationError<V>>
+    where
+        F: Fn(V, V) -> V,
+    {
+        let transformed: Vec<(V, V)> = points.iter().map(|&(x, y)| (x, forward(x, y))).collect();
+        check_finite(transformed.iter().map(|&(x, u)| [x, u]))?;
+        Ok(Self::new(fit(&transformed)?, inverse))
+    }
+
+    /// Returns the interpolant of the transformed values.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Evaluates the interpolant at `x` and maps the result back with the inverse transform.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the inner interpolant.
+    pub fn try_value<V>(&self, x: V) -> Result<V, InterpolationError<V>>
+    where
+        V: InterpolationValue,
+        S: Interpolator<V>,
+        F: Fn(V, V) -> V,
+    {
+        Ok((self.inverse)(x, self.inner.try_value(x)?))
+    }
+}
+
+impl<S, F> sealed::Sealed for TransformedInterpolator<S, F> {}
+
+impl<V, S, F> Interpolator<V> for TransformedInterpolator<S, F>
+where
+    V: InterpolationValue,
+    S: Interpolator<V>,
+    F: Fn(V, V) -> V + Send + Sync + 'static,
+{
+    fn try_value(&self, x: V) -> Result<V, InterpolationError<V>> {
+        TransformedInterpolator::try_value(self, x)
+    }
+
+    fn method(&self) -> MethodTag {
+        MethodTag::Transformed
+    }
+
+    /// Returns the metadata of the inner interpolant.
+    fn metadata(&self) -> Option<&Metadata> {
+        self.inner.metadata()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::interpolator::{Interpolator, MethodTag};
+    use crate::interpolation::slopes::SlopeMethod;
+    use crate::interpolation::transformed::TransformedInterpolator;
+    use crate::metadata::Metadata;
+    use crate::InterpolationError;
+
+    #[test]
+    fn test_zero_rates() {
+        // discount factors of a flat 3% curve, interpolated as zero rates
+        let discount = |t: f64| (-0.03 * t).exp();
+        let points: Vec<(f64, f64)> = [0.5, 1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|&t| (t, discount(t)))
+            .collect();
+        let curve = TransformedInterpolator::try_new(
+            &points,
+            |t, df: f64| -df.ln() / t,
+            |t, rate| (-rate * t).exp(),
+            |points| {
+                HermiteSpline::try_new_with_slope_method(points, SlopeMethod::Pchip)
+                    .map(|spline| spline.with_metadata(Metadata::new().with_name("zero")))
+            },
+        )
+        .unwrap();
+        for t in [0.5, 0.7, 3.0, 9.9] {
+            assert!((curve.try_value(t).unwrap() - discount(t)).abs() < 1e-14);
+            assert!((curve.inner().try_value(t).unwrap() - 0.03).abs() < 1e-14);
+        }
+        let boxed: Box<dyn Interpolator<f64>> = Box::new(curve);
+        assert_eq!(boxed.method(), MethodTag::Transformed);
+        assert_eq!(boxed.metadata().unwrap().name(), Some("zero"));
+        assert!(matches!(
+            boxed.try_value(11.0),
+            Err(InterpolationError::OutOfUpperBound(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_transform() {
+        let points = [(0.0, 1.0), (1.0, -1.0), (2.0, 2.0)];
+        assert!(matches!(
+            TransformedInterpolator::try_new(
+                &points,
+                |_, y: f64| y.ln(),
+                |_, u| u.exp(),
+                CatmullRomSpline::try_new,
+            ),
+            Err(InterpolationError::NonFiniteError(1))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::math::dense_matrix::solve_dense;
+use crate::{InterpolationError, TranscendentalValue};
+
+/// The differential structure preserved by a [`VectorFieldInterpolator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorFieldKind {
+    /// The interpolated field has zero divergence everywhere (e.g. incompressible flow).
+    DivergenceFree,
+    /// The interpolated field has zero curl everywhere (e.g. electrostatic fields).
+    CurlFree,
+}
+
+/// A sampled vector: the position `(x, y)` and the field vector `(u, v)` there.
+pub type VectorSample<V> = ((V, V), (V, V));
+
+/// Interpolates a 2D vector field from scattered samples with a matrix-valued Gaussian kernel.
+///
+/// With `φ(r) = exp(-ε² |r|²)`, the kernel is `-∇∇ᵀφ` for curl-free fields and
+/// `(∇∇ᵀ - ∇²I)φ` for divergence-free fields, so every basis function, and therefore the
+/// interpolant itself, has the requested structure exactly.
+pub struct VectorFieldInterpolator<V: TranscendentalValue> {
+    centers: Vec<(V, V)>,
+    weights: Vec<(V, V)>,
+    epsilon: V,
+    kind: VectorFieldKind,
+}
+
+impl<V: TranscendentalValue> VectorFieldInterpolator<V> {
+    /// Constructs a new `VectorFieldInterpolator` from sampled vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - A slice of `((x, y), (u, v))` tuples: the sample position and the field vector.
+    /// * `epsilon` - The shape parameter of the Gaussian; larger values give more localized kernels.
+    /// * `kind` - Whether the field should be divergence-free or curl-free.
+    ///
+    /// # Errors
+    ///
+    /// * `InterpolationError::InsufficientPointsError(0)` - If `samples` is empty.
+    /// * `InterpolationError::SingularMatrixError` - If the interpolation system cannot be solved,
+    ///   e.g. because two samples share a position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::interpolation::vector_field::{
+    ///     VectorFieldInterpolator, VectorFieldKind,
+    /// };
+    ///
+    /// // samples of the rotation field (-y, x)
+    /// let samples = [((1.0_f64, 0.0), (0.0, 1.0)), ((0.0, 1.0), (-1.0, 0.0))];
+    /// let field =
+    ///     VectorFieldInterpolator::try_new(&samples, 1.0, VectorFieldKind::DivergenceFree).unwrap();
+    /// let (u, v) = field.value(1.0, 0.0);
+    /// assert!(u.abs() < 1e-12 && (v - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn try_new(
+        samples: &[VectorSample<V>],
+        epsilon: V,
+        kind: VectorFieldKind,
+    ) -> Result<Self, InterpolationError<V>> {
+        if samples.is_empty() {
+            return Err(InterpolationError::InsufficientPointsError(0));
+        }
+        let n = 2 * samples.len();
+        let mut a = Vec::with_capacity(n * n);
+        for &(p, _) in samples {
+            for row in 0..2 {
+                for &(q, _) in samples {
+                    let k = kernel(kind, epsilon, p.0 - q.0, p.1 - q.1);
+                    a.extend_from_slice(&k[row]);
+                }
+            }
+        }
+        let b = samples.iter().flat_map(|&(_, f)| [f.0, f.1]).collect();
+        let c = solve_dense(a, b).ok_or(InterpolationError::SingularMatrixError)?;
+        Ok(Self {
+            centers: samples.iter().map(|&(p, _)| p).collect(),
+            weights: c.chunks_exact(2).map(|w| (w[0], w[1])).collect(),
+            epsilon,
+            kind,
+        })
+    }
+
+    /// Evaluates the interpolated vector at `(x, y)`.
+    pub fn value(&self, x: V, y: V) -> (V, V) {
+        self.centers.iter().zip(&self.weights).fold(
+            (V::zero(), V::zero()),
+            |(u, v), (center, weight)| {
+                let k = kernel(self.kind, self.epsilon, x - center.0, y - center.1);
+                (
+                    u + k[0][0] * weight.0 + k[0][1] * weight.1,
+                    v + k[1][0] * weight.0 + k[1][1] * weight.1,
+                )
+            },
+        )
+    }
+}
+
+/// Returns the 2 × 2 kernel matrix for the offset `(dx, dy)`.
+fn kernel<V: TranscendentalValue>(kind: VectorFieldKind, epsilon: V, dx: V, dy: V) -> [[V; 2]; 2] {
+    let two = V::from_integer(2);
+    let e2 = epsilon * epsilon;
+    let e4 = e2 * e2;
+    let r2 = dx * dx + dy * dy;
+    let phi = (-e2 * r2).exp();
+    // ∇∇ᵀφ = φ (4ε⁴ d dᵀ - 2ε² I)
+    let outer = [[dx * dx, dx * dy], [dy * dx, dy * dy]];
+    let (outer_scale, diagonal) = match kind {
+        VectorFieldKind::CurlFree => (-two * two * e4, two * e2),
+        VectorFieldKind::DivergenceFree => (two * two * e4, two * e2 - two * two * e4 * r2),
+    };
+    let mut k = [[V::zero(); 2]; 2];
+    for (i, row) in k.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            *entry = outer[i][j] * outer_scale;
+            if i == j {
+                *entry += diagonal;
+            }
+            *entry *= phi;
+        }
+    }
+    k
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::vector_field::{VectorFieldInterpolator, VectorFieldKind};
+    use crate::InterpolationError;
+
+    fn samples() -> Vec<((f64, f64), (f64, f64))> {
+        [(0.0, 0.0), (1.0, 0.2), (0.3, 1.1), (-0.8, 0.4), (0.5, -0.7)]
+            .iter()
+            .map(|&(x, y)| ((x, y), (y - x * x, x + 0.5 * y)))
+            .collect()
+    }
+
+    /// Returns the divergence and curl at `(x, y)` by central differences.
+    fn divergence_and_curl(field: &VectorFieldInterpolator<f64>, x: f64, y: f64) -> (f64, f64) {
+        let h = 1e-5;
+        let (u_xp, v_xp) = field.value(x + h, y);
+        let (u_xm, v_xm) = field.value(x - h, y);
+        let (u_yp, v_yp) = field.value(x, y + h);
+        let (u_ym, v_ym) = field.value(x, y - h);
+        (
+            (u_xp - u_xm + v_yp - v_ym) / (2.0 * h),
+            (v_xp - v_xm - u_yp + u_ym) / (2.0 * h),
+        )
+    }
+
+    #[test]
+    fn test_structure_is_preserved() {
+        let samples = samples();
+        for kind in [VectorFieldKind::DivergenceFree, VectorFieldKind::CurlFree] {
+            let field = VectorFieldInterpolator::try_new(&samples, 1.5, kind).unwrap();
+            for &((x, y), (u, v)) in &samples {
+                let (actual_u, actual_v) = field.value(x, y);
+                assert!((actual_u - u).abs() < 1e-9 && (actual_v - v).abs() < 1e-9);
+            }
+            for (x, y) in [(0.2, 0.3), (-0.4, 0.9), (1.3, -0.2)] {
+                let (divergence, curl) = divergence_and_curl(&field, x, y);
+                match kind {
+                    VectorFieldKind::DivergenceFree => assert!(divergence.abs() < 1e-6),
+                    VectorFieldKind::CurlFree => assert!(curl.abs() < 1e-6),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicate_positions() {
+        let mut samples = samples();
+        samples.push(samples[0]);
+        assert!(matches!(
+            VectorFieldInterpolator::try_new(&samples, 1.0, VectorFieldKind::CurlFree),
+            Err(InterpolationError::SingularMatrixError)
+        ));
+    }
+}
@@ -1,15 +1,69 @@
-use num_traits::{FromPrimitive, Num};
+use crate::interpolation::binary::BinaryFormatError;
+use crate::math::tridiagonal_matrix::MatrixValidationError;
+use num_traits::Num;
 #[cfg(feature = "decimal")]
 use rust_decimal::Decimal;
 use std::fmt::Debug;
 use std::ops::{AddAssign, DivAssign, MulAssign, Neg, SubAssign};
 use thiserror::Error;
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod interpolation;
-mod math;
+pub mod math;
+pub mod metadata;
+pub mod prelude;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+#[cfg(not(feature = "profiling"))]
+pub(crate) mod profiling;
+#[cfg(feature = "testutils")]
+pub mod testutils;
+pub mod tolerance;
+#[cfg(feature = "verification")]
+pub mod verification;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
 
+pub use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+pub use crate::interpolation::hermite_spline::HermiteSpline;
+pub use crate::interpolation::interpolator::Interpolator;
+pub use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+pub use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+
+/// The scalar type of the interpolants: `f32`, `f64`, `Decimal` with the `decimal` feature, and
+/// `f16` / `bf16` with the `half` feature.
+///
+/// The values must be `Copy`, which rules out heap-allocated arbitrary-precision floats.
+///
+/// Other types, such as the dual numbers of automatic differentiation, can implement the trait
+/// with just the arithmetic bounds, the defaults covering the rest, to differentiate through the
+/// construction and evaluation of a spline. They must be `'static`, as `nalgebra` requires of its
+/// scalars, so types borrowing a tape are out; a dual number should order and compare by its
+/// value alone, which places a query among the knots.
+///
+/// The slopes and polynomial coefficients of a spline are signed even for increasing data, hence
+/// the `Neg` bound, which excludes unsigned types. The interpolants build their integer constants
+/// with [`Self::from_integer`] and [`Self::from_count`], never failing, instead of the
+/// conversions of `FromPrimitive`.
+///
+/// # Exactness
+///
+/// Every interpolant returns the stored value, without arithmetic, for a query at one of its
+/// knots. For `Decimal`, whose operations are exact up to its 28 significant digits, the splines
+/// moreover reproduce data sampled from a line with representable slopes exactly, and polynomials
+/// up to their [`degree_exactness`](interpolation::natural_cubic_spline::NaturalCubicSpline::degree_exactness)
+/// as long as no division in the fit rounds.
+///
+/// # Thread safety
+///
+/// The values are `Send + Sync`, and the interpolants hold nothing else that is not: no caches or
+/// other interior mutability, so a fitted curve can be shared across threads behind an `Arc` or a
+/// plain reference, and every [`Interpolator`] is `Send + Sync` too.
 pub trait InterpolationValue:
     'static
+    + Send
+    + Sync
     + Num
     + Copy
     + PartialOrd
@@ -20,25 +74,376 @@ pub trait InterpolationValue:
     + SubAssign
     + MulAssign
     + DivAssign
-    + FromPrimitive
 {
+    /// Returns the number of representable values between `self` and `other`, or `None` if the
+    /// type has no such notion or either value is not finite.
+    ///
+    /// This backs [`Tolerance::Ulps`](tolerance::Tolerance::Ulps).
+    fn ulps_between(self, _other: Self) -> Option<u64> {
+        None
+    }
+
+    /// Returns `false` for NaN and infinite values, which the interpolants reject as input.
+    ///
+    /// The default only detects NaN, as the values that do not equal themselves.
+    #[allow(clippy::eq_op)]
+    fn is_finite(self) -> bool {
+        self == self
+    }
+
+    /// Returns `self` rounded down to a `usize`, or `None` if it is negative, too large, or the
+    /// type has no such conversion.
+    ///
+    /// This backs the constant-time segment lookup of uniformly spaced knots, which falls back to
+    /// a binary search on `None`.
+    fn floor_to_usize(self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the integer `n`.
+    ///
+    /// The default builds `n` from [`One::one`](num_traits::One::one) by doubling and adding,
+    /// which is exact for integers the type represents and cannot fail.
+    fn from_integer(n: i64) -> Self {
+        let magnitude = n.unsigned_abs();
+        let value: Self = from_binary(u64::BITS - magnitude.leading_zeros(), |bit| {
+            magnitude >> bit & 1 == 1
+        });
+        if n < 0 {
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// Returns the number `n`, e.g. of an index or of segments, as [`Self::from_integer`] does.
+    fn from_count(n: usize) -> Self {
+        from_binary(usize::BITS - n.leading_zeros(), |bit| n >> bit & 1 == 1)
+    }
+}
+
+/// Builds the non-negative integer with the given binary digits, most significant first.
+fn from_binary<V: InterpolationValue>(digits: u32, is_set: impl Fn(u32) -> bool) -> V {
+    (0..digits).rev().fold(V::zero(), |acc, bit| {
+        let doubled = acc + acc;
+        if is_set(bit) {
+            doubled + V::one()
+        } else {
+            doubled
+        }
+    })
+}
+
+macro_rules! impl_float_ulps_between {
+    ($float:ty, $signed:ty) => {
+        fn ulps_between(self, other: Self) -> Option<u64> {
+            if !(self.is_finite() && other.is_finite()) {
+                return None;
+            }
+            // Map the sign-magnitude bit patterns onto a monotonic integer line.
+            let key = |v: $float| {
+                let bits = v.to_bits() as $signed;
+                if bits < 0 {
+                    <$signed>::MIN - bits
+                } else {
+                    bits
+                }
+            };
+            Some(key(self).abs_diff(key(other)).into())
+        }
+    };
+}
+
+impl InterpolationValue for f32 {
+    impl_float_ulps_between!(f32, i32);
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    fn floor_to_usize(self) -> Option<usize> {
+        // the float-to-integer cast truncates towards zero, i.e. rounds non-negative values down
+        (self >= 0.0 && self < usize::MAX as f32).then_some(self as usize)
+    }
+
+    fn from_integer(n: i64) -> Self {
+        n as f32
+    }
+
+    fn from_count(n: usize) -> Self {
+        n as f32
+    }
+}
+
+impl InterpolationValue for f64 {
+    impl_float_ulps_between!(f64, i64);
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    fn floor_to_usize(self) -> Option<usize> {
+        // the float-to-integer cast truncates towards zero, i.e. rounds non-negative values down
+        (self >= 0.0 && self < usize::MAX as f64).then_some(self as usize)
+    }
+
+    fn from_integer(n: i64) -> Self {
+        n as f64
+    }
+
+    fn from_count(n: usize) -> Self {
+        n as f64
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl InterpolationValue for Decimal {
+    fn floor_to_usize(self) -> Option<usize> {
+        num_traits::ToPrimitive::to_usize(&self.floor())
+    }
+
+    fn from_integer(n: i64) -> Self {
+        Decimal::from(n)
+    }
+
+    fn from_count(n: usize) -> Self {
+        Decimal::from(n)
+    }
 }
 
-impl InterpolationValue for f32 {}
+/// Implements [`InterpolationValue`] and [`TranscendentalValue`] for the 16-bit floats of `half`,
+/// computing the transcendental functions in `f32`.
+#[cfg(feature = "half")]
+macro_rules! impl_half_value {
+    ($($half:ty),*) => {
+        $(
+            impl InterpolationValue for $half {
+                impl_float_ulps_between!($half, i16);
 
-impl InterpolationValue for f64 {}
+                fn is_finite(self) -> bool {
+                    <$half>::is_finite(self)
+                }
 
+                fn floor_to_usize(self) -> Option<usize> {
+                    InterpolationValue::floor_to_usize(self.to_f32())
+                }
+
+                fn from_integer(n: i64) -> Self {
+                    <$half>::from_f64(n as f64)
+                }
+
+                fn from_count(n: usize) -> Self {
+                    <$half>::from_f64(n as f64)
+                }
+            }
+
+            impl TranscendentalValue for $half {
+                fn sqrt(self) -> Self {
+                    <$half>::from_f32(self.to_f32().sqrt())
+                }
+
+                fn exp(self) -> Self {
+                    <$half>::from_f32(self.to_f32().exp())
+                }
+
+                fn ln(self) -> Self {
+                    <$half>::from_f32(self.to_f32().ln())
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "half")]
+impl_half_value!(half::f16, half::bf16);
+
+/// An [`InterpolationValue`] supporting the transcendental functions needed by kernel methods.
+///
+/// The functions never panic: an implementation without infinities saturates instead.
+pub trait TranscendentalValue: InterpolationValue {
+    /// Returns the square root; only called with non-negative arguments.
+    fn sqrt(self) -> Self;
+    /// Returns `e^self`.
+    fn exp(self) -> Self;
+    /// Returns the natural logarithm; only called with positive arguments.
+    fn ln(self) -> Self;
+}
+
+macro_rules! impl_float_transcendental_value {
+    ($($float:ty),*) => {
+        $(
+            impl TranscendentalValue for $float {
+                fn sqrt(self) -> Self {
+                    <$float>::sqrt(self)
+                }
+
+                fn exp(self) -> Self {
+                    <$float>::exp(self)
+                }
+
+                fn ln(self) -> Self {
+                    <$float>::ln(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_float_transcendental_value!(f32, f64);
+
+// `Decimal` has neither infinities nor NaN, so the results out of its range saturate instead of
+// panicking as `MathematicalOps` does
 #[cfg(feature = "decimal")]
-impl InterpolationValue for Decimal {}
+impl TranscendentalValue for Decimal {
+    /// Returns the square root, and zero for a negative argument.
+    fn sqrt(self) -> Self {
+        rust_decimal::MathematicalOps::sqrt(&self).unwrap_or(Decimal::ZERO)
+    }
 
+    /// Returns `e^self`, zero where it underflows and `Decimal::MAX` where it overflows.
+    fn exp(self) -> Self {
+        rust_decimal::MathematicalOps::checked_exp(&self).unwrap_or(if self.is_sign_negative() {
+            Decimal::ZERO
+        } else {
+            Decimal::MAX
+        })
+    }
+
+    /// Returns the natural logarithm, and `Decimal::MIN` for a non-positive argument.
+    fn ln(self) -> Self {
+        rust_decimal::MathematicalOps::checked_ln(&self).unwrap_or(Decimal::MIN)
+    }
+}
+
+/// The error type of the interpolants of this crate.
+///
+/// New variants may be added, e.g. by Cargo features, so a `match` needs a wildcard arm.
 #[derive(Error, Debug)]
-pub enum HermiteSplineError<V: InterpolationValue> {
-    #[error("points must be sorted")]
-    PointOrderError,
+#[non_exhaustive]
+pub enum InterpolationError<V: InterpolationValue> {
+    /// The point with this index is smaller than its predecessor.
+    #[error("point {0} is out of order")]
+    PointOrderError(usize),
+    /// Two points share this x-coordinate where distinct ones are required.
+    #[error("duplicate x-coordinate: {0}")]
+    DuplicatePointError(V),
+    /// The point with this index has a non-finite coordinate.
+    #[error("point {0} has a non-finite coordinate")]
+    NonFiniteError(usize),
     #[error("out of lower bound: {0}")]
     OutOfLowerBound(V),
     #[error("out of upper bound: {0}")]
     OutOfUpperBound(V),
     #[error("length of inputs: {0} is not enough points for construction")]
     InsufficientPointsError(usize),
+    #[error("coefficient rows must be non-empty, of equal length and one per segment")]
+    CoefficientShapeError,
+    #[error("expected {0} grid values but got {1}")]
+    GridShapeError(usize, usize),
+    #[error("the interpolation system is singular")]
+    SingularMatrixError,
+    #[error("expected {0} values but got {1}")]
+    LengthMismatchError(usize, usize),
+    /// The weight with this index is not positive and finite.
+    #[error("weight {0} is not positive and finite")]
+    InvalidWeightError(usize),
+    /// The smoothing parameter is negative or not finite.
+    #[error("smoothing parameter {0} is negative or not finite")]
+    SmoothingParameterError(V),
+    /// This hyperparameter of a Gaussian process is out of its range or not finite.
+    #[error("hyperparameter {0} is out of range or not finite")]
+    HyperparameterError(V),
+    /// No spline of the requested shape interpolates the points around the one with this index.
+    #[error("no spline of the requested shape interpolates the points around point {0}")]
+    ShapeError(usize),
+    /// This seam of a composite interpolant is not an interior point after the previous seam.
+    #[error("seam {0} is not an interior point after the previous seam")]
+    SeamError(V),
+    /// The point with this index has a non-positive value where its logarithm is interpolated.
+    #[error("point {0} has a non-positive value")]
+    NonPositiveValueError(usize),
+    /// The query is NaN or infinite, which has no place among the knots.
+    #[error("query {0} is not finite")]
+    NonFiniteInput(V),
+    #[error("linear solver failed: {0}")]
+    SolverError(#[from] MatrixValidationError),
+    /// A binary record could not be decoded.
+    #[error("invalid binary record: {0}")]
+    FormatError(#[from] BinaryFormatError),
+    /// Points could not be read from CSV or JSON data.
+    #[cfg(feature = "io")]
+    #[error("failed to load points: {0}")]
+    LoadError(#[from] interpolation::io::LoadError),
+}
+
+/// Deprecated alias of [`InterpolationError`], which is not specific to Hermite splines.
+#[deprecated(note = "use `spline_interpolation::InterpolationError` instead")]
+pub type HermiteSplineError<V> = InterpolationError<V>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_binary, InterpolationValue};
+
+    #[test]
+    fn test_integer_constants() {
+        for n in [0_u64, 1, 2, 6, 384, 1_000_000_007] {
+            let built: f64 = from_binary(u64::BITS - n.leading_zeros(), |bit| n >> bit & 1 == 1);
+            assert_eq!(built, n as f64);
+        }
+        assert_eq!(f64::from_integer(-384), -384.0);
+        assert_eq!(f32::from_count(7), 7.0);
+        #[cfg(feature = "decimal")]
+        assert_eq!(
+            rust_decimal::Decimal::from_integer(-3),
+            rust_decimal::Decimal::new(-3, 0)
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_transcendental_saturates() {
+        use crate::TranscendentalValue;
+        use rust_decimal::Decimal;
+
+        assert_eq!(Decimal::from(-200).exp(), Decimal::ZERO);
+        assert_eq!(Decimal::from(200).exp(), Decimal::MAX);
+        assert_eq!(Decimal::from(-1).sqrt(), Decimal::ZERO);
+        assert_eq!(Decimal::ZERO.ln(), Decimal::MIN);
+        assert_eq!(Decimal::ZERO.exp(), Decimal::ONE);
+        assert_eq!(Decimal::from(4).sqrt(), Decimal::TWO);
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn test_half_precision() {
+        use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+        use crate::tolerance::Tolerance;
+        use half::{bf16, f16};
+
+        let points = [(0.0_f32, 1.0), (0.5, 0.25), (1.25, 0.5), (2.0, -0.75)];
+        let reference = NaturalCubicSpline::try_new(&points).unwrap();
+        let halves: Vec<_> = points
+            .iter()
+            .map(|&(x, y)| (f16::from_f32(x), f16::from_f32(y)))
+            .collect();
+        let spline = NaturalCubicSpline::try_new(&halves)
+            .unwrap()
+            .with_tolerance(Tolerance::Ulps(2));
+        let bf16_points: Vec<_> = points
+            .iter()
+            .map(|&(x, y)| (bf16::from_f32(x), bf16::from_f32(y)))
+            .collect();
+        let bf16_spline = NaturalCubicSpline::try_new(&bf16_points).unwrap();
+        for x in [0.1_f32, 0.7, 1.3, 1.9] {
+            let expected = reference.try_value(x).unwrap();
+            let actual = spline.try_value(f16::from_f32(x)).unwrap().to_f32();
+            assert!((actual - expected).abs() < 1e-2);
+            let actual = bf16_spline.try_value(bf16::from_f32(x)).unwrap().to_f32();
+            assert!((actual - expected).abs() < 5e-2);
+        }
+        // the next f16 above the knot at 0.5 snaps to it
+        let next = f16::from_bits(f16::from_f32(0.5).to_bits() + 1);
+        assert_eq!(spline.try_value(next).unwrap(), f16::from_f32(0.25));
+        assert_eq!(f16::from_count(3).floor_to_usize(), Some(3));
+    }
 }
@@ -1 +1,50 @@
-pub(crate) mod tridiagonal_matrix;
+//! Linear algebra used to fit the splines.
+
+#[cfg(feature = "linalg")]
+pub mod banded_matrix;
+#[cfg(not(feature = "linalg"))]
+#[allow(dead_code)]
+pub(crate) mod banded_matrix;
+#[cfg(feature = "linalg")]
+pub mod cyclic_tridiagonal_matrix;
+#[cfg(not(feature = "linalg"))]
+#[allow(dead_code)]
+pub(crate) mod cyclic_tridiagonal_matrix;
+pub(crate) mod dense_matrix;
+pub(crate) mod roots;
+pub mod tridiagonal_matrix;
+
+use crate::InterpolationValue;
+
+/// Returns the gap between one and the next larger value of the type, or a bound on it.
+pub(crate) fn epsilon<V: InterpolationValue>() -> V {
+    let two = V::from_integer(2);
+    let mut epsilon = V::one();
+    for _ in 0..u8::MAX {
+        if V::one() + epsilon / two == V::one() {
+            break;
+        }
+        epsilon /= two;
+    }
+    epsilon
+}
+
+/// Returns the absolute value of `v`.
+pub(crate) fn abs<V: InterpolationValue>(v: V) -> V {
+    if v < V::zero() {
+        -v
+    } else {
+        v
+    }
+}
+
+/// Returns the Newton divided differences `f[x_0], f[x_0, x_1], ..., f[x_0, ..., x_n]`.
+pub(crate) fn divided_differences<V: InterpolationValue>(xs: &[V], ys: &[V]) -> Vec<V> {
+    let mut table = ys.to_vec();
+    for k in 1..xs.len() {
+        for i in (k..xs.len()).rev() {
+            table[i] = (table[i] - table[i - 1]) / (xs[i] - xs[i - k]);
+        }
+    }
+    table
+}
@@ -0,0 +1,313 @@
+//! Banded linear systems, solved by LU factorization with partial pivoting.
+
+use crate::math::abs;
+use crate::math::tridiagonal_matrix::MatrixValidationError;
+use crate::InterpolationValue;
+
+/// A square matrix whose nonzero entries lie within `lower_bandwidth` sub-diagonals and
+/// `upper_bandwidth` super-diagonals, as the collocation matrices of B-splines.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "linalg")]
+/// # {
+/// use spline_interpolation::math::banded_matrix::BandedMatrix;
+///
+/// // [[0, 1, 0], [2, 1, 1], [0, 1, 2]] needs a row interchange
+/// let mut matrix = BandedMatrix::try_new(3, 1, 1).unwrap();
+/// for (i, j, value) in [(0, 1, 1.0), (1, 0, 2.0), (1, 1, 1.0), (1, 2, 1.0), (2, 1, 1.0), (2, 2, 2.0)] {
+///     matrix.set(i, j, value).unwrap();
+/// }
+/// assert_eq!(matrix.solve(&[1.0, 4.0, 3.0]).unwrap(), [1.0, 1.0, 1.0]);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandedMatrix<V: InterpolationValue> {
+    size: usize,
+    lower_bandwidth: usize,
+    upper_bandwidth: usize,
+    // entries[i * width + (j + lower_bandwidth - i)] is a[i][j]
+    entries: Vec<V>,
+}
+
+impl<V: InterpolationValue> BandedMatrix<V> {
+    /// Constructs a zero `size × size` matrix with the given bandwidths.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::MatrixShapeError` if `size` is zero.
+    pub fn try_new(
+        size: usize,
+        lower_bandwidth: usize,
+        upper_bandwidth: usize,
+    ) -> Result<Self, MatrixValidationError> {
+        if size == 0 {
+            return Err(MatrixValidationError::MatrixShapeError);
+        }
+        Ok(Self {
+            size,
+            lower_bandwidth,
+            upper_bandwidth,
+            entries: vec![V::zero(); size * (lower_bandwidth + upper_bandwidth + 1)],
+        })
+    }
+
+    /// Returns the number of rows.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of sub-diagonals.
+    pub fn lower_bandwidth(&self) -> usize {
+        self.lower_bandwidth
+    }
+
+    /// Returns the number of super-diagonals.
+    pub fn upper_bandwidth(&self) -> usize {
+        self.upper_bandwidth
+    }
+
+    fn offset(&self, i: usize, j: usize) -> Option<usize> {
+        let in_band = i < self.size
+            && j < self.size
+            && j + self.lower_bandwidth >= i
+            && j <= i + self.upper_bandwidth;
+        in_band.then(|| {
+            i * (self.lower_bandwidth + self.upper_bandwidth + 1) + j + self.lower_bandwidth - i
+        })
+    }
+
+    /// Returns `a[i][j]`, which is zero outside the band.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> V {
+        assert!(
+            i < self.size && j < self.size,
+            "({i}, {j}) is out of bounds"
+        );
+        self.offset(i, j)
+            .map_or(V::zero(), |offset| self.entries[offset])
+    }
+
+    /// Sets `a[i][j]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::MatrixShapeError` if `(i, j)` lies outside the band, and
+    /// `MatrixValidationError::NonFiniteEntryError(i, j)` if `value` is NaN or infinite.
+    pub fn set(&mut self, i: usize, j: usize, value: V) -> Result<(), MatrixValidationError> {
+        let offset = self
+            .offset(i, j)
+            .ok_or(MatrixValidationError::MatrixShapeError)?;
+        if !value.is_finite() {
+            return Err(MatrixValidationError::NonFiniteEntryError(i, j));
+        }
+        self.entries[offset] = value;
+        Ok(())
+    }
+
+    /// Computes the LU factorization with partial pivoting, which solves for any number of
+    /// right-hand sides without repeating the elimination.
+    ///
+    /// Row interchanges widen the upper band of `U` by `lower_bandwidth`, so the factorization
+    /// takes `O(n · lower_bandwidth · (lower_bandwidth + upper_bandwidth))` operations.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::ZeroPivotError(column)` if the matrix is singular, and
+    /// `MatrixValidationError::NonFiniteEntryError(row, column)` if the elimination overflows.
+    pub fn factorize(&self) -> Result<BandedLU<V>, MatrixValidationError> {
+        let (n, kl) = (self.size, self.lower_bandwidth);
+        let ku = self.upper_bandwidth + kl;
+        let width = kl + ku + 1;
+        let mut lu = vec![V::zero(); n * width];
+        for i in 0..n {
+            for j in i.saturating_sub(kl)..n.min(i + self.upper_bandwidth + 1) {
+                lu[i * width + j + kl - i] = self.get(i, j);
+            }
+        }
+        let at = |i: usize, j: usize| i * width + j + kl - i;
+        let mut pivots = Vec::with_capacity(n);
+        for k in 0..n {
+            let last_row = n.min(k + kl + 1);
+            let last_column = n.min(k + ku + 1);
+            if let Some(row) = (k..last_row).find(|&i| !lu[at(i, k)].is_finite()) {
+                return Err(MatrixValidationError::NonFiniteEntryError(row, k));
+            }
+            let pivot = (k + 1..last_row).fold(k, |pivot, i| {
+                if abs(lu[at(i, k)]) > abs(lu[at(pivot, k)]) {
+                    i
+                } else {
+                    pivot
+                }
+            });
+            if lu[at(pivot, k)].is_zero() {
+                return Err(MatrixValidationError::ZeroPivotError(k));
+            }
+            if pivot != k {
+                for j in k..last_column {
+                    lu.swap(at(k, j), at(pivot, j));
+                }
+            }
+            pivots.push(pivot);
+            for i in k + 1..last_row {
+                let multiplier = lu[at(i, k)] / lu[at(k, k)];
+                lu[at(i, k)] = multiplier;
+                if multiplier.is_zero() {
+                    continue;
+                }
+                for j in k + 1..last_column {
+                    let temp = multiplier * lu[at(k, j)];
+                    lu[at(i, j)] -= temp;
+                }
+            }
+        }
+        Ok(BandedLU {
+            size: n,
+            lower_bandwidth: kl,
+            upper_bandwidth: ku,
+            entries: lu,
+            pivots,
+        })
+    }
+
+    /// Solves `Ax = b` once; use [`Self::factorize`] to solve for several right-hand sides.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::factorize`] and [`BandedLU::solve`].
+    pub fn solve(&self, b: &[V]) -> Result<Vec<V>, MatrixValidationError> {
+        self.factorize()?.solve(b)
+    }
+}
+
+/// The LU factorization with partial pivoting of a [`BandedMatrix`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandedLU<V: InterpolationValue> {
+    size: usize,
+    lower_bandwidth: usize,
+    // of U, including the fill-in of the row interchanges
+    upper_bandwidth: usize,
+    // the multipliers of L below the diagonal and U on and above it, stored as in BandedMatrix
+    entries: Vec<V>,
+    // row k was interchanged with row pivots[k] at step k
+    pivots: Vec<usize>,
+}
+
+impl<V: InterpolationValue> BandedLU<V> {
+    /// Solves `Ax = b`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::RightHandSideLengthError(expected, actual)` if `b` does not
+    /// have one entry per row.
+    pub fn solve(&self, b: &[V]) -> Result<Vec<V>, MatrixValidationError> {
+        let (n, kl, ku) = (self.size, self.lower_bandwidth, self.upper_bandwidth);
+        if b.len() != n {
+            return Err(MatrixValidationError::RightHandSideLengthError(n, b.len()));
+        }
+        let width = kl + ku + 1;
+        let at = |i: usize, j: usize| self.entries[i * width + j + kl - i];
+        let mut x = b.to_vec();
+        for k in 0..n {
+            x.swap(k, self.pivots[k]);
+            for i in k + 1..n.min(k + kl + 1) {
+                let temp = at(i, k) * x[k];
+                x[i] -= temp;
+            }
+        }
+        for i in (0..n).rev() {
+            let sum = (i + 1..n.min(i + ku + 1)).fold(x[i], |sum, j| sum - at(i, j) * x[j]);
+            x[i] = sum / at(i, i);
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::banded_matrix::BandedMatrix;
+    use crate::math::tridiagonal_matrix::MatrixValidationError;
+
+    fn residual(matrix: &BandedMatrix<f64>, x: &[f64], b: &[f64]) -> f64 {
+        (0..matrix.size())
+            .map(|i| {
+                let ax: f64 = (0..matrix.size()).map(|j| matrix.get(i, j) * x[j]).sum();
+                (ax - b[i]).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_solve_with_pivoting() {
+        // a pentadiagonal-like matrix with one sub- and two super-diagonals and zeros on the
+        // diagonal, so every step needs a row interchange
+        let n = 7;
+        let mut matrix = BandedMatrix::try_new(n, 1, 2).unwrap();
+        for i in 0..n {
+            for j in i.saturating_sub(1)..n.min(i + 3) {
+                if i != j {
+                    matrix.set(i, j, (i + 2 * j + 1) as f64 * 0.5).unwrap();
+                }
+            }
+        }
+        let lu = matrix.factorize().unwrap();
+        for b in [[1.0; 7], [3.0, -1.0, 4.0, 1.0, -5.0, 9.0, 2.0]] {
+            let x = lu.solve(&b).unwrap();
+            assert!(residual(&matrix, &x, &b) < 1e-12);
+        }
+        assert_eq!(
+            lu.solve(&[1.0; 6]),
+            Err(MatrixValidationError::RightHandSideLengthError(7, 6))
+        );
+        assert_eq!(
+            matrix.set(0, 3, 1.0),
+            Err(MatrixValidationError::MatrixShapeError)
+        );
+        assert_eq!(matrix.get(0, 3), 0.0);
+    }
+
+    #[test]
+    fn test_singular() {
+        // the last two rows are equal
+        let mut matrix = BandedMatrix::try_new(3, 1, 1).unwrap();
+        for (i, j, value) in [
+            (0, 0, 1.0),
+            (1, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 1, 1.0),
+            (2, 2, 1.0),
+        ] {
+            matrix.set(i, j, value).unwrap();
+        }
+        assert_eq!(
+            matrix.factorize(),
+            Err(MatrixValidationError::ZeroPivotError(2))
+        );
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let mut matrix = BandedMatrix::try_new(2, 1, 1).unwrap();
+        assert_eq!(
+            matrix.set(0, 1, f64::NAN),
+            Err(MatrixValidationError::NonFiniteEntryError(0, 1))
+        );
+        assert_eq!(
+            matrix.set(1, 0, f64::NEG_INFINITY),
+            Err(MatrixValidationError::NonFiniteEntryError(1, 0))
+        );
+        assert_eq!(matrix.get(0, 1), 0.0);
+        // finite entries whose elimination overflows
+        for (i, j, value) in [(0, 0, 1.0), (0, 1, 1.5e308), (1, 0, 1.0), (1, 1, -1.5e308)] {
+            matrix.set(i, j, value).unwrap();
+        }
+        assert_eq!(
+            matrix.factorize(),
+            Err(MatrixValidationError::NonFiniteEntryError(1, 1))
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Cyclic tridiagonal linear systems, as those of periodic splines, solved with the
+//! Sherman–Morrison formula on top of the Thomas algorithm.
+
+use crate::math::tridiagonal_matrix::{MatrixValidationError, TridiagonalLU, TridiagonalMatrix};
+use crate::InterpolationValue;
+
+/// A square tridiagonal matrix with two additional corner entries `a[0][n - 1]` and
+/// `a[n - 1][0]`, i.e. whose diagonals wrap around.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "linalg")]
+/// # {
+/// use spline_interpolation::math::cyclic_tridiagonal_matrix::CyclicTridiagonalMatrix;
+///
+/// // [[4, 1, 1], [1, 4, 1], [1, 1, 4]]
+/// let matrix =
+///     CyclicTridiagonalMatrix::try_new(vec![1.0_f64; 3], vec![4.0; 3], vec![1.0; 3]).unwrap();
+/// let x = matrix.solve(&[6.0, 12.0, 18.0]).unwrap();
+/// for (actual, expected) in x.into_iter().zip([0.0, 2.0, 4.0]) {
+///     assert!((actual - expected).abs() < 1e-12);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CyclicTridiagonalMatrix<V: InterpolationValue> {
+    upper_diagonal: Vec<V>,
+    diagonal: Vec<V>,
+    lower_diagonal: Vec<V>,
+}
+
+impl<V: InterpolationValue> CyclicTridiagonalMatrix<V> {
+    /// Constructs a new `CyclicTridiagonalMatrix` from its wrapped diagonals.
+    ///
+    /// # Arguments
+    ///
+    /// * `upper_diagonal` - The entries `a[i][(i + 1) % n]`.
+    /// * `diagonal` - The entries `a[i][i]`.
+    /// * `lower_diagonal` - The entries `a[i][(i + n - 1) % n]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::MatrixShapeError` if the diagonals do not have the same
+    /// length `n`, or if `n < 3`.
+    pub fn try_new(
+        upper_diagonal: Vec<V>,
+        diagonal: Vec<V>,
+        lower_diagonal: Vec<V>,
+    ) -> Result<Self, MatrixValidationError> {
+        if !(upper_diagonal.len() == diagonal.len()
+            && lower_diagonal.len() == diagonal.len()
+            && diagonal.len() >= 3)
+        {
+            return Err(MatrixValidationError::MatrixShapeError);
+        }
+        Ok(Self {
+            upper_diagonal,
+            diagonal,
+            lower_diagonal,
+        })
+    }
+
+    /// Returns the number of rows.
+    pub fn size(&self) -> usize {
+        self.diagonal.len()
+    }
+
+    /// Factorizes the matrix as a rank-one update `T + u vᵀ` of a tridiagonal matrix `T`, which
+    /// solves for any number of right-hand sides without repeating the elimination.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::ZeroPivotError(row)` if `T` has a zero pivot, and
+    /// `MatrixValidationError::SingularMatrixError` if the update makes the matrix singular.
+    pub fn factorize(&self) -> Result<CyclicTridiagonalLU<V>, MatrixValidationError> {
+        let n = self.size();
+        // a[0][n - 1] and a[n - 1][0]
+        let (beta, alpha) = (self.lower_diagonal[0], self.upper_diagonal[n - 1]);
+        let gamma = if self.diagonal[0].is_zero() {
+            -V::one()
+        } else {
+            -self.diagonal[0]
+        };
+        let mut diagonal = self.diagonal.clone();
+        diagonal[0] -= gamma;
+        diagonal[n - 1] -= alpha * beta / gamma;
+        let tridiagonal = TridiagonalMatrix::try_new(
+            self.upper_diagonal[..n - 1].to_vec(),
+            diagonal,
+            self.lower_diagonal[1..].to_vec(),
+        )?
+        .factorize()?;
+        let mut u = vec![V::zero(); n];
+        u[0] = gamma;
+        u[n - 1] = alpha;
+        let correction = tridiagonal.solve_unchecked(&u);
+        let last_weight = beta / gamma;
+        let denominator = V::one() + correction[0] + last_weight * correction[n - 1];
+        if denominator.is_zero() {
+            return Err(MatrixValidationError::SingularMatrixError);
+        }
+        Ok(CyclicTridiagonalLU {
+            tridiagonal,
+            correction,
+            last_weight,
+            denominator,
+        })
+    }
+
+    /// Solves `Ax = b` once; use [`Self::factorize`] to solve for several right-hand sides.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::factorize`] and [`CyclicTridiagonalLU::solve`].
+    pub fn solve(&self, b: &[V]) -> Result<Vec<V>, MatrixValidationError> {
+        self.factorize()?.solve(b)
+    }
+}
+
+/// The factorization of a [`CyclicTridiagonalMatrix`], solving for any number of right-hand
+/// sides in `O(n)` each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CyclicTridiagonalLU<V: InterpolationValue> {
+    tridiagonal: TridiagonalLU<V>,
+    // T⁻¹u
+    correction: Vec<V>,
+    // v = (1, 0, ..., 0, last_weight)
+    last_weight: V,
+    // 1 + vᵀT⁻¹u
+    denominator: V,
+}
+
+impl<V: InterpolationValue> CyclicTridiagonalLU<V> {
+    /// Solves `Ax = b`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::RightHandSideLengthError(expected, actual)` if `b` does not
+    /// have one entry per row.
+    pub fn solve(&self, b: &[V]) -> Result<Vec<V>, MatrixValidationError> {
+        let mut x = self.tridiagonal.solve(b)?;
+        let n = x.len();
+        let factor = (x[0] + self.last_weight * x[n - 1]) / self.denominator;
+        for (x, &correction) in x.iter_mut().zip(&self.correction) {
+            *x -= factor * correction;
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::cyclic_tridiagonal_matrix::CyclicTridiagonalMatrix;
+    use crate::math::tridiagonal_matrix::MatrixValidationError;
+
+    #[test]
+    fn test_periodic_system() {
+        let n = 6;
+        let upper: Vec<f64> = (0..n).map(|i| 0.5 + 0.1 * i as f64).collect();
+        let lower: Vec<f64> = (0..n).map(|i| 1.0 - 0.05 * i as f64).collect();
+        let diagonal: Vec<f64> = (0..n).map(|i| 4.0 + (i % 2) as f64).collect();
+        let matrix =
+            CyclicTridiagonalMatrix::try_new(upper.clone(), diagonal.clone(), lower.clone())
+                .unwrap();
+        let lu = matrix.factorize().unwrap();
+        let b = [1.0, -2.0, 3.0, 0.5, 0.0, 4.0];
+        let x = lu.solve(&b).unwrap();
+        for i in 0..n {
+            let ax = lower[i] * x[(i + n - 1) % n] + diagonal[i] * x[i] + upper[i] * x[(i + 1) % n];
+            assert!((ax - b[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(
+            CyclicTridiagonalMatrix::try_new(vec![1.0; 2], vec![4.0; 2], vec![1.0; 2]),
+            Err(MatrixValidationError::MatrixShapeError)
+        );
+        // all ones
+        let matrix =
+            CyclicTridiagonalMatrix::try_new(vec![1.0; 3], vec![1.0; 3], vec![1.0; 3]).unwrap();
+        assert!(matrix.factorize().is_err());
+        let matrix =
+            CyclicTridiagonalMatrix::try_new(vec![1.0; 3], vec![4.0; 3], vec![1.0; 3]).unwrap();
+        assert_eq!(
+            matrix.solve(&[1.0; 4]),
+            Err(MatrixValidationError::RightHandSideLengthError(3, 4))
+        );
+    }
+}
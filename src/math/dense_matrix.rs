@@ -0,0 +1,176 @@
+use crate::math::{abs, epsilon};
+use crate::InterpolationValue;
+#[cfg(feature = "gp")]
+use crate::TranscendentalValue;
+
+/// Solves `Ax = b` for a dense row-major `n × n` matrix by Gaussian elimination with partial
+/// pivoting.
+///
+/// Returns `None` if the matrix is singular to working precision, i.e. a pivot is within `n`
+/// epsilons of the largest entry, or if an entry is or becomes NaN or infinite.
+pub(crate) fn solve_dense<V: InterpolationValue>(mut a: Vec<V>, mut b: Vec<V>) -> Option<Vec<V>> {
+    let n = b.len();
+    debug_assert_eq!(a.len(), n * n);
+    if !a.iter().all(|&v| v.is_finite()) {
+        return None;
+    }
+    let largest = a.iter().fold(
+        V::zero(),
+        |largest, &v| {
+            if abs(v) > largest {
+                abs(v)
+            } else {
+                largest
+            }
+        },
+    );
+    let threshold = V::from_count(n) * epsilon::<V>() * largest;
+    for k in 0..n {
+        let pivot = (k + 1..n).fold(k, |pivot, i| {
+            if abs(a[i * n + k]) > abs(a[pivot * n + k]) {
+                i
+            } else {
+                pivot
+            }
+        });
+        let magnitude = abs(a[pivot * n + k]);
+        if !magnitude.is_finite() || magnitude <= threshold {
+            return None;
+        }
+        if pivot != k {
+            for col in 0..n {
+                a.swap(k * n + col, pivot * n + col);
+            }
+            b.swap(k, pivot);
+        }
+        for i in k + 1..n {
+            let factor = a[i * n + k] / a[k * n + k];
+            if factor.is_zero() {
+                continue;
+            }
+            for col in k..n {
+                let temp = factor * a[k * n + col];
+                a[i * n + col] -= temp;
+            }
+            let temp = factor * b[k];
+            b[i] -= temp;
+        }
+    }
+    for k in (0..n).rev() {
+        let mut sum = b[k];
+        for col in k + 1..n {
+            sum -= a[k * n + col] * b[col];
+        }
+        b[k] = sum / a[k * n + k];
+    }
+    Some(b)
+}
+
+/// Factors a symmetric positive definite row-major `n × n` matrix as `L Lᵀ`, reading its lower
+/// triangle only, and returns the row-major lower triangular `L`.
+///
+/// Returns `None` if the matrix is not positive definite.
+#[cfg(feature = "gp")]
+pub(crate) fn cholesky<V: TranscendentalValue>(a: &[V], n: usize) -> Option<Vec<V>> {
+    debug_assert_eq!(a.len(), n * n);
+    let mut l = vec![V::zero(); n * n];
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = a[i * n + j];
+            for k in 0..j {
+                sum -= l[i * n + k] * l[j * n + k];
+            }
+            if i == j {
+                if sum <= V::zero() || !sum.is_finite() {
+                    return None;
+                }
+                l[i * n + i] = sum.sqrt();
+            } else {
+                l[i * n + j] = sum / l[j * n + j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solves `Lx = b` in place for a row-major lower triangular `L` with a non-zero diagonal.
+#[cfg(feature = "gp")]
+pub(crate) fn solve_lower<V: InterpolationValue>(l: &[V], b: &mut [V]) {
+    let n = b.len();
+    for i in 0..n {
+        let mut sum = b[i];
+        for k in 0..i {
+            sum -= l[i * n + k] * b[k];
+        }
+        b[i] = sum / l[i * n + i];
+    }
+}
+
+/// Solves `Lᵀx = b` in place for a row-major lower triangular `L` with a non-zero diagonal.
+#[cfg(feature = "gp")]
+pub(crate) fn solve_lower_transposed<V: InterpolationValue>(l: &[V], b: &mut [V]) {
+    let n = b.len();
+    for i in (0..n).rev() {
+        let mut sum = b[i];
+        for k in i + 1..n {
+            sum -= l[k * n + i] * b[k];
+        }
+        b[i] = sum / l[i * n + i];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::dense_matrix::solve_dense;
+
+    #[test]
+    fn test_solve_with_pivoting() {
+        let a = vec![0.0_f64, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0];
+        let x = solve_dense(a, vec![7.0, 6.0, 13.0]).unwrap();
+        for (actual, expected) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_singular() {
+        let a = vec![1.0, 2.0, 2.0, 4.0];
+        assert!(solve_dense(a, vec![1.0, 2.0]).is_none());
+        // singular up to rounding: the elimination leaves a pivot of one ulp rather than zero
+        let a = vec![0.1, 0.3, 0.2, 0.6000000000000001];
+        assert!(solve_dense(a, vec![1.0, 2.0]).is_none());
+        // the same relative gap at any scale
+        let scaled = vec![1e-200, 1e-200, 1e-200, 2e-200];
+        assert_eq!(
+            solve_dense(scaled, vec![1e-200, 1e-200]).unwrap(),
+            [1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_non_finite() {
+        assert!(solve_dense(vec![1.0, f64::NAN, 0.0, 1.0], vec![1.0, 1.0]).is_none());
+        assert!(solve_dense(vec![f64::INFINITY, 0.0, 0.0, 1.0], vec![1.0, 1.0]).is_none());
+        // finite entries whose elimination overflows
+        let a = vec![1.0, 1.5e308, 1.0, -1.5e308];
+        assert!(solve_dense(a, vec![1.0, 1.0]).is_none());
+    }
+
+    #[cfg(feature = "gp")]
+    #[test]
+    fn test_cholesky() {
+        use crate::math::dense_matrix::{cholesky, solve_lower, solve_lower_transposed};
+
+        let a = [4.0_f64, 2.0, -2.0, 2.0, 10.0, 2.0, -2.0, 2.0, 6.0];
+        let l = cholesky(&a, 3).unwrap();
+        assert_eq!(l, [2.0, 0.0, 0.0, 1.0, 3.0, 0.0, -1.0, 1.0, 2.0]);
+        // A x = b for x = (1, 2, 3)
+        let mut x = [2.0, 28.0, 20.0];
+        solve_lower(&l, &mut x);
+        solve_lower_transposed(&l, &mut x);
+        for (actual, expected) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert!(cholesky(&[1.0, 2.0, 2.0, 1.0], 2).is_none());
+    }
+}
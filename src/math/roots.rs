@@ -0,0 +1,122 @@
+//! Real roots of polynomials on an interval.
+
+use crate::InterpolationValue;
+
+/// The largest number of safeguarded Newton steps refining one root.
+const MAX_ITERATIONS: usize = 128;
+
+/// Evaluates a polynomial stored highest power first.
+pub(crate) fn horner<V: InterpolationValue>(coefficients: &[V], t: V) -> V {
+    coefficients.iter().fold(V::zero(), |acc, &c| acc * t + c)
+}
+
+/// Returns the roots in `[low, high]`, in increasing order, of the polynomial with `coefficients`
+/// stored highest power first.
+///
+/// The roots of the derivative split the interval into pieces on which the polynomial is
+/// monotonic, so each piece holds a root exactly when the polynomial changes sign across it or
+/// vanishes at one of its ends; the root is then refined by Newton's method safeguarded by
+/// bisection. Roots of even multiplicity are only found where the polynomial evaluates to zero
+/// exactly, and a polynomial vanishing identically yields the ends of the interval.
+pub(crate) fn roots_in<V: InterpolationValue>(coefficients: &[V], low: V, high: V) -> Vec<V> {
+    let degree = coefficients.len().saturating_sub(1);
+    let mut ends = vec![low];
+    if degree > 1 {
+        let derivative: Vec<V> = coefficients[..degree]
+            .iter()
+            .enumerate()
+            .map(|(k, &c)| c * V::from_count(degree - k))
+            .collect();
+        ends.extend(
+            roots_in(&derivative, low, high)
+                .into_iter()
+                .filter(|&t| low < t && t < high),
+        );
+    }
+    ends.push(high);
+    let mut roots: Vec<V> = Vec::new();
+    let mut push = |root: V| {
+        if roots.last() != Some(&root) {
+            roots.push(root);
+        }
+    };
+    for piece in ends.windows(2) {
+        let (a, b) = (piece[0], piece[1]);
+        let (pa, pb) = (horner(coefficients, a), horner(coefficients, b));
+        if pa == V::zero() {
+            push(a);
+        }
+        if pa != V::zero() && pb != V::zero() && (pa < V::zero()) != (pb < V::zero()) {
+            push(bracketed_root(coefficients, a, b, pa));
+        }
+        if pb == V::zero() {
+            push(b);
+        }
+    }
+    roots
+}
+
+/// Refines the root of a polynomial changing sign on `[a, b]`, where it takes the value `pa` at
+/// `a`.
+fn bracketed_root<V: InterpolationValue>(coefficients: &[V], a: V, b: V, pa: V) -> V {
+    let degree = coefficients.len() - 1;
+    let slope = |t: V| {
+        coefficients[..degree]
+            .iter()
+            .enumerate()
+            .fold(V::zero(), |acc, (k, &c)| {
+                acc * t + c * V::from_count(degree - k)
+            })
+    };
+    let below_at_a = pa < V::zero();
+    let (mut low, mut high) = (a, b);
+    let mut t = (a + b) / V::from_integer(2);
+    for _ in 0..MAX_ITERATIONS {
+        let value = horner(coefficients, t);
+        if value == V::zero() {
+            break;
+        }
+        if (value < V::zero()) == below_at_a {
+            low = t;
+        } else {
+            high = t;
+        }
+        let derivative = slope(t);
+        let next = if derivative == V::zero() {
+            None
+        } else {
+            Some(t - value / derivative)
+        }
+        .filter(|&newton| low < newton && newton < high)
+        .unwrap_or((low + high) / V::from_integer(2));
+        if next == t {
+            break;
+        }
+        t = next;
+    }
+    t
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::roots::roots_in;
+
+    #[test]
+    fn test_roots_in() {
+        // (t - 1)(t - 2)(t - 3)
+        let cubic = [1.0_f64, -6.0, 11.0, -6.0];
+        let roots = roots_in(&cubic, 0.0, 4.0);
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((root - expected).abs() < 1e-12);
+        }
+        // only the roots inside, and the ends where the polynomial vanishes
+        assert_eq!(roots_in(&cubic, 1.5, 2.5).len(), 1);
+        assert_eq!(roots_in(&cubic, 1.0, 2.0), vec![1.0, 2.0]);
+        // a double root where the polynomial touches zero
+        assert_eq!(roots_in(&[1.0, -2.0, 1.0], 0.0, 3.0), vec![1.0]);
+        assert!(roots_in(&[1.0, 0.0, 1.0], -2.0, 2.0).is_empty());
+        assert_eq!(roots_in(&[0.0, 0.0], -1.0, 1.0), vec![-1.0, 1.0]);
+        assert_eq!(roots_in(&[2.0, -1.0], 0.0, 1.0), vec![0.5]);
+    }
+}
@@ -1,11 +1,46 @@
+//! Tridiagonal linear systems, solved with the Thomas algorithm.
+
 use crate::InterpolationValue;
+use thiserror::Error;
 
-#[derive(Debug)]
+/// The errors of the matrices of this module and their factorizations.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MatrixValidationError {
+    /// The off-diagonals are not one shorter than the diagonal, or the diagonal is empty.
+    #[error("the off-diagonals must be one shorter than the non-empty diagonal")]
     MatrixShapeError,
+    /// The elimination met a zero pivot in the given row: the matrix is singular, or needs
+    /// pivoting which the Thomas algorithm does not do.
+    #[error("zero pivot in row {0}")]
+    ZeroPivotError(usize),
+    /// The right-hand side does not have one entry per row.
+    #[error("expected a right-hand side of length {0} but got {1}")]
+    RightHandSideLengthError(usize, usize),
+    /// The matrix is singular.
+    #[error("the matrix is singular")]
+    SingularMatrixError,
+    /// The entry in the given row and column is NaN or infinite, as given or after an elimination
+    /// step overflowed.
+    #[error("non-finite entry in row {0}, column {1}")]
+    NonFiniteEntryError(usize, usize),
 }
 
-pub(crate) struct TridiagonalMatrix<V: InterpolationValue> {
+/// A square tridiagonal matrix.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::math::tridiagonal_matrix::TridiagonalMatrix;
+///
+/// // [[2, 1, 0], [1, 2, 1], [0, 1, 2]]
+/// let matrix =
+///     TridiagonalMatrix::try_new(vec![1.0, 1.0], vec![2.0, 2.0, 2.0], vec![1.0, 1.0]).unwrap();
+/// let lu = matrix.factorize().unwrap();
+/// assert_eq!(lu.solve(&[3.0, 4.0, 3.0]).unwrap(), [1.0, 1.0, 1.0]);
+/// assert_eq!(lu.solve(&[2.0, 0.0, -2.0]).unwrap(), [1.0, 0.0, -1.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TridiagonalMatrix<V: InterpolationValue> {
     upper_diagonal: Vec<V>,
     diagonal: Vec<V>,
     lower_diagonal: Vec<V>,
@@ -13,6 +48,18 @@ pub(crate) struct TridiagonalMatrix<V: InterpolationValue> {
 }
 
 impl<V: InterpolationValue> TridiagonalMatrix<V> {
+    /// Constructs a new `TridiagonalMatrix` from its diagonals.
+    ///
+    /// # Arguments
+    ///
+    /// * `upper_diagonal` - The entries `a[i][i + 1]`.
+    /// * `diagonal` - The entries `a[i][i]`.
+    /// * `lower_diagonal` - The entries `a[i + 1][i]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::MatrixShapeError` if the diagonal is empty or the
+    /// off-diagonals are not one shorter than it.
     pub fn try_new(
         upper_diagonal: Vec<V>,
         diagonal: Vec<V>,
@@ -31,46 +78,187 @@ impl<V: InterpolationValue> TridiagonalMatrix<V> {
         })
     }
 
-    // Solve Ax = b.
-    pub fn solve(self, b: &[V]) -> Vec<V> {
+    /// Returns the number of rows.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Computes the LU factorization (the elimination of the Thomas algorithm), which solves for
+    /// any number of right-hand sides without repeating the elimination.
+    ///
+    /// No pivoting is done, which is stable for diagonally dominant matrices such as those of
+    /// cubic splines.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::ZeroPivotError(row)` if a pivot is exactly zero.
+    pub fn factorize(&self) -> Result<TridiagonalLU<V>, MatrixValidationError> {
         // shape validation is already done at construction phase
-        solve_with_thomas_algorithm_unchecked(
-            self.size,
-            self.lower_diagonal.as_slice(),
-            self.diagonal.as_slice(),
-            self.upper_diagonal.as_slice(),
-            b,
-        )
+        let mut multipliers = Vec::with_capacity(self.size - 1);
+        let mut pivots = Vec::with_capacity(self.size);
+        pivots.push(self.diagonal[0]);
+        for ix in 1..self.size {
+            if pivots[ix - 1].is_zero() {
+                return Err(MatrixValidationError::ZeroPivotError(ix - 1));
+            }
+            let multiplier = self.lower_diagonal[ix - 1] / pivots[ix - 1];
+            pivots.push(self.diagonal[ix] - multiplier * self.upper_diagonal[ix - 1]);
+            multipliers.push(multiplier);
+        }
+        if pivots[self.size - 1].is_zero() {
+            return Err(MatrixValidationError::ZeroPivotError(self.size - 1));
+        }
+        Ok(TridiagonalLU {
+            multipliers,
+            pivots,
+            upper_diagonal: self.upper_diagonal.clone(),
+        })
     }
+
+    /// Solves `Ax = b` once; use [`Self::factorize`] to solve for several right-hand sides.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::factorize`] and [`TridiagonalLU::solve`].
+    pub fn solve(&self, b: &[V]) -> Result<Vec<V>, MatrixValidationError> {
+        self.factorize()?.solve(b)
+    }
+}
+
+/// The LU factorization of a [`TridiagonalMatrix`], solving for any number of right-hand sides
+/// in `O(n)` each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TridiagonalLU<V: InterpolationValue> {
+    // multipliers[i] eliminates the sub-diagonal entry of row i + 1
+    multipliers: Vec<V>,
+    pivots: Vec<V>,
+    upper_diagonal: Vec<V>,
 }
 
-fn solve_with_thomas_algorithm_unchecked<V: InterpolationValue>(
-    matrix_size: usize,
-    lower_diagonal: &[V],
-    diagonal: &[V],
-    upper_diagonal: &[V],
-    b: &[V],
-) -> Vec<V> {
-    let mut x = b.to_vec();
-    let mut scratch = Vec::with_capacity(matrix_size);
-    scratch.push(upper_diagonal[0] / diagonal[0]);
-    x[0] /= diagonal[0];
+impl<V: InterpolationValue> TridiagonalLU<V> {
+    /// Solves `Ax = b`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::RightHandSideLengthError(expected, actual)` if `b` does not
+    /// have one entry per row.
+    pub fn solve(&self, b: &[V]) -> Result<Vec<V>, MatrixValidationError> {
+        let mut x = b.to_vec();
+        self.solve_into(&mut x)?;
+        Ok(x)
+    }
 
-    /* loop from 1 to X - 1 inclusive */
-    for ix in 1..matrix_size {
-        if ix < matrix_size - 1 {
-            scratch.push(
-                upper_diagonal[ix] / (diagonal[ix] - lower_diagonal[ix - 1] * scratch[ix - 1]),
-            );
+    /// Solves `Ax = b` in place, overwriting `b` with `x`, without allocating.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixValidationError::RightHandSideLengthError(expected, actual)` if `b` does not
+    /// have one entry per row, leaving it untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::math::tridiagonal_matrix::TridiagonalMatrix;
+    ///
+    /// let matrix =
+    ///     TridiagonalMatrix::try_new(vec![1.0, 1.0], vec![2.0, 2.0, 2.0], vec![1.0, 1.0]).unwrap();
+    /// let lu = matrix.factorize().unwrap();
+    /// let mut b = [3.0, 4.0, 3.0];
+    /// lu.solve_into(&mut b).unwrap();
+    /// assert_eq!(b, [1.0, 1.0, 1.0]);
+    /// ```
+    pub fn solve_into(&self, b: &mut [V]) -> Result<(), MatrixValidationError> {
+        if b.len() != self.pivots.len() {
+            return Err(MatrixValidationError::RightHandSideLengthError(
+                self.pivots.len(),
+                b.len(),
+            ));
+        }
+        self.solve_into_unchecked(b);
+        Ok(())
+    }
+
+    /// Solves `Ax = b` for a `b` known to have one entry per row.
+    pub(crate) fn solve_unchecked(&self, b: &[V]) -> Vec<V> {
+        let mut x = b.to_vec();
+        self.solve_into_unchecked(&mut x);
+        x
+    }
+
+    /// Solves `Ax = b` in place for a `b` known to have one entry per row.
+    pub(crate) fn solve_into_unchecked(&self, x: &mut [V]) {
+        let n = self.pivots.len();
+        for ix in 1..n {
+            let temp = self.multipliers[ix - 1] * x[ix - 1];
+            x[ix] -= temp;
+        }
+        x[n - 1] /= self.pivots[n - 1];
+        for ix in (0..n - 1).rev() {
+            x[ix] = (x[ix] - self.upper_diagonal[ix] * x[ix + 1]) / self.pivots[ix];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::tridiagonal_matrix::{MatrixValidationError, TridiagonalMatrix};
+
+    #[test]
+    fn test_multiple_right_hand_sides() {
+        // [[2, 1, 0, 0], [1, 3, 1, 0], [0, 1, 4, 2], [0, 0, 1, 5]]
+        let matrix = TridiagonalMatrix::try_new(
+            vec![1.0_f64, 1.0, 2.0],
+            vec![2.0, 3.0, 4.0, 5.0],
+            vec![1.0, 1.0, 1.0],
+        )
+        .unwrap();
+        let lu = matrix.factorize().unwrap();
+        for (b, expected) in [
+            ([3.0, 5.0, 7.0, 6.0], [1.0, 1.0, 1.0, 1.0]),
+            ([2.0, 4.0, 20.0, 23.0], [1.0, 0.0, 3.0, 4.0]),
+        ] {
+            for (x, expected) in lu.solve(&b).unwrap().iter().zip(expected) {
+                assert!((x - expected).abs() < 1e-12);
+            }
+            let mut x = b;
+            lu.solve_into(&mut x).unwrap();
+            assert_eq!(x.to_vec(), lu.solve(&b).unwrap());
         }
-        x[ix] = (x[ix] - lower_diagonal[ix - 1] * x[ix - 1])
-            / (diagonal[ix] - lower_diagonal[ix - 1] * scratch[ix - 1]);
     }
 
-    /* loop from X - 2 to 0 inclusive */
-    for ix in (0..matrix_size - 2).rev() {
-        let temp = scratch[ix] * x[ix + 1];
-        x[ix] -= temp;
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(
+            TridiagonalMatrix::<f64>::try_new(vec![], vec![], vec![]),
+            Err(MatrixValidationError::MatrixShapeError)
+        );
+        // [[1, 1, 0], [1, 1, 1], [0, 1, 1]] is regular but needs pivoting
+        let matrix =
+            TridiagonalMatrix::try_new(vec![1.0, 1.0], vec![1.0, 1.0, 1.0], vec![1.0, 1.0])
+                .unwrap();
+        assert_eq!(
+            matrix.factorize(),
+            Err(MatrixValidationError::ZeroPivotError(1))
+        );
+        let matrix = TridiagonalMatrix::try_new(vec![0.0], vec![1.0, 0.0], vec![0.0]).unwrap();
+        assert_eq!(
+            matrix.solve(&[1.0, 1.0]),
+            Err(MatrixValidationError::ZeroPivotError(1))
+        );
+        let lu = TridiagonalMatrix::try_new(vec![], vec![2.0], vec![])
+            .unwrap()
+            .factorize()
+            .unwrap();
+        assert_eq!(lu.solve(&[1.0]), Ok(vec![0.5]));
+        assert_eq!(
+            lu.solve(&[1.0, 2.0]),
+            Err(MatrixValidationError::RightHandSideLengthError(1, 2))
+        );
+        let mut b = [1.0, 2.0];
+        assert_eq!(
+            lu.solve_into(&mut b),
+            Err(MatrixValidationError::RightHandSideLengthError(1, 2))
+        );
+        assert_eq!(b, [1.0, 2.0]);
     }
-    x
 }
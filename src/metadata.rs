@@ -0,0 +1,155 @@
+//! Provenance information attached to curves.
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+/// Optional provenance information of a curve: a name, a source, a build time and arbitrary
+/// key-value attributes.
+///
+/// The build time is never filled in automatically; pass e.g. `SystemTime::now()` where the
+/// platform provides a clock.
+///
+/// # Example
+///
+/// ```
+/// use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+/// use spline_interpolation::metadata::Metadata;
+///
+/// let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)])
+///     .unwrap()
+///     .with_metadata(
+///         Metadata::new()
+///             .with_name("discount")
+///             .with_source("market data 2024-05-31")
+///             .with_attribute("currency", "JPY"),
+///     );
+/// assert_eq!(spline.metadata().name(), Some("discount"));
+/// assert_eq!(spline.metadata().attribute("currency"), Some("JPY"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata {
+    name: Option<String>,
+    source: Option<String>,
+    built_at: Option<SystemTime>,
+    attributes: BTreeMap<String, String>,
+}
+
+impl Metadata {
+    /// Creates empty metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the curve.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets where the data of the curve came from.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets when the curve was built.
+    pub fn with_built_at(mut self, built_at: SystemTime) -> Self {
+        self.built_at = Some(built_at);
+        self
+    }
+
+    /// Adds an attribute, replacing any previous value of `key`.
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_attribute(key, value);
+        self
+    }
+
+    /// Adds an attribute, returning the previous value of `key`.
+    pub fn set_attribute(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        self.attributes.insert(key.into(), value.into())
+    }
+
+    /// Returns the name of the curve.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns where the data of the curve came from.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Returns when the curve was built.
+    pub fn built_at(&self) -> Option<SystemTime> {
+        self.built_at
+    }
+
+    /// Returns the value of the attribute `key`.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get(key).map(String::as_str)
+    }
+
+    /// Returns the attributes ordered by key.
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns `true` if nothing is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::metadata::Metadata;
+    use std::time::{Duration, SystemTime};
+
+    fn sample() -> Metadata {
+        Metadata::new()
+            .with_name("usd-ois")
+            .with_source("bootstrap")
+            .with_built_at(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+            .with_attribute("tenor", "3M")
+            .with_attribute("desk", "rates")
+    }
+
+    #[test]
+    fn test_accessors() {
+        let mut metadata = sample();
+        assert!(!metadata.is_empty());
+        assert!(Metadata::new().is_empty());
+        assert_eq!(metadata.source(), Some("bootstrap"));
+        assert_eq!(
+            metadata.set_attribute("tenor", "6M"),
+            Some("3M".to_string())
+        );
+        let attributes: Vec<_> = metadata.attributes().collect();
+        assert_eq!(attributes, [("desk", "rates"), ("tenor", "6M")]);
+    }
+
+    #[test]
+    fn test_conversion_keeps_metadata() {
+        let spline = CatmullRomSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)])
+            .unwrap()
+            .with_metadata(sample());
+        assert_eq!(PiecewisePolynomial::from(&spline).metadata(), &sample());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let metadata = sample();
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert_eq!(serde_json::from_str::<Metadata>(&json).unwrap(), metadata);
+    }
+}
@@ -0,0 +1,43 @@
+//! Re-exports of the commonly used types.
+//!
+//! ```
+//! use spline_interpolation::prelude::*;
+//!
+//! let spline = NaturalCubicSpline::try_new(&[(0.0, 1.0), (0.5, 0.5), (1.0, 0.0)]).unwrap();
+//! assert_eq!(spline.try_value(0.75).unwrap(), 0.25);
+//! ```
+
+pub use crate::interpolation::bezier::CubicBezier;
+pub use crate::interpolation::builder::{Extrapolated, Extrapolation, Method, SplineBuilder};
+pub use crate::interpolation::bundle::SplineBundle;
+pub use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+pub use crate::interpolation::composite::{CompositeBuilder, CompositeInterpolator, PieceMethod};
+pub use crate::interpolation::continuity::{ContinuityReport, Junction};
+pub use crate::interpolation::dedup::DedupPolicy;
+pub use crate::interpolation::difference::MaxDifference;
+pub use crate::interpolation::grid_2d::{
+    BicubicInterpolator, BicubicSpline2D, BilinearInterpolator,
+};
+pub use crate::interpolation::grid_nd::InterpN;
+pub use crate::interpolation::hermite_spline::HermiteSpline;
+pub use crate::interpolation::interpolator::{Interpolator, MethodTag};
+pub use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+pub use crate::interpolation::newton::NewtonPolynomial;
+pub use crate::interpolation::parametric::{Frame, ParametricCurve};
+pub use crate::interpolation::partition::{
+    Density, DensityPartition, PartitionedSpline, Region, RegionMethod,
+};
+pub use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+pub use crate::interpolation::rbf::{PolynomialTail, RbfInterpolator, RbfKernel};
+pub use crate::interpolation::sample::SplineSample;
+pub use crate::interpolation::shape::Shape;
+pub use crate::interpolation::slopes::SlopeMethod;
+pub use crate::interpolation::smoothing_spline::SmoothingSpline;
+pub use crate::interpolation::streaming::{
+    PointBuilder, StreamingCatmullRomSpline, StreamingHermiteSpline,
+};
+pub use crate::interpolation::transformed::TransformedInterpolator;
+pub use crate::interpolation::vector_field::{VectorFieldInterpolator, VectorFieldKind};
+pub use crate::metadata::Metadata;
+pub use crate::tolerance::Tolerance;
+pub use crate::{InterpolationError, InterpolationValue, TranscendentalValue};
@@ -0,0 +1,200 @@
+//! Counters of the work done inside the interpolants, for benchmarks and performance comparisons.
+//!
+//! The evaluation and construction paths count their binary searches and the segments they
+//! evaluate into per-thread counters; [`measure`] reports the counts of a closure. Allocations are
+//! counted only while [`CountingAllocator`] is the global allocator, which only a binary can
+//! install. The work of other threads, e.g. the rayon pool of the `rayon` feature, is counted on
+//! those threads.
+//!
+//! Without the `profiling` feature the counting compiles to nothing.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "profiling")]
+//! # {
+//! use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+//! use spline_interpolation::profiling;
+//!
+//! let points: Vec<(f64, f64)> = (0..100).map(|i| (i as f64 * i as f64, i as f64)).collect();
+//! let spline = NaturalCubicSpline::try_new(&points).unwrap();
+//! let (_, counters) = profiling::measure(|| {
+//!     for x in [0.5, 10.5, 99.5] {
+//!         spline.try_value(x).unwrap();
+//!     }
+//! });
+//! assert_eq!(counters.binary_searches, 3);
+//! assert_eq!(counters.segments_evaluated, 3);
+//! # }
+//! ```
+
+#[cfg(feature = "profiling")]
+pub use counting::*;
+
+/// Counts a binary search for a query among the knots.
+#[inline(always)]
+pub(crate) fn record_binary_search() {
+    #[cfg(feature = "profiling")]
+    counting::add(&counting::BINARY_SEARCHES, 1);
+}
+
+/// Counts the evaluation of a polynomial segment.
+#[inline(always)]
+pub(crate) fn record_segment_evaluation() {
+    #[cfg(feature = "profiling")]
+    counting::add(&counting::SEGMENTS_EVALUATED, 1);
+}
+
+// the only code besides the `ffi` module allowed to use unsafe code, to implement `GlobalAlloc`
+#[cfg(feature = "profiling")]
+#[allow(unsafe_code)]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout};
+    use std::cell::Cell;
+    use std::ops::Sub;
+
+    thread_local! {
+        pub(super) static BINARY_SEARCHES: Cell<u64> = const { Cell::new(0) };
+        pub(super) static SEGMENTS_EVALUATED: Cell<u64> = const { Cell::new(0) };
+        static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+        static ALLOCATED_BYTES: Cell<u64> = const { Cell::new(0) };
+    }
+
+    /// Adds `n` to a counter, skipping threads being torn down.
+    pub(super) fn add(counter: &'static std::thread::LocalKey<Cell<u64>>, n: u64) {
+        let _ = counter.try_with(|count| count.set(count.get().wrapping_add(n)));
+    }
+
+    /// A snapshot of the counters of the current thread.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Counters {
+        /// The binary searches for a query or a new point among the knots; lookups on uniformly
+        /// spaced knots search a few knots only but count as well.
+        pub binary_searches: u64,
+        /// The polynomial segments evaluated, once per query or per sample of the derivatives.
+        pub segments_evaluated: u64,
+        /// The heap allocations, with [`CountingAllocator`] installed.
+        pub allocations: u64,
+        /// The bytes requested by the heap allocations, with [`CountingAllocator`] installed.
+        pub allocated_bytes: u64,
+    }
+
+    impl Sub for Counters {
+        type Output = Self;
+
+        fn sub(self, earlier: Self) -> Self {
+            Self {
+                binary_searches: self.binary_searches.wrapping_sub(earlier.binary_searches),
+                segments_evaluated: self
+                    .segments_evaluated
+                    .wrapping_sub(earlier.segments_evaluated),
+                allocations: self.allocations.wrapping_sub(earlier.allocations),
+                allocated_bytes: self.allocated_bytes.wrapping_sub(earlier.allocated_bytes),
+            }
+        }
+    }
+
+    /// Returns the counters of the current thread since it started or was [`reset`].
+    pub fn counters() -> Counters {
+        Counters {
+            binary_searches: BINARY_SEARCHES.with(Cell::get),
+            segments_evaluated: SEGMENTS_EVALUATED.with(Cell::get),
+            allocations: ALLOCATIONS.with(Cell::get),
+            allocated_bytes: ALLOCATED_BYTES.with(Cell::get),
+        }
+    }
+
+    /// Sets the counters of the current thread to zero.
+    pub fn reset() {
+        for counter in [
+            &BINARY_SEARCHES,
+            &SEGMENTS_EVALUATED,
+            &ALLOCATIONS,
+            &ALLOCATED_BYTES,
+        ] {
+            counter.with(|count| count.set(0));
+        }
+    }
+
+    /// Runs `f` and returns its result with the work it counted on the current thread.
+    pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Counters) {
+        let before = counters();
+        let result = f();
+        (result, counters() - before)
+    }
+
+    /// A global allocator forwarding to `A` and counting the allocations of each thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::profiling::CountingAllocator;
+    /// use std::alloc::System;
+    ///
+    /// #[global_allocator]
+    /// static ALLOCATOR: CountingAllocator<System> = CountingAllocator::new(System);
+    /// # fn main() {}
+    /// ```
+    #[derive(Debug, Default)]
+    pub struct CountingAllocator<A> {
+        inner: A,
+    }
+
+    impl<A> CountingAllocator<A> {
+        /// Wraps the allocator `inner`.
+        pub const fn new(inner: A) -> Self {
+            Self { inner }
+        }
+    }
+
+    fn record_allocation(size: usize) {
+        add(&ALLOCATIONS, 1);
+        add(&ALLOCATED_BYTES, size as u64);
+    }
+
+    // SAFETY: every call forwards to `inner` unchanged; the counters are const-initialized
+    // thread-locals without destructors, so updating them does not allocate.
+    unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record_allocation(layout.size());
+            self.inner.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record_allocation(layout.size());
+            self.inner.alloc_zeroed(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            self.inner.dealloc(ptr, layout);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record_allocation(new_size);
+            self.inner.realloc(ptr, layout, new_size)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "profiling"))]
+mod tests {
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::profiling::{counters, measure, reset, Counters};
+
+    #[test]
+    fn test_counts() {
+        let points: Vec<_> = (0..20).map(|i| (i as f64, (i * i) as f64, 0.0)).collect();
+        let spline = HermiteSpline::try_new(&points).unwrap();
+        let pp = PiecewisePolynomial::from(&spline);
+        let (_, counted) = measure(|| {
+            spline.try_value(3.5).unwrap();
+            pp.try_eval_all(7.25).unwrap();
+            assert!(spline.try_value(25.0).is_err());
+        });
+        assert_eq!(counted.segments_evaluated, 2);
+        assert_eq!(counted.binary_searches, 3);
+        reset();
+        assert_eq!(counters(), Counters::default());
+    }
+}
@@ -0,0 +1,250 @@
+//! Helpers for testing code built on the interpolants: checks that an interpolant reproduces the
+//! polynomials its method is exact for, and [`approx`] comparisons of the spline outputs.
+//!
+//! The polynomials are given by their coefficients, highest power first, as in
+//! [`PiecewisePolynomial`].
+//!
+//! # Example
+//!
+//! ```
+//! use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+//! use spline_interpolation::testutils::{assert_reproduces_polynomial, polynomial_hermite_points};
+//!
+//! // 2x^3 - x + 1 sampled with its slopes on uneven knots
+//! let cubic = [2.0, 0.0, -1.0, 1.0];
+//! let points = polynomial_hermite_points(&cubic, &[0.0, 0.3, 1.0, 1.7, 2.0]);
+//! let spline = HermiteSpline::try_new(&points).unwrap();
+//! assert_reproduces_polynomial(&spline, &cubic, 0.0..=2.0, 1e-12);
+//! ```
+
+use crate::interpolation::interpolator::Interpolator;
+use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use crate::interpolation::sample::SplineSample;
+use crate::math::abs;
+use crate::math::roots::horner;
+use crate::{InterpolationError, InterpolationValue};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use std::ops::RangeInclusive;
+
+/// The number of segments between the points at which [`reproduction_error`] compares.
+const SAMPLES: usize = 1000;
+
+/// Returns the points `(x, p(x))` of the polynomial `p` with `coefficients` at every `x` of `xs`.
+pub fn polynomial_points<V: InterpolationValue>(coefficients: &[V], xs: &[V]) -> Vec<(V, V)> {
+    xs.iter().map(|&x| (x, horner(coefficients, x))).collect()
+}
+
+/// Returns the points `(x, p(x), p'(x))` of the polynomial `p` with `coefficients` at every `x`
+/// of `xs`, e.g. to build a [`HermiteSpline`](crate::interpolation::hermite_spline::HermiteSpline).
+pub fn polynomial_hermite_points<V: InterpolationValue>(
+    coefficients: &[V],
+    xs: &[V],
+) -> Vec<(V, V, V)> {
+    let degree = coefficients.len().saturating_sub(1);
+    let derivative: Vec<V> = coefficients[..degree]
+        .iter()
+        .enumerate()
+        .map(|(k, &c)| c * V::from_count(degree - k))
+        .collect();
+    xs.iter()
+        .map(|&x| (x, horner(coefficients, x), horner(&derivative, x)))
+        .collect()
+}
+
+/// Returns the largest absolute difference between the interpolant and the polynomial with
+/// `coefficients` over 1001 evenly spaced points of `domain`, its ends included.
+///
+/// # Errors
+///
+/// Returns the first error of [`Interpolator::try_value`], e.g. if `domain` reaches outside the
+/// interpolant.
+pub fn reproduction_error<V: InterpolationValue, I: Interpolator<V> + ?Sized>(
+    interpolant: &I,
+    coefficients: &[V],
+    domain: RangeInclusive<V>,
+) -> Result<V, InterpolationError<V>> {
+    let (&start, &end) = (domain.start(), domain.end());
+    let step = (end - start) / V::from_count(SAMPLES);
+    (0..=SAMPLES).try_fold(V::zero(), |worst, i| {
+        let x = if i == SAMPLES {
+            end
+        } else {
+            start + step * V::from_count(i)
+        };
+        let error = abs(interpolant.try_value(x)? - horner(coefficients, x));
+        Ok(if error > worst { error } else { worst })
+    })
+}
+
+/// Asserts that the interpolant reproduces the polynomial with `coefficients` within `tol` over
+/// `domain`, as measured by [`reproduction_error`].
+///
+/// # Panics
+///
+/// Panics with the error found if it exceeds `tol`, or if the interpolant fails to evaluate.
+#[track_caller]
+pub fn assert_reproduces_polynomial<V: InterpolationValue, I: Interpolator<V> + ?Sized>(
+    interpolant: &I,
+    coefficients: &[V],
+    domain: RangeInclusive<V>,
+    tol: V,
+) {
+    match reproduction_error(interpolant, coefficients, domain) {
+        Ok(error) => assert!(
+            error <= tol,
+            "{:?} interpolant misses the polynomial {coefficients:?} by {error:?} > {tol:?}",
+            interpolant.method()
+        ),
+        Err(error) => panic!(
+            "{:?} interpolant failed to evaluate: {error:?}",
+            interpolant.method()
+        ),
+    }
+}
+
+impl<V: InterpolationValue + AbsDiffEq<Epsilon = V>> AbsDiffEq for SplineSample<V> {
+    type Epsilon = V;
+
+    fn default_epsilon() -> V {
+        V::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: V) -> bool {
+        self.value.abs_diff_eq(&other.value, epsilon)
+            && self.dydx.abs_diff_eq(&other.dydx, epsilon)
+            && self.d2ydx2.abs_diff_eq(&other.d2ydx2, epsilon)
+    }
+}
+
+impl<V: InterpolationValue + RelativeEq<Epsilon = V>> RelativeEq for SplineSample<V> {
+    fn default_max_relative() -> V {
+        V::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: V, max_relative: V) -> bool {
+        self.value.relative_eq(&other.value, epsilon, max_relative)
+            && self.dydx.relative_eq(&other.dydx, epsilon, max_relative)
+            && self
+                .d2ydx2
+                .relative_eq(&other.d2ydx2, epsilon, max_relative)
+    }
+}
+
+impl<V: InterpolationValue + UlpsEq<Epsilon = V>> UlpsEq for SplineSample<V> {
+    fn default_max_ulps() -> u32 {
+        V::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: V, max_ulps: u32) -> bool {
+        self.value.ulps_eq(&other.value, epsilon, max_ulps)
+            && self.dydx.ulps_eq(&other.dydx, epsilon, max_ulps)
+            && self.d2ydx2.ulps_eq(&other.d2ydx2, epsilon, max_ulps)
+    }
+}
+
+/// Piecewise polynomials are compared breakpoint by breakpoint and coefficient by coefficient, so
+/// they differ whenever their segments or degrees do.
+impl<V: InterpolationValue + AbsDiffEq<Epsilon = V>> AbsDiffEq for PiecewisePolynomial<V> {
+    type Epsilon = V;
+
+    fn default_epsilon() -> V {
+        V::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: V) -> bool {
+        self.all_pairs(other, |a, b| a.abs_diff_eq(b, epsilon))
+    }
+}
+
+impl<V: InterpolationValue + RelativeEq<Epsilon = V>> RelativeEq for PiecewisePolynomial<V> {
+    fn default_max_relative() -> V {
+        V::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: V, max_relative: V) -> bool {
+        self.all_pairs(other, |a, b| a.relative_eq(b, epsilon, max_relative))
+    }
+}
+
+impl<V: InterpolationValue + UlpsEq<Epsilon = V>> UlpsEq for PiecewisePolynomial<V> {
+    fn default_max_ulps() -> u32 {
+        V::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: V, max_ulps: u32) -> bool {
+        self.all_pairs(other, |a, b| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+impl<V: InterpolationValue> PiecewisePolynomial<V> {
+    /// Returns `true` if both have the same shape and `eq` holds for every pair of corresponding
+    /// breakpoints and coefficients.
+    fn all_pairs(&self, other: &Self, eq: impl Fn(&V, &V) -> bool) -> bool {
+        self.segment_count() == other.segment_count()
+            && self.degree() == other.degree()
+            && self
+                .breakpoints()
+                .iter()
+                .zip(other.breakpoints())
+                .all(|(a, b)| eq(a, b))
+            && (0..self.segment_count()).all(|i| {
+                self.coefficients(i)
+                    .iter()
+                    .zip(other.coefficients(i))
+                    .all(|(a, b)| eq(a, b))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::catmull_rom_spline::CatmullRomSpline;
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::interpolation::piecewise_polynomial::PiecewisePolynomial;
+    use crate::testutils::{assert_reproduces_polynomial, polynomial_points, reproduction_error};
+    use approx::{assert_abs_diff_eq, assert_relative_ne, assert_ulps_eq};
+
+    const XS: [f64; 6] = [0.0, 0.4, 1.0, 1.3, 2.1, 2.5];
+
+    #[test]
+    fn test_reproduction() {
+        // Catmull-Rom splines on uneven knots are exact for lines and no further
+        let line = [-1.5, 2.0];
+        let spline = CatmullRomSpline::try_new(&polynomial_points(&line, &XS)).unwrap();
+        assert_reproduces_polynomial(&spline, &line, 0.0..=2.5, 1e-12);
+        let quadratic = [0.5, -1.0, 2.0];
+        let spline = CatmullRomSpline::try_new(&polynomial_points(&quadratic, &XS)).unwrap();
+        assert!(reproduction_error(&spline, &quadratic, 0.0..=2.5).unwrap() > 1e-3);
+        assert!(reproduction_error(&spline, &quadratic, 0.0..=3.0).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "misses the polynomial")]
+    fn test_reproduction_failure() {
+        // the natural end conditions bend a cubic with curvature at the ends
+        let cubic = [1.0, 0.0, 0.0, 0.0];
+        let spline = NaturalCubicSpline::try_new(&polynomial_points(&cubic, &XS)).unwrap();
+        assert_reproduces_polynomial(&spline, &cubic, 0.0..=2.5, 1e-9);
+    }
+
+    #[test]
+    fn test_approx() {
+        let points = polynomial_points(&[1.0, -3.0, 2.0], &XS);
+        let spline = NaturalCubicSpline::try_new(&points).unwrap();
+        let shifted: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x, y + 1e-10)).collect();
+        let nearby = NaturalCubicSpline::try_new(&shifted).unwrap();
+        assert_abs_diff_eq!(
+            spline.try_eval_all(1.7).unwrap(),
+            nearby.try_eval_all(1.7).unwrap(),
+            epsilon = 1e-9
+        );
+        let (polynomial, nearby) = (
+            PiecewisePolynomial::from(&spline),
+            PiecewisePolynomial::from(&nearby),
+        );
+        assert_abs_diff_eq!(polynomial, nearby, epsilon = 1e-9);
+        assert_relative_ne!(polynomial, nearby);
+        assert_ulps_eq!(polynomial, polynomial.clone());
+        assert_relative_ne!(polynomial, polynomial.derivative(), epsilon = 1.0);
+    }
+}
@@ -0,0 +1,57 @@
+//! Comparison policies for deciding when two values are "the same".
+
+use crate::math::abs;
+use crate::InterpolationValue;
+
+/// How close two values must be to be treated as equal.
+///
+/// The splines use it to decide whether a query hits a knot exactly, see e.g.
+/// [`NaturalCubicSpline::with_tolerance`](crate::interpolation::natural_cubic_spline::NaturalCubicSpline::with_tolerance),
+/// and [`ContinuityReport`](crate::interpolation::continuity::ContinuityReport) to compare the
+/// limits at a breakpoint. Other comparisons, e.g. of root finding, use their own thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tolerance<V: InterpolationValue> {
+    /// Values must be identical.
+    #[default]
+    Exact,
+    /// `|a - b| <= tolerance`.
+    Absolute(V),
+    /// `|a - b| <= tolerance * max(|a|, |b|)`.
+    Relative(V),
+    /// `a` and `b` are at most this many representable values apart.
+    ///
+    /// Types without a notion of units in the last place (see
+    /// [`InterpolationValue::ulps_between`]) compare exactly.
+    Ulps(u64),
+}
+
+impl<V: InterpolationValue> Tolerance<V> {
+    /// Returns `true` if `a` and `b` are equal under this tolerance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spline_interpolation::tolerance::Tolerance;
+    ///
+    /// assert!(Tolerance::Absolute(1e-9).eq(1.0, 1.0 + 1e-12));
+    /// assert!(Tolerance::Relative(1e-3).eq(1000.0, 1000.5));
+    /// assert!(Tolerance::Ulps(1).eq(1.0, 1.0 + f64::EPSILON));
+    /// assert!(!Tolerance::Exact.eq(1.0, 1.0 + f64::EPSILON));
+    /// ```
+    pub fn eq(&self, a: V, b: V) -> bool {
+        if a == b {
+            return true;
+        }
+        match *self {
+            Tolerance::Exact => false,
+            Tolerance::Absolute(tolerance) => abs(a - b) <= tolerance,
+            Tolerance::Relative(tolerance) => {
+                let (abs_a, abs_b) = (abs(a), abs(b));
+                let scale = if abs_a < abs_b { abs_b } else { abs_a };
+                abs(a - b) <= tolerance * scale
+            }
+            Tolerance::Ulps(ulps) => a.ulps_between(b).is_some_and(|distance| distance <= ulps),
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! Canonical test curves with a known analytic truth, and the largest error of each interpolation
+//! method against them, to compare methods on data resembling a dataset and to pin the accuracy
+//! of the crate.
+//!
+//! The curves are sampled in `f64` at deterministic knots, and the errors are measured at 2001
+//! evenly spaced points of the domain, the knots of the ends included.
+//!
+//! # Example
+//!
+//! The Runge function defeats the exact polynomial through equally spaced knots, whereas the
+//! splines converge:
+//!
+//! ```
+//! use spline_interpolation::interpolation::builder::Method;
+//! use spline_interpolation::interpolation::newton::NewtonPolynomial;
+//! use spline_interpolation::verification::ReferenceCurve;
+//!
+//! let runge = ReferenceCurve::runge(11);
+//! let polynomial = NewtonPolynomial::try_new(runge.points()).unwrap();
+//! assert!(runge.max_error(&polynomial).unwrap() > 1.9);
+//! assert!(runge.max_error_of(Method::NaturalCubic).unwrap() < 0.03);
+//! ```
+//!
+//! Ranking the methods on a near step puts the monotone PCHIP slopes ahead of the cubic splines,
+//! whose wiggles spread from the step:
+//!
+//! ```
+//! use spline_interpolation::interpolation::builder::Method;
+//! use spline_interpolation::interpolation::slopes::SlopeMethod;
+//! use spline_interpolation::verification::ReferenceCurve;
+//!
+//! let step = ReferenceCurve::step(16);
+//! let ranked = step
+//!     .ranked_methods(&[
+//!         Method::NaturalCubic,
+//!         Method::CatmullRom,
+//!         Method::Hermite(SlopeMethod::Pchip),
+//!     ])
+//!     .unwrap();
+//! assert_eq!(ranked[0].0, Method::Hermite(SlopeMethod::Pchip));
+//! ```
+
+use crate::interpolation::builder::{Method, SplineBuilder};
+use crate::interpolation::interpolator::Interpolator;
+use crate::InterpolationError;
+use std::f64::consts::PI;
+
+/// The number of intervals between the points at which the errors are measured.
+const SAMPLES: usize = 2000;
+
+/// A function known in closed form, sampled at the knots of an interpolation problem.
+#[derive(Debug, Clone)]
+pub struct ReferenceCurve {
+    name: &'static str,
+    points: Vec<(f64, f64)>,
+    truth: fn(f64) -> f64,
+    derivative: fn(f64) -> f64,
+}
+
+impl ReferenceCurve {
+    fn sample(
+        name: &'static str,
+        knots: impl Iterator<Item = f64>,
+        truth: fn(f64) -> f64,
+        derivative: fn(f64) -> f64,
+    ) -> Self {
+        Self {
+            name,
+            points: knots.map(|x| (x, truth(x))).collect(),
+            truth,
+            derivative,
+        }
+    }
+
+    /// Runge's function `1 / (1 + 25x²)` at `n` equally spaced knots of `[-1, 1]`, on which the
+    /// polynomial through the knots diverges as `n` grows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n < 2`.
+    pub fn runge(n: usize) -> Self {
+        Self::sample(
+            "runge",
+            even_knots(-1.0, 1.0, n),
+            |x| 1.0 / (1.0 + 25.0 * x * x),
+            |x| -50.0 * x / (1.0 + 25.0 * x * x).powi(2),
+        )
+    }
+
+    /// `sin(x)` over one period `[0, 2π]` at `n` knots clustering towards the ends, the
+    /// Chebyshev–Lobatto points `π (1 - cos(πi / (n - 1)))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n < 2`.
+    pub fn uneven_sine(n: usize) -> Self {
+        Self::sample(
+            "uneven sine",
+            even_knots(0.0, 1.0, n).map(|t| PI * (1.0 - (PI * t).cos())),
+            f64::sin,
+            f64::cos,
+        )
+    }
+
+    /// The logistic step `1 / (1 + exp(-40x))` at `n` equally spaced knots of `[-1, 1]`, rising
+    /// from 0 to 1 within a few knots, where the cubic splines overshoot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n < 2`.
+    pub fn step(n: usize) -> Self {
+        fn logistic(x: f64) -> f64 {
+            1.0 / (1.0 + (-40.0 * x).exp())
+        }
+        Self::sample("step", even_knots(-1.0, 1.0, n), logistic, |x| {
+            40.0 * logistic(x) * (1.0 - logistic(x))
+        })
+    }
+
+    /// Returns the name of the curve.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the points `(x, f(x))` at the knots, in increasing order of `x`.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Returns the points `(x, f(x), f'(x))` at the knots with the exact slopes, e.g. for a
+    /// [`HermiteSpline`](crate::interpolation::hermite_spline::HermiteSpline).
+    pub fn hermite_points(&self) -> Vec<(f64, f64, f64)> {
+        self.points
+            .iter()
+            .map(|&(x, y)| (x, y, (self.derivative)(x)))
+            .collect()
+    }
+
+    /// Returns the first and last knots.
+    pub fn domain(&self) -> (f64, f64) {
+        (self.points[0].0, self.points[self.points.len() - 1].0)
+    }
+
+    /// Evaluates the analytic truth at `x`.
+    pub fn truth(&self, x: f64) -> f64 {
+        (self.truth)(x)
+    }
+
+    /// Returns the largest absolute difference between the interpolant and the truth over the
+    /// domain.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of [`Interpolator::try_value`].
+    pub fn max_error<I: Interpolator<f64> + ?Sized>(
+        &self,
+        interpolant: &I,
+    ) -> Result<f64, InterpolationError<f64>> {
+        let (start, end) = self.domain();
+        even_knots(start, end, SAMPLES + 1).try_fold(0.0, |worst: f64, x| {
+            Ok(worst.max((interpolant.try_value(x)? - self.truth(x)).abs()))
+        })
+    }
+
+    /// Builds the interpolant of `method` through the points and returns its
+    /// [largest error](Self::max_error).
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`SplineBuilder::build`] and [`Self::max_error`].
+    pub fn max_error_of(&self, method: Method) -> Result<f64, InterpolationError<f64>> {
+        self.max_error(&*SplineBuilder::new(&self.points).method(method).build()?)
+    }
+
+    /// Returns every method with its [largest error](Self::max_error_of), the most accurate
+    /// first, keeping the given order between equal errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of [`Self::max_error_of`].
+    pub fn ranked_methods(
+        &self,
+        methods: &[Method],
+    ) -> Result<Vec<(Method, f64)>, InterpolationError<f64>> {
+        let mut ranked = methods
+            .iter()
+            .map(|&method| Ok((method, self.max_error_of(method)?)))
+            .collect::<Result<Vec<_>, InterpolationError<f64>>>()?;
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+        Ok(ranked)
+    }
+}
+
+/// Yields `n` equally spaced points from `start` to `end`, both exactly.
+fn even_knots(start: f64, end: f64, n: usize) -> impl Iterator<Item = f64> {
+    assert!(n >= 2, "a reference curve needs at least 2 knots, got {n}");
+    let last = n - 1;
+    (0..n).map(move |i| {
+        if i == last {
+            end
+        } else {
+            start + (end - start) * i as f64 / last as f64
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::builder::Method;
+    use crate::interpolation::hermite_spline::HermiteSpline;
+    use crate::interpolation::newton::NewtonPolynomial;
+    use crate::interpolation::slopes::SlopeMethod;
+    use crate::verification::ReferenceCurve;
+
+    const METHODS: [Method; 6] = [
+        Method::NaturalCubic,
+        Method::CatmullRom,
+        Method::Hermite(SlopeMethod::FiniteDifference),
+        Method::Hermite(SlopeMethod::CatmullRom),
+        Method::Hermite(SlopeMethod::Akima),
+        Method::Hermite(SlopeMethod::Pchip),
+    ];
+
+    /// Pins the largest error of every method on every curve, as a regression suite.
+    #[test]
+    fn test_reference_errors() {
+        let cases: [(ReferenceCurve, [f64; 6]); 3] = [
+            (
+                ReferenceCurve::runge(21),
+                [0.0035, 0.0123, 0.0123, 0.0123, 0.0077, 0.014],
+            ),
+            (
+                ReferenceCurve::uneven_sine(12),
+                [0.0017, 0.017, 0.0156, 0.017, 0.0173, 0.057],
+            ),
+            (
+                ReferenceCurve::step(16),
+                [0.066, 0.041, 0.041, 0.041, 0.0214, 0.022],
+            ),
+        ];
+        for (curve, bounds) in &cases {
+            for (&method, &bound) in METHODS.iter().zip(bounds) {
+                let error = curve.max_error_of(method).unwrap();
+                assert!(
+                    error <= bound,
+                    "{} {method:?}: {error} > {bound}",
+                    curve.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_convergence() {
+        // halving the spacing divides the errors by 2^4 on the smooth sine, where the cubic
+        // splines converge at the fourth order, and still by more than 2^2 on Runge's function
+        for (coarse, fine, ratio) in [
+            (
+                ReferenceCurve::uneven_sine(10),
+                ReferenceCurve::uneven_sine(19),
+                15.0,
+            ),
+            (ReferenceCurve::runge(21), ReferenceCurve::runge(41), 4.0),
+        ] {
+            let hermite = |curve: &ReferenceCurve| {
+                let spline = HermiteSpline::try_new(&curve.hermite_points()).unwrap();
+                curve.max_error(&spline).unwrap()
+            };
+            let exact_ratio = hermite(&coarse) / hermite(&fine);
+            let natural_ratio = coarse.max_error_of(Method::NaturalCubic).unwrap()
+                / fine.max_error_of(Method::NaturalCubic).unwrap();
+            assert!(exact_ratio > ratio, "{}: {exact_ratio}", coarse.name());
+            assert!(natural_ratio > ratio, "{}: {natural_ratio}", coarse.name());
+        }
+        // the polynomial through more equally spaced knots of Runge's function gets worse
+        let polynomial_error = |n| {
+            let curve = ReferenceCurve::runge(n);
+            curve
+                .max_error(&NewtonPolynomial::try_new(curve.points()).unwrap())
+                .unwrap()
+        };
+        assert!(polynomial_error(11) < polynomial_error(15));
+    }
+}
@@ -0,0 +1,73 @@
+//! Thin `wasm-bindgen` wrappers, so a browser front end evaluates the same curves as a Rust
+//! back end.
+//!
+//! The errors reach JavaScript as `Error`s carrying the message of the [`InterpolationError`].
+
+use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use crate::InterpolationError;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// A natural cubic spline over `f64`, exported to JavaScript as `JsCubicSpline`.
+#[wasm_bindgen]
+pub struct JsCubicSpline {
+    spline: NaturalCubicSpline<f64>,
+}
+
+#[wasm_bindgen]
+impl JsCubicSpline {
+    /// Constructs the natural cubic spline through the points `(xs[i], ys[i])`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`NaturalCubicSpline::try_new_xy`].
+    #[wasm_bindgen(constructor)]
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<JsCubicSpline, JsError> {
+        Ok(Self {
+            spline: NaturalCubicSpline::try_new_xy(xs, ys).map_err(to_js)?,
+        })
+    }
+
+    /// Evaluates the spline at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`NaturalCubicSpline::try_value`].
+    pub fn value(&self, x: f64) -> Result<f64, JsError> {
+        self.spline.try_value(x).map_err(to_js)
+    }
+
+    /// Evaluates the spline at every `xs[i]`, returned as a `Float64Array`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of [`Self::value`].
+    pub fn values(&self, xs: &[f64]) -> Result<Vec<f64>, JsError> {
+        xs.iter().map(|&x| self.value(x)).collect()
+    }
+}
+
+/// Converts an error into a JavaScript `Error` with its message.
+fn to_js(error: InterpolationError<f64>) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpolation::natural_cubic_spline::NaturalCubicSpline;
+    use crate::wasm::JsCubicSpline;
+
+    // the error paths call into JavaScript, so only the successful ones run natively
+    #[test]
+    fn test_matches_spline() {
+        let (xs, ys) = ([0.0, 0.5, 1.5, 2.0], [1.0, 0.5, 2.0, 0.0]);
+        let js = JsCubicSpline::new(&xs, &ys).unwrap();
+        let spline = NaturalCubicSpline::try_new_xy(&xs, &ys).unwrap();
+        let queries = [0.0, 0.25, 1.0, 1.75, 2.0];
+        let expected: Vec<f64> = queries
+            .iter()
+            .map(|&x| spline.try_value(x).unwrap())
+            .collect();
+        assert_eq!(js.values(&queries).unwrap(), expected);
+        assert_eq!(js.value(1.0).unwrap(), expected[2]);
+    }
+}
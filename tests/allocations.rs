@@ -0,0 +1,36 @@
+//! Counts the allocations of construction and evaluation with the counting allocator installed,
+//! which takes a binary of its own.
+
+#![cfg(feature = "profiling")]
+
+use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use spline_interpolation::profiling::{self, CountingAllocator};
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator<System> = CountingAllocator::new(System);
+
+#[test]
+fn test_evaluation_does_not_allocate() {
+    let points: Vec<(f64, f64)> = (0..50).map(|i| (i as f64 * 0.1, (i % 7) as f64)).collect();
+    let slopes: Vec<_> = points.iter().map(|&(x, y)| (x, y, 0.0)).collect();
+    let (natural, construction) = profiling::measure(|| NaturalCubicSpline::try_new(&points));
+    let natural = natural.unwrap();
+    assert!(construction.allocations > 0);
+    assert!(construction.allocated_bytes >= 3 * 50 * 8);
+    let hermite = HermiteSpline::try_new(&slopes).unwrap();
+    let catmull_rom = CatmullRomSpline::try_new(&points).unwrap();
+    let (_, evaluation) = profiling::measure(|| {
+        for k in 0..40 {
+            let x = 0.05 + k as f64 * 0.1;
+            natural.try_value(x).unwrap();
+            hermite.try_value(x).unwrap();
+            catmull_rom.try_value(x).unwrap();
+        }
+    });
+    assert_eq!(evaluation.allocations, 0);
+    assert_eq!(evaluation.segments_evaluated, 120);
+    assert_eq!(evaluation.binary_searches, 120);
+}
@@ -0,0 +1,208 @@
+//! Implements `InterpolationValue` outside the crate for a minimal dual number, the way an
+//! automatic differentiation library would, and differentiates through the splines with it.
+
+use num_traits::{Num, One, Zero};
+use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use spline_interpolation::InterpolationValue;
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+/// `re + eps ε` with `ε² = 0`, ordered and compared by `re` alone.
+#[derive(Debug, Clone, Copy)]
+struct Dual {
+    re: f64,
+    eps: f64,
+}
+
+impl Dual {
+    fn constant(re: f64) -> Self {
+        Dual { re, eps: 0.0 }
+    }
+
+    fn variable(re: f64) -> Self {
+        Dual { re, eps: 1.0 }
+    }
+}
+
+impl PartialEq for Dual {
+    fn eq(&self, other: &Self) -> bool {
+        self.re == other.re
+    }
+}
+
+impl PartialOrd for Dual {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.re.partial_cmp(&other.re)
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Dual {
+            re: self.re + rhs.re,
+            eps: self.eps + rhs.eps,
+        }
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Dual {
+            re: self.re - rhs.re,
+            eps: self.eps - rhs.eps,
+        }
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Dual {
+            re: self.re * rhs.re,
+            eps: self.re * rhs.eps + self.eps * rhs.re,
+        }
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Dual {
+            re: self.re / rhs.re,
+            eps: (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re),
+        }
+    }
+}
+
+impl Rem for Dual {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        let quotient = (self.re / rhs.re).trunc();
+        self - Dual::constant(quotient) * rhs
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Dual {
+            re: -self.re,
+            eps: -self.eps,
+        }
+    }
+}
+
+macro_rules! impl_assign {
+    ($($trait:ident, $method:ident, $op:tt);*) => {
+        $(
+            impl $trait for Dual {
+                fn $method(&mut self, rhs: Self) {
+                    *self = *self $op rhs;
+                }
+            }
+        )*
+    };
+}
+
+impl_assign!(AddAssign, add_assign, +; SubAssign, sub_assign, -; MulAssign, mul_assign, *;
+    DivAssign, div_assign, /);
+
+impl Zero for Dual {
+    fn zero() -> Self {
+        Dual::constant(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re == 0.0
+    }
+}
+
+impl One for Dual {
+    fn one() -> Self {
+        Dual::constant(1.0)
+    }
+}
+
+impl Num for Dual {
+    type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        f64::from_str_radix(s, radix).map(Dual::constant)
+    }
+}
+
+impl InterpolationValue for Dual {
+    fn is_finite(self) -> bool {
+        self.re.is_finite() && self.eps.is_finite()
+    }
+}
+
+const XS: [f64; 5] = [0.0, 0.5, 1.25, 2.0, 3.0];
+
+fn ys() -> Vec<f64> {
+    XS.iter().map(|x| x.sin()).collect()
+}
+
+/// Lifts `values` to constants, except the `seed`-th one, which becomes the variable.
+fn seeded(values: &[f64], seed: usize) -> Vec<Dual> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            if i == seed {
+                Dual::variable(v)
+            } else {
+                Dual::constant(v)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_derivative_in_the_query() {
+    let xs: Vec<Dual> = XS.iter().copied().map(Dual::constant).collect();
+    let natural = NaturalCubicSpline::try_new_xy(&xs, &seeded(&ys(), usize::MAX)).unwrap();
+    let reference = NaturalCubicSpline::try_new_xy(&XS, &ys()).unwrap();
+    for x in [0.2, 0.9, 1.7, 2.6] {
+        let value = natural.try_value(Dual::variable(x)).unwrap();
+        let sample = reference.try_eval_all(x).unwrap();
+        assert!((value.re - sample.value).abs() < 1e-14);
+        assert!((value.eps - sample.dydx).abs() < 1e-13);
+    }
+}
+
+#[test]
+fn test_derivatives_in_the_data() {
+    let xs: Vec<Dual> = XS.iter().copied().map(Dual::constant).collect();
+    let natural = NaturalCubicSpline::try_new_xy(&XS, &ys()).unwrap();
+    let catmull_rom = CatmullRomSpline::try_new_xy(&XS, &ys()).unwrap();
+    for x in [0.2, 0.9, 1.7, 2.6] {
+        let query = Dual::constant(x);
+        let natural_gradient = natural.value_gradient(x).unwrap();
+        let catmull_rom_gradient = catmull_rom.value_gradient(x).unwrap();
+        for i in 0..XS.len() {
+            let dual = NaturalCubicSpline::try_new_xy(&xs, &seeded(&ys(), i)).unwrap();
+            assert!((dual.try_value(query).unwrap().eps - natural_gradient[i]).abs() < 1e-13);
+            let dual = CatmullRomSpline::try_new_xy(&xs, &seeded(&ys(), i)).unwrap();
+            assert!((dual.try_value(query).unwrap().eps - catmull_rom_gradient[i]).abs() < 1e-13);
+        }
+        // the slopes of a Hermite spline are inputs too
+        let dydxs: Vec<f64> = XS.iter().map(|x| x.cos()).collect();
+        let dual = HermiteSpline::try_new_xy(&xs, &seeded(&ys(), 0), &seeded(&dydxs, 1)).unwrap();
+        let bumped = HermiteSpline::try_new_xy(
+            &XS,
+            &ys(),
+            &[dydxs[0], dydxs[1] + 1e-6, dydxs[2], dydxs[3], dydxs[4]],
+        )
+        .unwrap();
+        let hermite = HermiteSpline::try_new_xy(&XS, &ys(), &dydxs).unwrap();
+        let slope_sensitivity =
+            (bumped.try_value(x).unwrap() - hermite.try_value(x).unwrap()) / 1e-6;
+        let expected = hermite.value_gradient(x).unwrap()[0] + slope_sensitivity;
+        assert!((dual.try_value(query).unwrap().eps - expected).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,155 @@
+//! Locks the public surface of the crate: every item intended to be public is named here, so
+//! removing or moving one breaks this test, and the `ui` cases check what must stay impossible.
+
+#![allow(unused_imports)]
+
+use spline_interpolation::interpolation::binary::{BinaryFormatError, BinaryScalar};
+use spline_interpolation::interpolation::bundle::SplineBundle;
+use spline_interpolation::interpolation::catmull_rom_spline::CatmullRomSpline;
+#[cfg(feature = "complex")]
+use spline_interpolation::interpolation::complex::ComplexSpline;
+use spline_interpolation::interpolation::dedup::DedupPolicy;
+#[cfg(feature = "gp")]
+use spline_interpolation::interpolation::gp::{GaussianProcess, GpKernel};
+use spline_interpolation::interpolation::grid_2d::{
+    BicubicInterpolator, BicubicSpline2D, BilinearInterpolator,
+};
+use spline_interpolation::interpolation::grid_nd::InterpN;
+use spline_interpolation::interpolation::hermite_spline::HermiteSpline;
+use spline_interpolation::interpolation::interpolator::{Interpolator, MethodTag};
+#[cfg(feature = "io")]
+use spline_interpolation::interpolation::io::{read_csv_points, read_json_points, LoadError};
+use spline_interpolation::interpolation::natural_cubic_spline::NaturalCubicSpline;
+use spline_interpolation::interpolation::newton::NewtonPolynomial;
+use spline_interpolation::interpolation::partition::{
+    Density, DensityPartition, PartitionedSpline, Region, RegionMethod,
+};
+use spline_interpolation::interpolation::piecewise_polynomial::PiecewisePolynomial;
+use spline_interpolation::interpolation::rbf::{PolynomialTail, RbfInterpolator, RbfKernel};
+use spline_interpolation::interpolation::slopes::SlopeMethod;
+use spline_interpolation::interpolation::streaming::{
+    PointBuilder, StreamingCatmullRomSpline, StreamingHermiteSpline,
+};
+use spline_interpolation::interpolation::vector_field::{
+    VectorFieldInterpolator, VectorFieldKind, VectorSample,
+};
+#[cfg(feature = "linalg")]
+use spline_interpolation::math::banded_matrix::{BandedLU, BandedMatrix};
+#[cfg(feature = "linalg")]
+use spline_interpolation::math::cyclic_tridiagonal_matrix::{
+    CyclicTridiagonalLU, CyclicTridiagonalMatrix,
+};
+use spline_interpolation::math::tridiagonal_matrix::{
+    MatrixValidationError, TridiagonalLU, TridiagonalMatrix,
+};
+use spline_interpolation::metadata::Metadata;
+use spline_interpolation::tolerance::Tolerance;
+#[cfg(feature = "verification")]
+use spline_interpolation::verification::ReferenceCurve;
+#[allow(deprecated)]
+use spline_interpolation::HermiteSplineError;
+use spline_interpolation::{InterpolationError, InterpolationValue, TranscendentalValue};
+
+type Result<T> = std::result::Result<T, InterpolationError<f64>>;
+
+fn constructor<P, T>(_: fn(&[P]) -> Result<T>) {}
+
+#[test]
+fn test_constructor_signatures() {
+    constructor::<(f64, f64, f64), HermiteSpline<f64>>(HermiteSpline::try_new);
+    constructor::<(f64, f64), NaturalCubicSpline<f64>>(NaturalCubicSpline::try_new);
+    constructor::<(f64, f64), CatmullRomSpline<f64>>(CatmullRomSpline::try_new);
+    let _: fn(&NaturalCubicSpline<f64>, f64) -> Result<f64> = NaturalCubicSpline::try_value;
+    let _: fn(&dyn Interpolator<f64>, f64) -> Result<f64> = |curve, x| curve.try_value(x);
+}
+
+#[test]
+fn test_prelude_matches_modules() {
+    use spline_interpolation::prelude as p;
+    fn same<T>(_: fn() -> Option<T>, _: fn() -> Option<T>) {}
+    same::<NaturalCubicSpline<f64>>(|| None, || None::<p::NaturalCubicSpline<f64>>);
+    same::<HermiteSpline<f64>>(|| None, || None::<p::HermiteSpline<f64>>);
+    same::<CatmullRomSpline<f64>>(|| None, || None::<p::CatmullRomSpline<f64>>);
+    same::<InterpolationError<f64>>(|| None, || None::<p::InterpolationError<f64>>);
+    same::<Metadata>(|| None, || None::<p::Metadata>);
+}
+
+#[test]
+fn test_root_reexports_match_modules() {
+    fn same<T>(_: fn() -> Option<T>, _: fn() -> Option<T>) {}
+    same::<NaturalCubicSpline<f64>>(
+        || None,
+        || None::<spline_interpolation::NaturalCubicSpline<f64>>,
+    );
+    same::<HermiteSpline<f64>>(|| None, || None::<spline_interpolation::HermiteSpline<f64>>);
+    same::<CatmullRomSpline<f64>>(
+        || None,
+        || None::<spline_interpolation::CatmullRomSpline<f64>>,
+    );
+    same::<PiecewisePolynomial<f64>>(
+        || None,
+        || None::<spline_interpolation::PiecewisePolynomial<f64>>,
+    );
+    let _: fn(&dyn spline_interpolation::Interpolator<f64>) -> &dyn Interpolator<f64> = |c| c;
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_error_alias() {
+    let error: HermiteSplineError<f64> = InterpolationError::PointOrderError(2);
+    assert_eq!(error.to_string(), "point 2 is out of order");
+}
+
+#[test]
+fn test_thread_safety() {
+    use spline_interpolation::prelude::*;
+    use std::sync::Arc;
+    fn send_sync<T: Send + Sync>() {}
+    send_sync::<NaturalCubicSpline<f64>>();
+    send_sync::<HermiteSpline<f64>>();
+    send_sync::<CatmullRomSpline<f64>>();
+    send_sync::<SmoothingSpline<f64>>();
+    send_sync::<PiecewisePolynomial<f64>>();
+    send_sync::<PartitionedSpline<f64>>();
+    send_sync::<CompositeInterpolator<f64>>();
+    send_sync::<Extrapolated<f64>>();
+    send_sync::<SplineBundle<f64>>();
+    send_sync::<BicubicSpline2D<f64>>();
+    send_sync::<InterpN<f64, 3>>();
+    send_sync::<ParametricCurve<f64, 2>>();
+    send_sync::<RbfInterpolator<f64, 2>>();
+    send_sync::<StreamingHermiteSpline<f64>>();
+    send_sync::<StreamingCatmullRomSpline<f64>>();
+    send_sync::<Box<dyn Interpolator<f64>>>();
+    send_sync::<InterpolationError<f64>>();
+
+    // a calibrated curve shared by a pool of pricing threads
+    let points: Vec<(f64, f64)> = (0..20).map(|i| (i as f64, (i as f64).sqrt())).collect();
+    let curve: Arc<dyn Interpolator<f64>> = Arc::new(NaturalCubicSpline::try_new(&points).unwrap());
+    let expected: Vec<f64> = (0..190)
+        .map(|i| curve.try_value(i as f64 / 10.0).unwrap())
+        .collect();
+    std::thread::scope(|scope| {
+        for offset in 0..4 {
+            let (curve, expected) = (Arc::clone(&curve), &expected);
+            scope.spawn(move || {
+                for i in (offset..190).step_by(4) {
+                    assert_eq!(curve.try_value(i as f64 / 10.0).unwrap(), expected[i]);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn test_compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
+
+// the diagnostics list the implementors of the sealed trait, which the `gp` feature adds to, so
+// these cases run with the default features, as a separate CI step
+#[cfg(not(feature = "gp"))]
+#[test]
+fn test_compile_fail_default_features() {
+    trybuild::TestCases::new().compile_fail("tests/ui/default_features/*.rs");
+}
@@ -0,0 +1,25 @@
+use spline_interpolation::interpolation::interpolator::{Interpolator, MethodTag};
+use spline_interpolation::InterpolationError;
+use std::any::Any;
+
+struct Constant(f64);
+
+impl Interpolator<f64> for Constant {
+    fn try_value(&self, _x: f64) -> Result<f64, InterpolationError<f64>> {
+        Ok(self.0)
+    }
+
+    fn method(&self) -> MethodTag {
+        MethodTag::PiecewisePolynomial
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,3 @@
+use spline_interpolation::math::dense_matrix;
+
+fn main() {}
@@ -0,0 +1,3 @@
+use spline_interpolation::natural_cubic_spline::NaturalCubicSpline;
+
+fn main() {}